@@ -10,6 +10,10 @@ use anchor_client::solana_sdk::{
     commitment_config::CommitmentConfig, pubkey::Pubkey, signer::keypair::Keypair,
 };
 use clap::{Parser, Subcommand};
+use merkle_tree::{
+    friendly_csv_entry::{parse_duration, parse_timestamp},
+    schedule_builder::ScheduleBuilder,
+};
 
 use crate::instructions::*;
 #[derive(Parser, Debug)]
@@ -61,10 +65,25 @@ pub enum Commands {
     InitializeLockEscrowFromFile(InitializeLockEscrowFromFileArgs),
     /// Create merkle tree and print root
     CreateMerkleTree(CreateMerkleTreeArgs),
+    /// Validate every row of a csv and report errors with line numbers
+    ValidateCsv(ValidateCsvArgs),
+    /// Convert a csv with dates, durations and decimal amounts into a csv of raw values
+    NormalizeCsv(NormalizeCsvArgs),
+    /// Expand a csv of total amounts into a csv of vesting parameters with a schedule
+    GenerateScheduleCsv(GenerateScheduleCsvArgs),
+    /// Create vesting escrow of a total amount with a schedule
+    CreateVestingEscrowFromSchedule(CreateVestingEscrowFromScheduleArgs),
+    /// Compare two merkle trees and report added, removed and changed leaves as json
+    DiffMerkleTree(DiffMerkleTreeArgs),
+    /// Create merkle tree of the next version, without the recipients served by the root escrow
+    /// of the prior version
+    CreateNextMerkleTree(CreateNextMerkleTreeArgs),
     /// Generate test lock
     GenerateTestLock(GenerateTestLockArgs),
     /// Create root escrow
     CreateRootEscrow(CreateRootEscrowArgs),
+    /// Rebuild merkle tree from csv and compare its root to the root escrow on-chain
+    VerifyRoot(VerifyRootArgs),
     /// Verify root escrow
     VerifyRootEscrow(VerifyRootEscrowArgs),
     /// Fund root escrow
@@ -111,6 +130,88 @@ pub struct GenerateTestLockArgs {
     #[clap(long, env)]
     pub num_node: u64,
 }
+#[derive(Parser, Debug)]
+pub struct ValidateCsvArgs {
+    /// CSV path
+    #[clap(long, env)]
+    pub csv_path: PathBuf,
+}
+
+#[derive(Parser, Debug)]
+pub struct NormalizeCsvArgs {
+    /// CSV path, with dates, durations and decimal token amounts
+    #[clap(long, env)]
+    pub csv_path: PathBuf,
+    /// Normalized CSV out path
+    #[clap(long, env)]
+    pub out_path: PathBuf,
+    /// Decimals of rows without mint, rows with a mint need its mint file
+    #[clap(long, env)]
+    pub decimals: Option<u8>,
+    /// Mint account saved with `solana account <MINT> --output json`, can be repeated
+    #[clap(long)]
+    pub mint_file: Vec<PathBuf>,
+}
+
+#[derive(Parser, Debug)]
+pub struct ScheduleArgs {
+    /// Vesting start time, a date, a date time or a unix timestamp
+    #[clap(long, env, value_parser = parse_timestamp)]
+    pub vesting_start_time: u64,
+    /// Duration between vesting start and cliff, e.g. `12mo`
+    #[clap(long, env, value_parser = parse_duration, default_value = "0")]
+    pub cliff_duration: u64,
+    /// Basis points of the total amount unlocked at cliff
+    #[clap(long, env, default_value = "0")]
+    pub cliff_bps: u16,
+    /// Vesting duration after cliff, e.g. `36mo`
+    #[clap(long, env, value_parser = parse_duration)]
+    pub duration: u64,
+    /// Duration of a period, e.g. `1mo`
+    #[clap(long, env, value_parser = parse_duration)]
+    pub frequency: u64,
+    /// update recipient mode
+    #[clap(long, env, default_value = "0")]
+    pub update_recipient_mode: u8,
+    /// cancel mode
+    #[clap(long, env, default_value = "0")]
+    pub cancel_mode: u8,
+}
+
+impl ScheduleArgs {
+    pub fn get_schedule_builder(&self, total_amount: u64) -> ScheduleBuilder {
+        ScheduleBuilder::new(total_amount, self.duration, self.frequency)
+            .with_vesting_start_time(self.vesting_start_time)
+            .with_cliff(self.cliff_duration, self.cliff_bps)
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct GenerateScheduleCsvArgs {
+    /// CSV path, with `recipient`, `total_amount` and optional `mint` columns
+    #[clap(long, env)]
+    pub csv_path: PathBuf,
+    /// CSV out path, input of create-merkle-tree
+    #[clap(long, env)]
+    pub out_path: PathBuf,
+    #[clap(flatten)]
+    pub schedule: ScheduleArgs,
+}
+
+#[derive(Parser, Debug)]
+pub struct CreateVestingEscrowFromScheduleArgs {
+    #[clap(long, env)]
+    pub recipient: Pubkey,
+    /// token mint
+    #[clap(long, env)]
+    pub token_mint: Pubkey,
+    /// Total amount in base units
+    #[clap(long, env)]
+    pub total_amount: u64,
+    #[clap(flatten)]
+    pub schedule: ScheduleArgs,
+}
+
 #[derive(Parser, Debug)]
 pub struct CreateMerkleTreeArgs {
     /// CSV path
@@ -120,6 +221,70 @@ pub struct CreateMerkleTreeArgs {
     /// Merkle tree out path
     #[clap(long, env)]
     pub merkle_tree_path: PathBuf,
+
+    /// Use the position of leaves as leaf index, required by root escrow in bitmap mode
+    #[clap(long, env)]
+    pub leaf_position: bool,
+
+    /// Build the merkle tree with bounded memory and write proofs to json lines shards instead
+    /// of merkle_tree.json, for csv with millions of entries
+    #[clap(long, env)]
+    pub streaming: bool,
+
+    /// Number of proofs in a json lines shard of a streamed merkle tree
+    #[clap(long, env, default_value = "10000")]
+    pub shard_size: u64,
+
+    /// Sort leaves by recipient pubkey, so the root doesn't depend on the order of csv rows
+    #[clap(long, env, conflicts_with = "streaming")]
+    pub canonical: bool,
+
+    /// Write merkle_tree.bin in the binary proof file format instead of json
+    #[clap(long, env)]
+    pub binary: bool,
+
+    /// Write proofs to one json shard per first byte of recipient pubkey with a manifest, to be
+    /// served by a static site
+    #[clap(long, env, conflicts_with = "binary")]
+    pub prefix_shards: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct DiffMerkleTreeArgs {
+    /// Merkle tree of the prior version
+    #[clap(long, env)]
+    pub old_merkle_tree_path: PathBuf,
+    /// Merkle tree of the next version
+    #[clap(long, env)]
+    pub new_merkle_tree_path: PathBuf,
+    /// Report out path, the report is printed if not set
+    #[clap(long, env)]
+    pub out_path: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug)]
+pub struct CreateNextMerkleTreeArgs {
+    /// CSV path of the next version
+    #[clap(long, env)]
+    pub csv_path: PathBuf,
+    /// Merkle tree of the prior version
+    #[clap(long, env)]
+    pub prior_merkle_tree_path: PathBuf,
+    /// Merkle tree out path
+    #[clap(long, env)]
+    pub merkle_tree_path: PathBuf,
+    /// Base of the root escrow of the prior version
+    #[clap(long, env)]
+    pub base: Pubkey,
+    /// token mint
+    #[clap(long, env)]
+    pub mint: Pubkey,
+    /// Version of the next merkle tree, defaults to the prior version + 1
+    #[clap(long, env)]
+    pub version: Option<u64>,
+    /// The root escrow is a multi mint root escrow distributing every mint of the merkle tree
+    #[clap(long, env)]
+    pub multi_mint: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -135,6 +300,41 @@ pub struct CreateRootEscrowArgs {
     /// token mint
     #[clap(long, env)]
     pub mint: Pubkey,
+    /// timestamp after which creator can claw back undistributed tokens, 0 to disable clawback
+    #[clap(long, env, default_value = "0")]
+    pub clawback_deadline: u64,
+    /// 0 to create a vesting escrow for each leaf, 1 to let recipients claim from root escrow,
+    /// 2 to let recipients claim fully unlocked leaves tracked by claim bitmaps
+    #[clap(long, env, default_value = "0")]
+    pub distribution_mode: u8,
+    /// The root escrow is a multi mint root escrow distributing every mint of the merkle tree
+    #[clap(long, env)]
+    pub multi_mint: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct VerifyRootArgs {
+    /// CSV path
+    #[clap(long, env)]
+    pub csv_path: PathBuf,
+    /// Version of the root escrow
+    #[clap(long, env, default_value = "0")]
+    pub version: u64,
+    /// The merkle tree was created with leaf positions
+    #[clap(long, env)]
+    pub leaf_position: bool,
+    /// The merkle tree was created in canonical order
+    #[clap(long, env)]
+    pub canonical: bool,
+
+    #[clap(long, env)]
+    pub base: Pubkey,
+    /// token mint
+    #[clap(long, env)]
+    pub mint: Pubkey,
+    /// The root escrow is a multi mint root escrow distributing every mint of the merkle tree
+    #[clap(long, env)]
+    pub multi_mint: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -151,6 +351,9 @@ pub struct VerifyRootEscrowArgs {
     /// token mint
     #[clap(long, env)]
     pub mint: Pubkey,
+    /// The root escrow is a multi mint root escrow distributing every mint of the merkle tree
+    #[clap(long, env)]
+    pub multi_mint: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -164,6 +367,13 @@ pub struct FundRootEscrowArgs {
     /// token mint
     #[clap(long, env)]
     pub mint: Pubkey,
+    /// lamports deposited to the rent pool paying rent of escrows created from root escrow, only
+    /// for single mint root escrow
+    #[clap(long, env, default_value = "0")]
+    pub rent_deposit: u64,
+    /// The root escrow is a multi mint root escrow distributing every mint of the merkle tree
+    #[clap(long, env)]
+    pub multi_mint: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -177,6 +387,9 @@ pub struct CreateVestingEcrowFromRootArgs {
     /// token mint
     #[clap(long, env)]
     pub mint: Pubkey,
+    /// The root escrow is a multi mint root escrow distributing every mint of the merkle tree
+    #[clap(long, env)]
+    pub multi_mint: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -190,6 +403,9 @@ pub struct VerifyAllEscrowCreatedArgs {
     /// token mint
     #[clap(long, env)]
     pub mint: Pubkey,
+    /// The root escrow is a multi mint root escrow distributing every mint of the merkle tree
+    #[clap(long, env)]
+    pub multi_mint: bool,
 }
 
 fn main() {
@@ -200,8 +416,19 @@ fn main() {
             process_initialize_lock_escrow_from_file(&args, sub_args)
         }
         Commands::CreateMerkleTree(sub_args) => process_create_merkle_tree(sub_args),
+        Commands::ValidateCsv(sub_args) => process_validate_csv(sub_args),
+        Commands::NormalizeCsv(sub_args) => process_normalize_csv(sub_args),
+        Commands::GenerateScheduleCsv(sub_args) => process_generate_schedule_csv(sub_args),
+        Commands::CreateVestingEscrowFromSchedule(sub_args) => {
+            process_create_vesting_escrow_from_schedule(&args, sub_args)
+        }
+        Commands::DiffMerkleTree(sub_args) => process_diff_merkle_tree(sub_args),
+        Commands::CreateNextMerkleTree(sub_args) => {
+            process_create_next_merkle_tree(&args, sub_args)
+        }
         Commands::GenerateTestLock(subg_args) => process_generate_test_lock(subg_args),
         Commands::CreateRootEscrow(sub_args) => process_create_root_escrow(&args, sub_args),
+        Commands::VerifyRoot(sub_args) => process_verify_root(&args, sub_args),
         Commands::VerifyRootEscrow(sub_args) => process_verify_root_escrow(&args, sub_args),
         Commands::FundRootEscrow(sub_args) => process_fund_root_escrow(&args, sub_args),
         Commands::CreateVestingEcrowFromRoot(sub_args) => {
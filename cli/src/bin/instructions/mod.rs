@@ -14,3 +14,17 @@ pub mod process_create_vesting_escrow_from_root;
 pub use process_create_vesting_escrow_from_root::*;
 pub mod process_verify_all_escrow_created;
 pub use process_verify_all_escrow_created::*;
+pub mod process_validate_csv;
+pub use process_validate_csv::*;
+pub mod process_normalize_csv;
+pub use process_normalize_csv::*;
+pub mod process_generate_schedule_csv;
+pub use process_generate_schedule_csv::*;
+pub mod process_create_vesting_escrow_from_schedule;
+pub use process_create_vesting_escrow_from_schedule::*;
+pub mod process_diff_merkle_tree;
+pub use process_diff_merkle_tree::*;
+pub mod process_create_next_merkle_tree;
+pub use process_create_next_merkle_tree::*;
+pub mod process_verify_root;
+pub use process_verify_root::*;
@@ -1,49 +1,95 @@
 use crate::*;
-use locker::CreateVestingEscrowParameters;
+use merkle_tree::binary_proof_file::BinaryProofFileWriter;
 use merkle_tree::csv_entry::CsvEntry;
-use merkle_tree::jup_lock_merkle_tree::JupLockMerkleTree;
-use std::collections::HashMap;
+use merkle_tree::jup_lock_merkle_tree::{sort_canonical, JupLockMerkleTree};
+use merkle_tree::proof_shards::PrefixShardProofSink;
+use merkle_tree::streaming_merkle_tree::{JsonLinesProofSink, StreamingMerkleTreeBuilder};
+use merkle_tree::tree_node::TreeNode;
 use std::fs;
 
-fn validate_csv_file(csv_entries: &Vec<CsvEntry>) {
-    // no duplicate
-    let mut hash_recipient = HashMap::new();
-    for val in csv_entries.iter() {
-        if hash_recipient.get(&val.recipient).is_some() {
-            panic!("{} is duplicated", val.recipient.to_string());
-        }
-        let params = CreateVestingEscrowParameters {
-            vesting_start_time: val.vesting_start_time,
-            cliff_time: val.cliff_time,
-            frequency: val.frequency,
-            cliff_unlock_amount: val.cliff_unlock_amount,
-            amount_per_period: val.amount_per_period,
-            number_of_period: val.number_of_period,
-            update_recipient_mode: val.update_recipient_mode,
-            cancel_mode: val.cancel_mode,
-        };
-        if params.validate().is_err() {
-            panic!("{} is invalid params", val.recipient.to_string());
-        }
-        if params.get_total_deposit_amount().unwrap() == 0 {
-            panic!("{} is invalid params", val.recipient.to_string());
-        }
-        hash_recipient.insert(&val.recipient, true);
+pub fn validate_csv_file(csv_path: &PathBuf) {
+    // a recipient can have several entries, each of them gets its own leaf index
+    let errors = CsvEntry::validate_file(csv_path).unwrap();
+    if !errors.is_empty() {
+        print_csv_errors(&errors);
+        panic!("{} is invalid", csv_path.display());
     }
 }
 
+/// Build a merkle tree from a csv, with leaves in canonical order if `canonical` is set
+pub fn create_merkle_tree_from_csv(
+    csv_path: &PathBuf,
+    version: u64,
+    leaf_position: bool,
+    canonical: bool,
+) -> JupLockMerkleTree {
+    let mut tree_nodes = CsvEntry::new_from_file(csv_path)
+        .unwrap()
+        .into_iter()
+        .map(TreeNode::from_csv)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    if canonical {
+        sort_canonical(&mut tree_nodes);
+    }
+    if leaf_position {
+        JupLockMerkleTree::new_with_leaf_positions(tree_nodes, version).unwrap()
+    } else {
+        JupLockMerkleTree::new(tree_nodes, version).unwrap()
+    }
+}
+
+fn process_create_streaming_merkle_tree(merkle_tree_args: &CreateMerkleTreeArgs) {
+    let base_path = &merkle_tree_args.merkle_tree_path;
+    fs::create_dir_all(base_path).unwrap();
+    let builder = StreamingMerkleTreeBuilder::new(&base_path.join("tmp"), 0)
+        .with_leaf_positions(merkle_tree_args.leaf_position);
+
+    let csv_path = &merkle_tree_args.csv_path;
+    if merkle_tree_args.binary {
+        let mut sink = BinaryProofFileWriter::new(&base_path.join("merkle_tree.bin")).unwrap();
+        builder.build_from_csv(csv_path, &mut sink).unwrap();
+    } else if merkle_tree_args.prefix_shards {
+        let mut sink = PrefixShardProofSink::new(base_path).unwrap();
+        builder.build_from_csv(csv_path, &mut sink).unwrap();
+    } else {
+        let mut sink = JsonLinesProofSink::new(base_path, merkle_tree_args.shard_size).unwrap();
+        builder.build_from_csv(csv_path, &mut sink).unwrap();
+    }
+    fs::remove_dir(base_path.join("tmp")).unwrap();
+}
+
 pub fn process_create_merkle_tree(merkle_tree_args: &CreateMerkleTreeArgs) {
-    let csv_entries = CsvEntry::new_from_file(&merkle_tree_args.csv_path).unwrap();
     // validate
-    validate_csv_file(&csv_entries);
+    validate_csv_file(&merkle_tree_args.csv_path);
+
+    if merkle_tree_args.streaming {
+        process_create_streaming_merkle_tree(merkle_tree_args);
+        return;
+    }
 
     // create merkle tree folder if not existed
     fs::create_dir_all(merkle_tree_args.merkle_tree_path.clone()).unwrap();
 
     // use index as 0
-    let merkle_tree = JupLockMerkleTree::new_from_entries(csv_entries, 0).unwrap();
+    let merkle_tree = create_merkle_tree_from_csv(
+        &merkle_tree_args.csv_path,
+        0,
+        merkle_tree_args.leaf_position,
+        merkle_tree_args.canonical,
+    );
 
     let base_path = &merkle_tree_args.merkle_tree_path;
+    if merkle_tree_args.binary {
+        let path = base_path.join("merkle_tree.bin");
+        merkle_tree.write_to_binary_file(&path).unwrap();
+        return;
+    }
+    if merkle_tree_args.prefix_shards {
+        let mut sink = PrefixShardProofSink::new(base_path).unwrap();
+        merkle_tree.write_to_sink(&mut sink).unwrap();
+        return;
+    }
     let base_path_clone = base_path.clone();
     let path = base_path_clone.as_path().join(format!("merkle_tree.json"));
 
@@ -0,0 +1,175 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::*;
+use anchor_client::solana_client::rpc_client::RpcClient;
+use locker::{DistributionMode, RootEscrow, RootEscrowClaimBitmap, LEAVES_PER_CLAIM_BITMAP};
+use merkle_tree::csv_entry::CsvEntry;
+use merkle_tree::jup_lock_merkle_tree::JupLockMerkleTree;
+use merkle_tree::tree_node::TreeNode;
+use merkle_tree::utils::{get_base_pda, get_multi_mint_root_escrow_pda, get_root_escrow_pda};
+use std::fs;
+
+/// Max number of accounts fetched by `getMultipleAccounts`
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+fn get_leaf_index_seed(leaf_index: Option<u64>) -> Vec<u8> {
+    leaf_index
+        .map(|leaf_index| leaf_index.to_le_bytes().to_vec())
+        .unwrap_or_default()
+}
+
+/// `base` marker created with the escrow of the leaf, in escrow mode. Unlike the escrow, which
+/// is closed once claimed, the marker is never closed
+fn get_base(
+    program_id: &Pubkey,
+    root_escrow: &Pubkey,
+    node: &TreeNode,
+    multi_mint: bool,
+) -> Pubkey {
+    let base_mint = if multi_mint { node.mint.as_ref() } else { None };
+    get_base_pda(
+        program_id,
+        root_escrow,
+        &node.recipient,
+        node.leaf_index,
+        base_mint,
+    )
+    .0
+}
+
+/// Claim status created at the first claim of the leaf, in direct claim mode
+fn get_claim_status(program_id: &Pubkey, root_escrow: &Pubkey, node: &TreeNode) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"claim_status".as_ref(),
+            root_escrow.as_ref(),
+            node.recipient.as_ref(),
+            get_leaf_index_seed(node.leaf_index).as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// Leaves of the prior tree served by its root escrow
+fn get_served_nodes<'a>(
+    args: &Args,
+    root_escrow: &Pubkey,
+    distribution_mode: DistributionMode,
+    multi_mint: bool,
+    nodes: &[&'a TreeNode],
+) -> Vec<&'a TreeNode> {
+    let program = args.get_program_client();
+    let program_id = program.id();
+
+    if distribution_mode == DistributionMode::Bitmap {
+        let mut claim_bitmaps: HashMap<u64, Option<RootEscrowClaimBitmap>> = HashMap::new();
+        return nodes
+            .iter()
+            .filter(|node| {
+                let leaf_index = node.leaf_index.unwrap();
+                let chunk_index = leaf_index / LEAVES_PER_CLAIM_BITMAP;
+                let claim_bitmap = claim_bitmaps.entry(chunk_index).or_insert_with(|| {
+                    let (claim_bitmap, _bump) = Pubkey::find_program_address(
+                        &[
+                            b"claim_bitmap".as_ref(),
+                            root_escrow.as_ref(),
+                            chunk_index.to_le_bytes().as_ref(),
+                        ],
+                        &program_id,
+                    );
+                    program.account(claim_bitmap).ok()
+                });
+                claim_bitmap.as_ref().is_some_and(|claim_bitmap| {
+                    let offset = leaf_index % LEAVES_PER_CLAIM_BITMAP;
+                    claim_bitmap.bits[(offset / 64) as usize] & (1u64 << (offset % 64)) != 0
+                })
+            })
+            .cloned()
+            .collect();
+    }
+
+    let client =
+        RpcClient::new_with_commitment(args.rpc_url.clone(), CommitmentConfig::finalized());
+    let mut served_nodes = vec![];
+    for chunk in nodes.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let accounts: Vec<Pubkey> = chunk
+            .iter()
+            .map(|node| match distribution_mode {
+                DistributionMode::DirectClaim => get_claim_status(&program_id, root_escrow, node),
+                _ => get_base(&program_id, root_escrow, node, multi_mint),
+            })
+            .collect();
+        let accounts = client.get_multiple_accounts(&accounts).unwrap();
+        for (node, account) in chunk.iter().zip(accounts) {
+            if account.is_some() {
+                served_nodes.push(*node);
+            }
+        }
+    }
+    served_nodes
+}
+
+pub fn process_create_next_merkle_tree(args: &Args, sub_args: &CreateNextMerkleTreeArgs) {
+    validate_csv_file(&sub_args.csv_path);
+
+    let program = args.get_program_client();
+    let prior_tree = JupLockMerkleTree::new_from_file(&sub_args.prior_merkle_tree_path).unwrap();
+    let (root_escrow, _bump) = if sub_args.multi_mint {
+        get_multi_mint_root_escrow_pda(&program.id(), &sub_args.base, prior_tree.version)
+    } else {
+        get_root_escrow_pda(
+            &program.id(),
+            &sub_args.base,
+            &sub_args.mint,
+            prior_tree.version,
+        )
+    };
+    let root_escrow_state: RootEscrow = match program.account(root_escrow) {
+        Ok(root_escrow_state) => root_escrow_state,
+        Err(err) => {
+            println!(
+                "can't read root escrow {} of version {}, served leaves can't be found once it is closed: {}",
+                root_escrow, prior_tree.version, err
+            );
+            std::process::exit(1);
+        }
+    };
+    let distribution_mode =
+        DistributionMode::try_from(root_escrow_state.distribution_mode).unwrap();
+
+    let prior_nodes: Vec<&TreeNode> = prior_tree
+        .tree_nodes
+        .iter()
+        .filter(|n| n.is_for_mint(&sub_args.mint))
+        .collect();
+    let served: HashSet<(Pubkey, Option<Pubkey>, Option<u64>)> = get_served_nodes(
+        args,
+        &root_escrow,
+        distribution_mode,
+        sub_args.multi_mint,
+        &prior_nodes,
+    )
+    .iter()
+    .map(|n| n.get_leaf_key())
+    .collect();
+    println!(
+        "{} leaves are served by root escrow {} version {}",
+        served.len(),
+        root_escrow,
+        prior_tree.version
+    );
+
+    let tree_nodes = CsvEntry::new_from_file(&sub_args.csv_path)
+        .unwrap()
+        .into_iter()
+        .map(TreeNode::from_csv)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let version = sub_args.version.unwrap_or(prior_tree.version + 1);
+    let merkle_tree =
+        JupLockMerkleTree::new_excluding_served(tree_nodes, version, &served).unwrap();
+
+    fs::create_dir_all(&sub_args.merkle_tree_path).unwrap();
+    merkle_tree.write_to_file(&sub_args.merkle_tree_path.join("merkle_tree.json"));
+}
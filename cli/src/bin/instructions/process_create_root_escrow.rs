@@ -6,10 +6,15 @@ use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
 use anchor_client::solana_sdk::transaction::Transaction;
 use anchor_client::solana_sdk::{
     compute_budget::ComputeBudgetInstruction, instruction::Instruction, pubkey::Pubkey,
-    signature::read_keypair_file, signer::Signer,
+    signature::read_keypair_file, signature::Keypair, signer::Signer,
+};
+use locker::{
+    CreateMultiMintRootEscrowParameters, CreateRootEscrowParameters, DistributionMode, RootEscrow,
 };
-use locker::CreateRootEscrowParameters;
 use merkle_tree::jup_lock_merkle_tree::JupLockMerkleTree;
+use merkle_tree::utils::{
+    get_multi_mint_root_escrow_pda, get_root_escrow_mint_pda, get_root_escrow_pda,
+};
 
 pub fn process_create_root_escrow(args: &Args, sub_args: &CreateRootEscrowArgs) {
     let program = args.get_program_client();
@@ -18,6 +23,9 @@ pub fn process_create_root_escrow(args: &Args, sub_args: &CreateRootEscrowArgs)
 
     let merkle_tree = JupLockMerkleTree::new_from_file(&sub_args.merkle_tree_path).unwrap();
 
+    let (max_claim_amount, max_escrow) =
+        merkle_tree.get_max_claim_amount_and_escrow(&sub_args.mint);
+
     let mut ixs = vec![];
     // check priority fee
     if let Some(priority_fee) = args.priority_fee {
@@ -26,20 +34,78 @@ pub fn process_create_root_escrow(args: &Args, sub_args: &CreateRootEscrowArgs)
         ));
     }
 
-    let (root_escrow, _bump) = Pubkey::find_program_address(
-        &[
-            b"root_escrow".as_ref(),
-            base_kp.pubkey().as_ref(),
-            sub_args.mint.as_ref(),
-            merkle_tree.version.to_le_bytes().as_ref(),
-        ],
-        &program.id(),
-    );
-
     // println!("prpgram id {} {}", program.id(), locker::ID);
 
     let (event_authority, _bump) =
         Pubkey::find_program_address(&[b"__event_authority"], &program.id());
+
+    if sub_args.multi_mint {
+        // the root escrow is created with its first mint, next mints are added to it
+        let (root_escrow, _bump) =
+            get_multi_mint_root_escrow_pda(&program.id(), &base_kp.pubkey(), merkle_tree.version);
+        if program.account::<RootEscrow>(root_escrow).is_err() {
+            ixs.push(Instruction {
+                program_id: program.id(),
+                accounts: locker::accounts::CreateMultiMintRootEscrowCtx {
+                    base: base_kp.pubkey(),
+                    root_escrow,
+                    payer: program.payer(),
+                    creator: sub_args.creator,
+                    system_program: anchor_lang::solana_program::system_program::id(),
+                    event_authority,
+                    program: program.id(),
+                }
+                .to_account_metas(None),
+                data: locker::instruction::CreateMultiMintRootEscrow {
+                    params: CreateMultiMintRootEscrowParameters {
+                        max_escrow: merkle_tree.tree_nodes.len() as u64,
+                        version: merkle_tree.version,
+                        root: merkle_tree.merkle_root,
+                        clawback_deadline: sub_args.clawback_deadline,
+                        leaf_version: merkle_tree.leaf_version,
+                    },
+                }
+                .data(),
+            });
+        }
+
+        // creator signs for the mint of the root escrow
+        assert_eq!(sub_args.creator, program.payer());
+        let (root_escrow_mint, _bump) =
+            get_root_escrow_mint_pda(&program.id(), &root_escrow, &sub_args.mint);
+        ixs.push(Instruction {
+            program_id: program.id(),
+            accounts: locker::accounts::CreateRootEscrowMintCtx {
+                root_escrow,
+                root_escrow_mint,
+                token_mint: sub_args.mint,
+                creator: sub_args.creator,
+                system_program: anchor_lang::solana_program::system_program::id(),
+                event_authority,
+                program: program.id(),
+            }
+            .to_account_metas(None),
+            data: locker::instruction::CreateRootEscrowMint { max_claim_amount }.data(),
+        });
+        send_transaction(args, &ixs, &payer_kp, &base_kp);
+        return;
+    }
+
+    // claim bitmaps are indexed by leaf positions, a leaf index shared by recipients would be
+    // claimed once
+    if sub_args.distribution_mode == u8::from(DistributionMode::Bitmap) {
+        assert!(
+            merkle_tree.has_leaf_positions(),
+            "bitmap mode requires a single mint merkle tree created with --leaf-position"
+        );
+    }
+
+    let (root_escrow, _bump) = get_root_escrow_pda(
+        &program.id(),
+        &base_kp.pubkey(),
+        &sub_args.mint,
+        merkle_tree.version,
+    );
     ixs.push(Instruction {
         program_id: program.id(),
         accounts: locker::accounts::CreateRootEscrowCtx {
@@ -55,22 +121,28 @@ pub fn process_create_root_escrow(args: &Args, sub_args: &CreateRootEscrowArgs)
         .to_account_metas(None),
         data: locker::instruction::CreateRootEscrow {
             params: CreateRootEscrowParameters {
-                max_claim_amount: merkle_tree.max_claim_amount,
-                max_escrow: merkle_tree.max_escrow,
+                max_claim_amount,
+                max_escrow,
                 version: merkle_tree.version,
                 root: merkle_tree.merkle_root,
+                clawback_deadline: sub_args.clawback_deadline,
+                leaf_version: merkle_tree.leaf_version,
+                distribution_mode: sub_args.distribution_mode,
             },
         }
         .data(),
     });
+    send_transaction(args, &ixs, &payer_kp, &base_kp);
+}
 
+fn send_transaction(args: &Args, ixs: &[Instruction], payer_kp: &Keypair, base_kp: &Keypair) {
     let client =
         RpcClient::new_with_commitment(args.rpc_url.clone(), CommitmentConfig::finalized());
     let blockhash = client.get_latest_blockhash().unwrap();
     let tx = Transaction::new_signed_with_payer(
-        &ixs,
-        Some(&program.payer()),
-        &[&payer_kp, &base_kp],
+        ixs,
+        Some(&payer_kp.pubkey()),
+        &[payer_kp, base_kp],
         blockhash,
     );
 
@@ -13,9 +13,12 @@ use anchor_client::solana_sdk::{
 use anchor_spl::associated_token::get_associated_token_address;
 use anchor_spl::token::spl_token;
 use anyhow::Result;
-use locker::CreateVestingEscrowFromRootParams;
+use locker::CreateVestingEscrowFromRoot2Params;
 use merkle_tree::jup_lock_merkle_tree::JupLockMerkleTree;
 use merkle_tree::tree_node::TreeNode;
+use merkle_tree::utils::{
+    get_base_pda, get_multi_mint_root_escrow_pda, get_root_escrow_mint_pda, get_root_escrow_pda,
+};
 
 pub fn process_create_vesting_escrow_from_root(
     args: &Args,
@@ -26,17 +29,22 @@ pub fn process_create_vesting_escrow_from_root(
 
     let merkle_tree = JupLockMerkleTree::new_from_file(&sub_args.merkle_tree_path).unwrap();
 
-    let (root_escrow, _bump) = Pubkey::find_program_address(
-        &[
-            b"root_escrow".as_ref(),
-            sub_args.base.as_ref(),
-            sub_args.mint.as_ref(),
-            merkle_tree.version.to_le_bytes().as_ref(),
-        ],
-        &program.id(),
-    );
+    let (root_escrow, _bump) = if sub_args.multi_mint {
+        get_multi_mint_root_escrow_pda(&program.id(), &sub_args.base, merkle_tree.version)
+    } else {
+        get_root_escrow_pda(
+            &program.id(),
+            &sub_args.base,
+            &sub_args.mint,
+            merkle_tree.version,
+        )
+    };
 
-    for node in merkle_tree.tree_nodes.iter() {
+    for node in merkle_tree
+        .tree_nodes
+        .iter()
+        .filter(|n| n.is_for_mint(&sub_args.mint))
+    {
         if create_vesting_escrow_from_root(args, sub_args, &payer_kp, root_escrow, node).is_err() {
             println!("{} is failed", node.recipient);
         }
@@ -55,11 +63,14 @@ fn create_vesting_escrow_from_root(
         RpcClient::new_with_commitment(args.rpc_url.clone(), CommitmentConfig::finalized());
     let recipient = node.recipient;
 
-    let (base, _bump) = Pubkey::find_program_address(
-        &[b"base".as_ref(), root_escrow.as_ref(), recipient.as_ref()],
+    let base_mint = sub_args.multi_mint.then_some(&sub_args.mint);
+    let (base, _bump) = get_base_pda(
         &program.id(),
+        &root_escrow,
+        &recipient,
+        node.leaf_index,
+        base_mint,
     );
-
     let (escrow, _bump) =
         Pubkey::find_program_address(&[b"escrow".as_ref(), base.as_ref()], &program.id());
 
@@ -81,40 +92,74 @@ fn create_vesting_escrow_from_root(
 
     let (event_authority, _bump) =
         Pubkey::find_program_address(&[b"__event_authority"], &program.id());
-    ixs.push(Instruction {
-        program_id: program.id(),
-        accounts: locker::accounts::CreateVestingEscrowFromRootCtx {
-            root_escrow,
-            base,
-            escrow,
-            escrow_token: get_associated_token_address(&escrow, &sub_args.mint),
-            root_escrow_token: get_associated_token_address(&root_escrow, &sub_args.mint),
-            token_mint: sub_args.mint,
-            payer: program.payer(),
-            recipient,
-            associated_token_program: spl_associated_token_account::ID,
-            token_program: spl_token::ID,
-            system_program: anchor_lang::solana_program::system_program::id(),
-            event_authority,
-            program: program.id(),
-        }
-        .to_account_metas(None),
-        data: locker::instruction::CreateVestingEscrowFromRoot {
-            params: CreateVestingEscrowFromRootParams {
-                vesting_start_time: node.vesting_start_time,
-                cliff_time: node.cliff_time,
-                frequency: node.frequency,
-                cliff_unlock_amount: node.cliff_unlock_amount,
-                amount_per_period: node.amount_per_period,
-                number_of_period: node.number_of_period,
-                update_recipient_mode: node.update_recipient_mode,
-                cancel_mode: node.cancel_mode,
-            },
-            proof: node.proof.clone().unwrap(),
-            remaining_accounts_info: None,
-        }
-        .data(),
-    });
+    let params = CreateVestingEscrowFromRoot2Params {
+        vesting_start_time: node.vesting_start_time,
+        cliff_time: node.cliff_time,
+        frequency: node.frequency,
+        cliff_unlock_amount: node.cliff_unlock_amount,
+        amount_per_period: node.amount_per_period,
+        number_of_period: node.number_of_period,
+        update_recipient_mode: node.update_recipient_mode,
+        cancel_mode: node.cancel_mode,
+        leaf_index: node.leaf_index,
+    };
+    let proof = node.proof.clone().unwrap();
+    if sub_args.multi_mint {
+        let (root_escrow_mint, _bump) =
+            get_root_escrow_mint_pda(&program.id(), &root_escrow, &sub_args.mint);
+        ixs.push(Instruction {
+            program_id: program.id(),
+            accounts: locker::accounts::CreateVestingEscrowFromMultiMintRootCtx {
+                root_escrow,
+                root_escrow_mint,
+                base,
+                escrow,
+                escrow_token: get_associated_token_address(&escrow, &sub_args.mint),
+                root_escrow_token: get_associated_token_address(&root_escrow, &sub_args.mint),
+                token_mint: sub_args.mint,
+                payer: program.payer(),
+                recipient,
+                associated_token_program: spl_associated_token_account::ID,
+                token_program: spl_token::ID,
+                system_program: anchor_lang::solana_program::system_program::id(),
+                event_authority,
+                program: program.id(),
+            }
+            .to_account_metas(None),
+            data: locker::instruction::CreateVestingEscrowFromMultiMintRoot {
+                params,
+                proof,
+                remaining_accounts_info: None,
+            }
+            .data(),
+        });
+    } else {
+        ixs.push(Instruction {
+            program_id: program.id(),
+            accounts: locker::accounts::CreateVestingEscrowFromRoot2Ctx {
+                root_escrow,
+                base,
+                escrow,
+                escrow_token: get_associated_token_address(&escrow, &sub_args.mint),
+                root_escrow_token: get_associated_token_address(&root_escrow, &sub_args.mint),
+                token_mint: sub_args.mint,
+                payer: program.payer(),
+                recipient,
+                associated_token_program: spl_associated_token_account::ID,
+                token_program: spl_token::ID,
+                system_program: anchor_lang::solana_program::system_program::id(),
+                event_authority,
+                program: program.id(),
+            }
+            .to_account_metas(None),
+            data: locker::instruction::CreateVestingEscrowFromRootV2 {
+                params,
+                proof,
+                remaining_accounts_info: None,
+            }
+            .data(),
+        });
+    }
 
     let blockhash = client.get_latest_blockhash()?;
     let tx =
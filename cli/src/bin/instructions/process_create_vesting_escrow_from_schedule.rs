@@ -0,0 +1,120 @@
+use crate::*;
+use anchor_client::anchor_lang::InstructionData;
+use anchor_client::anchor_lang::ToAccountMetas;
+use anchor_client::solana_client::rpc_client::RpcClient;
+use anchor_client::solana_sdk::transaction::Transaction;
+use anchor_client::solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, signer::Signer,
+};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anyhow::Result;
+use locker::CreateVestingEscrowParameters;
+
+pub fn process_create_vesting_escrow_from_schedule(
+    args: &Args,
+    sub_args: &CreateVestingEscrowFromScheduleArgs,
+) {
+    let schedule = sub_args
+        .schedule
+        .get_schedule_builder(sub_args.total_amount)
+        .build()
+        .unwrap();
+    println!("create vesting escrow with schedule: {schedule:#?}");
+    create_vesting_escrow_from_schedule(
+        args,
+        sub_args,
+        CreateVestingEscrowParameters {
+            vesting_start_time: schedule.vesting_start_time,
+            cliff_time: schedule.cliff_time,
+            frequency: schedule.frequency,
+            cliff_unlock_amount: schedule.cliff_unlock_amount,
+            amount_per_period: schedule.amount_per_period,
+            number_of_period: schedule.number_of_period,
+            update_recipient_mode: sub_args.schedule.update_recipient_mode,
+            cancel_mode: sub_args.schedule.cancel_mode,
+        },
+    )
+    .unwrap();
+}
+
+fn create_vesting_escrow_from_schedule(
+    args: &Args,
+    sub_args: &CreateVestingEscrowFromScheduleArgs,
+    params: CreateVestingEscrowParameters,
+) -> Result<()> {
+    let program = args.get_program_client();
+    let client =
+        RpcClient::new_with_commitment(args.rpc_url.clone(), CommitmentConfig::finalized());
+    let keypair = read_keypair_file(args.keypair_path.clone().unwrap()).unwrap();
+    let token_mint = sub_args.token_mint;
+    // spl token or token 2022
+    let token_program = client.get_account(&token_mint)?.owner;
+
+    let mut ixs = vec![];
+    // check priority fee
+    if let Some(priority_fee) = args.priority_fee {
+        ixs.push(ComputeBudgetInstruction::set_compute_unit_price(
+            priority_fee,
+        ));
+    }
+
+    let base_kp = Keypair::new();
+    let (escrow, _bump) = Pubkey::find_program_address(
+        &[b"escrow".as_ref(), base_kp.pubkey().as_ref()],
+        &program.id(),
+    );
+    let escrow_token =
+        get_associated_token_address_with_program_id(&escrow, &token_mint, &token_program);
+
+    ixs.push(
+        spl_associated_token_account::instruction::create_associated_token_account(
+            &keypair.pubkey(),
+            &escrow,
+            &token_mint,
+            &token_program,
+        ),
+    );
+    let (event_authority, _bump) =
+        Pubkey::find_program_address(&[b"__event_authority"], &program.id());
+    ixs.push(Instruction {
+        program_id: program.id(),
+        accounts: locker::accounts::CreateVestingEscrow2Ctx {
+            base: base_kp.pubkey(),
+            escrow,
+            token_mint,
+            escrow_token,
+            sender: keypair.pubkey(),
+            sender_token: get_associated_token_address_with_program_id(
+                &keypair.pubkey(),
+                &token_mint,
+                &token_program,
+            ),
+            recipient: sub_args.recipient,
+            token_program,
+            system_program: anchor_lang::solana_program::system_program::id(),
+            event_authority,
+            program: program.id(),
+        }
+        .to_account_metas(None),
+        data: locker::instruction::CreateVestingEscrowV2 {
+            params,
+            remaining_accounts_info: None,
+        }
+        .data(),
+    });
+
+    let blockhash = client.get_latest_blockhash()?;
+    let tx = Transaction::new_signed_with_payer(
+        &ixs,
+        Some(&keypair.pubkey()),
+        &[&keypair, &base_kp],
+        blockhash,
+    );
+    let signature = client.send_and_confirm_transaction_with_spinner(&tx)?;
+
+    println!(
+        "successfully create vesting escrow {} for {} with signature {:?}",
+        escrow, sub_args.recipient, signature
+    );
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use crate::*;
+use merkle_tree::jup_lock_merkle_tree::JupLockMerkleTree;
+use merkle_tree::merkle_tree_diff::MerkleTreeDiff;
+use std::fs::File;
+
+pub fn process_diff_merkle_tree(sub_args: &DiffMerkleTreeArgs) {
+    let old_tree = JupLockMerkleTree::new_from_file(&sub_args.old_merkle_tree_path).unwrap();
+    let new_tree = JupLockMerkleTree::new_from_file(&sub_args.new_merkle_tree_path).unwrap();
+    let diff = MerkleTreeDiff::new(&old_tree, &new_tree);
+
+    // keep stdout for the report when no out path is given
+    eprintln!(
+        "version {} -> {}: {} added, {} removed, {} changed, {} unchanged",
+        diff.old_version,
+        diff.new_version,
+        diff.added.len(),
+        diff.removed.len(),
+        diff.changed.len(),
+        diff.unchanged
+    );
+    match &sub_args.out_path {
+        Some(out_path) => {
+            let file = File::create(out_path).unwrap();
+            serde_json::to_writer_pretty(file, &diff).unwrap();
+        }
+        None => println!("{}", serde_json::to_string_pretty(&diff).unwrap()),
+    }
+}
@@ -11,8 +11,11 @@ use anchor_client::solana_sdk::{
 };
 use anchor_spl::associated_token::get_associated_token_address;
 use anchor_spl::token::spl_token;
-use locker::RootEscrow;
+use locker::{RootEscrow, RootEscrowMint};
 use merkle_tree::jup_lock_merkle_tree::JupLockMerkleTree;
+use merkle_tree::utils::{
+    get_multi_mint_root_escrow_pda, get_root_escrow_mint_pda, get_root_escrow_pda,
+};
 
 pub fn process_fund_root_escrow(args: &Args, sub_args: &FundRootEscrowArgs) {
     let program = args.get_program_client();
@@ -28,48 +31,85 @@ pub fn process_fund_root_escrow(args: &Args, sub_args: &FundRootEscrowArgs) {
         ));
     }
 
-    let (root_escrow, _bump) = Pubkey::find_program_address(
-        &[
-            b"root_escrow".as_ref(),
-            sub_args.base.as_ref(),
-            sub_args.mint.as_ref(),
-            merkle_tree.version.to_le_bytes().as_ref(),
-        ],
-        &program.id(),
-    );
+    let (event_authority, _bump) =
+        Pubkey::find_program_address(&[b"__event_authority"], &program.id());
+    if sub_args.multi_mint {
+        assert!(
+            sub_args.rent_deposit == 0,
+            "rent deposit is only supported for single mint root escrow"
+        );
+        let (root_escrow, _bump) =
+            get_multi_mint_root_escrow_pda(&program.id(), &sub_args.base, merkle_tree.version);
+        let (root_escrow_mint, _bump) =
+            get_root_escrow_mint_pda(&program.id(), &root_escrow, &sub_args.mint);
+        let root_escrow_mint_state: RootEscrowMint = program.account(root_escrow_mint).unwrap();
+        let should_fund_amount = root_escrow_mint_state
+            .max_claim_amount
+            .checked_sub(root_escrow_mint_state.total_funded_amount)
+            .unwrap();
 
-    let root_escrow_state: RootEscrow = program.account(root_escrow).unwrap();
+        assert!(should_fund_amount > 0);
 
-    let should_fund_amount = root_escrow_state
-        .max_claim_amount
-        .checked_sub(root_escrow_state.total_funded_amount)
-        .unwrap();
+        ixs.push(Instruction {
+            program_id: program.id(),
+            accounts: locker::accounts::FundMultiMintRootEscrowCtx {
+                root_escrow,
+                root_escrow_mint,
+                token_mint: sub_args.mint,
+                root_escrow_token: get_associated_token_address(&root_escrow, &sub_args.mint),
+                payer: program.payer(),
+                payer_token: get_associated_token_address(&program.payer(), &sub_args.mint),
+                system_program: anchor_lang::solana_program::system_program::id(),
+                event_authority,
+                program: program.id(),
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID,
+            }
+            .to_account_metas(None),
+            data: locker::instruction::FundMultiMintRootEscrow {
+                max_amount: should_fund_amount,
+                remaining_accounts_info: None,
+            }
+            .data(),
+        });
+    } else {
+        let (root_escrow, _bump) = get_root_escrow_pda(
+            &program.id(),
+            &sub_args.base,
+            &sub_args.mint,
+            merkle_tree.version,
+        );
+        let root_escrow_state: RootEscrow = program.account(root_escrow).unwrap();
+        let should_fund_amount = root_escrow_state
+            .max_claim_amount
+            .checked_sub(root_escrow_state.total_funded_amount)
+            .unwrap();
 
-    assert!(should_fund_amount > 0);
+        assert!(should_fund_amount > 0 || sub_args.rent_deposit > 0);
 
-    let (event_authority, _bump) =
-        Pubkey::find_program_address(&[b"__event_authority"], &program.id());
-    ixs.push(Instruction {
-        program_id: program.id(),
-        accounts: locker::accounts::FundRootEscrowCtx {
-            root_escrow,
-            token_mint: sub_args.mint,
-            root_escrow_token: get_associated_token_address(&root_escrow, &sub_args.mint),
-            payer: program.payer(),
-            payer_token: get_associated_token_address(&program.payer(), &sub_args.mint),
-            system_program: anchor_lang::solana_program::system_program::id(),
-            event_authority,
-            program: program.id(),
-            token_program: spl_token::ID,
-            associated_token_program: spl_associated_token_account::ID,
-        }
-        .to_account_metas(None),
-        data: locker::instruction::FundRootEscrow {
-            max_amount: should_fund_amount,
-            remaining_accounts_info: None,
-        }
-        .data(),
-    });
+        ixs.push(Instruction {
+            program_id: program.id(),
+            accounts: locker::accounts::FundRootEscrowCtx {
+                root_escrow,
+                token_mint: sub_args.mint,
+                root_escrow_token: get_associated_token_address(&root_escrow, &sub_args.mint),
+                payer: program.payer(),
+                payer_token: get_associated_token_address(&program.payer(), &sub_args.mint),
+                system_program: anchor_lang::solana_program::system_program::id(),
+                event_authority,
+                program: program.id(),
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID,
+            }
+            .to_account_metas(None),
+            data: locker::instruction::FundRootEscrowV2 {
+                max_amount: should_fund_amount,
+                rent_deposit: sub_args.rent_deposit,
+                remaining_accounts_info: None,
+            }
+            .data(),
+        });
+    }
 
     let client =
         RpcClient::new_with_commitment(args.rpc_url.clone(), CommitmentConfig::finalized());
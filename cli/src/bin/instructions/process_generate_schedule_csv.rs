@@ -0,0 +1,26 @@
+use crate::*;
+use merkle_tree::schedule_builder::ScheduleCsvEntry;
+
+pub fn process_generate_schedule_csv(generate_schedule_csv_args: &GenerateScheduleCsvArgs) {
+    let schedule = &generate_schedule_csv_args.schedule;
+    let schedule_builder = schedule.get_schedule_builder(0);
+
+    let entries = ScheduleCsvEntry::new_from_file(&generate_schedule_csv_args.csv_path).unwrap();
+    let mut wtr = csv::Writer::from_path(&generate_schedule_csv_args.out_path).unwrap();
+    for entry in entries.iter() {
+        let csv_entry = entry
+            .to_csv_entry(
+                &schedule_builder,
+                schedule.update_recipient_mode,
+                schedule.cancel_mode,
+            )
+            .unwrap();
+        wtr.serialize(csv_entry).unwrap();
+    }
+    wtr.flush().unwrap();
+    println!(
+        "wrote {} rows to {}",
+        entries.len(),
+        generate_schedule_csv_args.out_path.display()
+    );
+}
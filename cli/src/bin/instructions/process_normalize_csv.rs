@@ -0,0 +1,24 @@
+use crate::*;
+use merkle_tree::friendly_csv_entry::{FriendlyCsvEntry, MintDecimals};
+
+pub fn process_normalize_csv(normalize_csv_args: &NormalizeCsvArgs) {
+    let mut mint_decimals = MintDecimals::new(normalize_csv_args.decimals);
+    for mint_file in normalize_csv_args.mint_file.iter() {
+        mint_decimals.add_mint_file(mint_file).unwrap();
+    }
+
+    let entries =
+        FriendlyCsvEntry::new_csv_entries_from_file(&normalize_csv_args.csv_path, &mint_decimals)
+            .unwrap();
+
+    let mut wtr = csv::Writer::from_path(&normalize_csv_args.out_path).unwrap();
+    for entry in entries.iter() {
+        wtr.serialize(entry).unwrap();
+    }
+    wtr.flush().unwrap();
+    println!(
+        "wrote {} rows to {}",
+        entries.len(),
+        normalize_csv_args.out_path.display()
+    );
+}
@@ -0,0 +1,79 @@
+use crate::*;
+use merkle_tree::csv_entry::{CsvEntry, CsvRowError};
+
+pub fn print_csv_errors(errors: &[CsvRowError]) {
+    for error in errors.iter() {
+        println!("{}", error);
+    }
+}
+
+pub fn process_validate_csv(validate_csv_args: &ValidateCsvArgs) {
+    let errors = CsvEntry::validate_file(&validate_csv_args.csv_path).unwrap();
+    if errors.is_empty() {
+        println!("{} is valid", validate_csv_args.csv_path.display());
+        return;
+    }
+
+    print_csv_errors(&errors);
+    println!("found {} errors", errors.len());
+    std::process::exit(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use locker::CreateVestingEscrowParameters;
+
+    #[test]
+    fn test_csv_and_program_validation_agree() {
+        let recipient = Pubkey::new_unique().to_string();
+        // (vesting_start_time, cliff_time, frequency, amount_per_period, update_recipient_mode, cancel_mode)
+        let rows = [
+            (0, 10, 1, 10, 0, 1),
+            (20, 10, 1, 10, 0, 0),
+            (10, 10, 1, 10, 3, 3),
+            (0, 10, 1, 10, 4, 0),
+            (0, 10, 1, 10, 0, 5),
+            (0, 10, 0, 10, 0, 0),
+            (0, 10, 0, 0, 0, 0),
+        ];
+        for (
+            vesting_start_time,
+            cliff_time,
+            frequency,
+            amount_per_period,
+            update_mode,
+            cancel_mode,
+        ) in rows
+        {
+            let entry = CsvEntry {
+                recipient: recipient.clone(),
+                vesting_start_time,
+                cliff_time,
+                frequency,
+                cliff_unlock_amount: 100,
+                amount_per_period,
+                number_of_period: 10,
+                update_recipient_mode: update_mode,
+                cancel_mode,
+                mint: None,
+            };
+            let params = CreateVestingEscrowParameters {
+                vesting_start_time,
+                cliff_time,
+                frequency,
+                cliff_unlock_amount: 100,
+                amount_per_period,
+                number_of_period: 10,
+                update_recipient_mode: update_mode,
+                cancel_mode,
+            };
+            assert_eq!(
+                entry.validate().is_empty(),
+                params.validate().is_ok(),
+                "{:?}",
+                entry
+            );
+        }
+    }
+}
@@ -3,26 +3,35 @@ use crate::VerifyAllEscrowCreatedArgs;
 use anchor_client::solana_sdk::pubkey::Pubkey;
 use locker::VestingEscrow;
 use merkle_tree::jup_lock_merkle_tree::JupLockMerkleTree;
+use merkle_tree::utils::{get_base_pda, get_multi_mint_root_escrow_pda, get_root_escrow_pda};
 
 pub fn process_verify_all_escrow_created(args: &Args, sub_args: &VerifyAllEscrowCreatedArgs) {
     let program = args.get_program_client();
     let merkle_tree = JupLockMerkleTree::new_from_file(&sub_args.merkle_tree_path).unwrap();
-    let (root_escrow, _bump) = Pubkey::find_program_address(
-        &[
-            b"root_escrow".as_ref(),
-            sub_args.base.as_ref(),
-            sub_args.mint.as_ref(),
-            merkle_tree.version.to_le_bytes().as_ref(),
-        ],
-        &program.id(),
-    );
+    let (root_escrow, _bump) = if sub_args.multi_mint {
+        get_multi_mint_root_escrow_pda(&program.id(), &sub_args.base, merkle_tree.version)
+    } else {
+        get_root_escrow_pda(
+            &program.id(),
+            &sub_args.base,
+            &sub_args.mint,
+            merkle_tree.version,
+        )
+    };
 
-    for node in merkle_tree.tree_nodes.iter() {
+    for node in merkle_tree
+        .tree_nodes
+        .iter()
+        .filter(|n| n.is_for_mint(&sub_args.mint))
+    {
         let recipient = node.recipient;
 
-        let (base, _bump) = Pubkey::find_program_address(
-            &[b"base".as_ref(), root_escrow.as_ref(), recipient.as_ref()],
+        let (base, _bump) = get_base_pda(
             &program.id(),
+            &root_escrow,
+            &recipient,
+            node.leaf_index,
+            sub_args.multi_mint.then_some(&sub_args.mint),
         );
 
         println!("verify {}", recipient);
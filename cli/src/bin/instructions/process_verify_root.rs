@@ -0,0 +1,97 @@
+use crate::*;
+use anchor_client::solana_sdk::hash::Hash;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AccountDeserialize;
+use locker::{RootEscrow, RootEscrowMint};
+use merkle_tree::utils::{
+    get_multi_mint_root_escrow_pda, get_root_escrow_mint_pda, get_root_escrow_pda,
+};
+
+fn get_account<T: AccountDeserialize>(program: &Program<Rc<Keypair>>, address: Pubkey) -> T {
+    match program.account(address) {
+        Ok(account) => account,
+        Err(err) => {
+            println!("can't read account {}: {}", address, err);
+            std::process::exit(1);
+        }
+    }
+}
+
+pub fn process_verify_root(args: &Args, sub_args: &VerifyRootArgs) {
+    validate_csv_file(&sub_args.csv_path);
+
+    let program = args.get_program_client();
+    let merkle_tree = create_merkle_tree_from_csv(
+        &sub_args.csv_path,
+        sub_args.version,
+        sub_args.leaf_position,
+        sub_args.canonical,
+    );
+
+    let (root_escrow, _bump) = if sub_args.multi_mint {
+        get_multi_mint_root_escrow_pda(&program.id(), &sub_args.base, sub_args.version)
+    } else {
+        get_root_escrow_pda(
+            &program.id(),
+            &sub_args.base,
+            &sub_args.mint,
+            sub_args.version,
+        )
+    };
+    let root_escrow_state: RootEscrow = get_account(&program, root_escrow);
+    let (max_claim_amount, max_escrow) =
+        merkle_tree.get_max_claim_amount_and_escrow(&sub_args.mint);
+    // the max claim amount of a mint is kept by its root escrow mint, the max escrow of a multi
+    // mint root escrow covers the leaves of every mint
+    let (on_chain_max_claim_amount, max_escrow) = if sub_args.multi_mint {
+        let (root_escrow_mint, _bump) =
+            get_root_escrow_mint_pda(&program.id(), &root_escrow, &sub_args.mint);
+        let root_escrow_mint_state: RootEscrowMint = get_account(&program, root_escrow_mint);
+        (
+            root_escrow_mint_state.max_claim_amount,
+            merkle_tree.tree_nodes.len() as u64,
+        )
+    } else {
+        (root_escrow_state.max_claim_amount, max_escrow)
+    };
+
+    let mut mismatches = vec![];
+    if root_escrow_state.root != merkle_tree.merkle_root {
+        mismatches.push(format!(
+            "root: on-chain {} rebuilt {}",
+            Hash::new_from_array(root_escrow_state.root),
+            Hash::new_from_array(merkle_tree.merkle_root)
+        ));
+    }
+    if root_escrow_state.leaf_version != merkle_tree.leaf_version {
+        mismatches.push(format!(
+            "leaf_version: on-chain {} rebuilt {}",
+            root_escrow_state.leaf_version, merkle_tree.leaf_version
+        ));
+    }
+    if on_chain_max_claim_amount != max_claim_amount {
+        mismatches.push(format!(
+            "max_claim_amount: on-chain {} rebuilt {}",
+            on_chain_max_claim_amount, max_claim_amount
+        ));
+    }
+    if root_escrow_state.max_escrow != max_escrow {
+        mismatches.push(format!(
+            "max_escrow: on-chain {} rebuilt {}",
+            root_escrow_state.max_escrow, max_escrow
+        ));
+    }
+
+    if mismatches.is_empty() {
+        println!(
+            "verified root {} of root escrow {}",
+            Hash::new_from_array(merkle_tree.merkle_root),
+            root_escrow
+        );
+        return;
+    }
+    for mismatch in mismatches.iter() {
+        println!("{}", mismatch);
+    }
+    std::process::exit(1);
+}
@@ -1,32 +1,47 @@
 use crate::Args;
 use crate::VerifyRootEscrowArgs;
-use anchor_client::solana_sdk::pubkey::Pubkey;
-use locker::RootEscrow;
+use locker::{RootEscrow, RootEscrowMint};
 use merkle_tree::jup_lock_merkle_tree::JupLockMerkleTree;
+use merkle_tree::utils::{
+    get_multi_mint_root_escrow_pda, get_root_escrow_mint_pda, get_root_escrow_pda,
+};
 
 pub fn process_verify_root_escrow(args: &Args, sub_args: &VerifyRootEscrowArgs) {
     let program = args.get_program_client();
 
     let merkle_tree = JupLockMerkleTree::new_from_file(&sub_args.merkle_tree_path).unwrap();
 
-    let (root_escrow, _bump) = Pubkey::find_program_address(
-        &[
-            b"root_escrow".as_ref(),
-            sub_args.base.as_ref(),
-            sub_args.mint.as_ref(),
-            merkle_tree.version.to_le_bytes().as_ref(),
-        ],
-        &program.id(),
-    );
+    let (max_claim_amount, max_escrow) =
+        merkle_tree.get_max_claim_amount_and_escrow(&sub_args.mint);
 
-    let root_escrow_state: RootEscrow = program.account(root_escrow).unwrap();
+    let root_escrow_state: RootEscrow = if sub_args.multi_mint {
+        let (root_escrow, _bump) =
+            get_multi_mint_root_escrow_pda(&program.id(), &sub_args.base, merkle_tree.version);
+        let (root_escrow_mint, _bump) =
+            get_root_escrow_mint_pda(&program.id(), &root_escrow, &sub_args.mint);
+        let root_escrow_mint_state: RootEscrowMint = program.account(root_escrow_mint).unwrap();
+        let root_escrow_state: RootEscrow = program.account(root_escrow).unwrap();
+        assert_eq!(root_escrow_mint_state.max_claim_amount, max_claim_amount);
+        assert_eq!(
+            root_escrow_state.max_escrow,
+            merkle_tree.tree_nodes.len() as u64
+        );
+        root_escrow_state
+    } else {
+        let (root_escrow, _bump) = get_root_escrow_pda(
+            &program.id(),
+            &sub_args.base,
+            &sub_args.mint,
+            merkle_tree.version,
+        );
+        let root_escrow_state: RootEscrow = program.account(root_escrow).unwrap();
+        assert_eq!(root_escrow_state.max_claim_amount, max_claim_amount);
+        assert_eq!(root_escrow_state.max_escrow, max_escrow);
+        root_escrow_state
+    };
 
     assert_eq!(root_escrow_state.creator, sub_args.creator);
-    assert_eq!(
-        root_escrow_state.max_claim_amount,
-        merkle_tree.max_claim_amount
-    );
-    assert_eq!(root_escrow_state.max_escrow, merkle_tree.max_escrow);
+    assert_eq!(root_escrow_state.leaf_version, merkle_tree.leaf_version);
     assert_eq!(root_escrow_state.version, merkle_tree.version);
     assert_eq!(root_escrow_state.root, merkle_tree.merkle_root);
 
@@ -0,0 +1,9 @@
+rpc_url=http://localhost:8899
+program_id=2r5VekMNiWPzi1pWwvJczrdPaZnJG59u91unSrTunwJg
+
+base=CwmZq21KvTYFiWkuiw2KzEiJumGehgExLvqCYBgerPJX
+mint=CnvihecwgFZkYzktcdKaPvrMGyP2s5KEMskbs19YYhzB
+version=0
+
+csv_path=/Users/andrewnguyen/Documents/solana/jup-lock/commands/trash/test.csv
+target/debug/cli --rpc-url $rpc_url --program-id $program_id verify-root --csv-path $csv_path --base $base --mint $mint --version $version --canonical
\ No newline at end of file
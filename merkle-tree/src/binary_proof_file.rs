@@ -0,0 +1,474 @@
+//! Binary merkle tree file, laid out as
+//! - header, see [BinaryProofFileHeader]
+//! - one fixed-size record per leaf in leaf order, the tree node followed by its proof
+//! - index of `(recipient, mint, leaf_index, position)` entries sorted by recipient, mint and leaf
+//!   index
+//!
+//! All integers are little endian.
+use std::{
+    fs::File,
+    io::{BufWriter, Seek, SeekFrom, Write},
+    os::unix::fs::FileExt,
+    path::Path,
+};
+
+use anchor_lang::solana_program::pubkey::Pubkey;
+
+use crate::{
+    error::MerkleTreeError::MerkleValidationError,
+    jup_lock_merkle_tree::{JupLockMerkleTree, Result},
+    streaming_merkle_tree::{MerkleTreeMetadata, ProofSink},
+    tree_node::TreeNode,
+};
+
+pub const BINARY_PROOF_FILE_MAGIC: [u8; 4] = *b"JLMT";
+pub const BINARY_PROOF_FILE_VERSION: u32 = 1;
+
+/// magic, format version, merkle root, version, max claim amount, max escrow, leaf version,
+/// padding and proof length
+pub const HEADER_SIZE: u64 = 4 + 4 + 32 + 8 + 8 + 8 + 1 + 3 + 4;
+/// recipient, mint, leaf index, schedule, modes and padding, followed by the proof
+pub const RECORD_BASE_SIZE: u64 = 32 + 32 + 8 + 8 * 6 + 1 + 1 + 6;
+/// recipient, mint, leaf index and position of the record
+pub const INDEX_ENTRY_SIZE: u64 = 32 + 32 + 8 + 8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinaryProofFileHeader {
+    pub metadata: MerkleTreeMetadata,
+    /// number of hashes in the proof of every leaf
+    pub proof_len: u32,
+}
+
+impl BinaryProofFileHeader {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_SIZE as usize);
+        bytes.extend_from_slice(&BINARY_PROOF_FILE_MAGIC);
+        bytes.extend_from_slice(&BINARY_PROOF_FILE_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&self.metadata.merkle_root);
+        bytes.extend_from_slice(&self.metadata.version.to_le_bytes());
+        bytes.extend_from_slice(&self.metadata.max_claim_amount.to_le_bytes());
+        bytes.extend_from_slice(&self.metadata.max_escrow.to_le_bytes());
+        bytes.push(self.metadata.leaf_version);
+        bytes.extend_from_slice(&[0u8; 3]);
+        bytes.extend_from_slice(&self.proof_len.to_le_bytes());
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8; HEADER_SIZE as usize]) -> Result<Self> {
+        if bytes[0..4] != BINARY_PROOF_FILE_MAGIC {
+            return Err(MerkleValidationError("Not a binary proof file".to_string()));
+        }
+        let format_version = read_u32(&bytes[4..8]);
+        if format_version != BINARY_PROOF_FILE_VERSION {
+            return Err(MerkleValidationError(format!(
+                "Unsupported binary proof file version {}",
+                format_version
+            )));
+        }
+        Ok(Self {
+            metadata: MerkleTreeMetadata {
+                merkle_root: bytes[8..40].try_into().unwrap(),
+                version: read_u64(&bytes[40..48]),
+                max_claim_amount: read_u64(&bytes[48..56]),
+                max_escrow: read_u64(&bytes[56..64]),
+                leaf_version: bytes[64],
+            },
+            proof_len: read_u32(&bytes[68..72]),
+        })
+    }
+
+    pub fn get_record_size(&self) -> u64 {
+        RECORD_BASE_SIZE + 32 * u64::from(self.proof_len)
+    }
+
+    pub fn get_index_offset(&self) -> u64 {
+        HEADER_SIZE + self.metadata.max_escrow * self.get_record_size()
+    }
+
+    /// Length of the file with the records and index of max escrow leaves, None on overflow
+    fn get_file_len(&self) -> Option<u64> {
+        let records_len = self
+            .metadata
+            .max_escrow
+            .checked_mul(self.get_record_size())?;
+        let index_len = self.metadata.max_escrow.checked_mul(INDEX_ENTRY_SIZE)?;
+        HEADER_SIZE.checked_add(records_len)?.checked_add(index_len)
+    }
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes(bytes.try_into().unwrap())
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    u64::from_le_bytes(bytes.try_into().unwrap())
+}
+
+fn encode_record(tree_node: &TreeNode, proof: &[[u8; 32]]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(RECORD_BASE_SIZE as usize + 32 * proof.len());
+    bytes.extend_from_slice(tree_node.recipient.as_ref());
+    bytes.extend_from_slice(tree_node.mint.unwrap_or_default().as_ref());
+    bytes.extend_from_slice(&tree_node.leaf_index.unwrap_or_default().to_le_bytes());
+    bytes.extend_from_slice(&tree_node.vesting_start_time.to_le_bytes());
+    bytes.extend_from_slice(&tree_node.cliff_time.to_le_bytes());
+    bytes.extend_from_slice(&tree_node.frequency.to_le_bytes());
+    bytes.extend_from_slice(&tree_node.cliff_unlock_amount.to_le_bytes());
+    bytes.extend_from_slice(&tree_node.amount_per_period.to_le_bytes());
+    bytes.extend_from_slice(&tree_node.number_of_period.to_le_bytes());
+    bytes.push(tree_node.update_recipient_mode);
+    bytes.push(tree_node.cancel_mode);
+    bytes.extend_from_slice(&[0u8; 6]);
+    for hash in proof.iter() {
+        bytes.extend_from_slice(hash);
+    }
+    bytes
+}
+
+/// Mint and leaf index are only set when the leaf version of the tree includes them
+fn decode_record(bytes: &[u8], leaf_version: u8) -> TreeNode {
+    let proof = bytes[RECORD_BASE_SIZE as usize..]
+        .chunks_exact(32)
+        .map(|hash| hash.try_into().unwrap())
+        .collect();
+    TreeNode {
+        recipient: Pubkey::new_from_array(bytes[0..32].try_into().unwrap()),
+        mint: (leaf_version & 1 != 0)
+            .then(|| Pubkey::new_from_array(bytes[32..64].try_into().unwrap())),
+        leaf_index: (leaf_version & 2 != 0).then(|| read_u64(&bytes[64..72])),
+        vesting_start_time: read_u64(&bytes[72..80]),
+        cliff_time: read_u64(&bytes[80..88]),
+        frequency: read_u64(&bytes[88..96]),
+        cliff_unlock_amount: read_u64(&bytes[96..104]),
+        amount_per_period: read_u64(&bytes[104..112]),
+        number_of_period: read_u64(&bytes[112..120]),
+        update_recipient_mode: bytes[120],
+        cancel_mode: bytes[121],
+        proof: Some(proof),
+    }
+}
+
+/// Write a binary proof file. Records are written as proofs come in, the index is kept in
+/// memory (80 bytes per leaf) and sorted when the sink is finished
+pub struct BinaryProofFileWriter {
+    writer: BufWriter<File>,
+    proof_len: Option<u32>,
+    index: Vec<(Pubkey, Pubkey, u64, u64)>,
+}
+
+impl BinaryProofFileWriter {
+    pub fn new(path: &Path) -> Result<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        // header is written once the tree is built
+        writer.write_all(&[0u8; HEADER_SIZE as usize])?;
+        Ok(Self {
+            writer,
+            proof_len: None,
+            index: Vec::new(),
+        })
+    }
+}
+
+impl ProofSink for BinaryProofFileWriter {
+    fn write_proof(&mut self, tree_node: &TreeNode) -> Result<()> {
+        let proof = tree_node.proof.as_deref().unwrap_or_default();
+        // all leaves are at the same depth
+        if *self.proof_len.get_or_insert(proof.len() as u32) != proof.len() as u32 {
+            return Err(MerkleValidationError(format!(
+                "Proof of {} does not have the same length as other proofs",
+                tree_node.recipient
+            )));
+        }
+        self.writer.write_all(&encode_record(tree_node, proof))?;
+        self.index.push((
+            tree_node.recipient,
+            tree_node.mint.unwrap_or_default(),
+            tree_node.leaf_index.unwrap_or_default(),
+            self.index.len() as u64,
+        ));
+        Ok(())
+    }
+
+    fn finish(&mut self, metadata: &MerkleTreeMetadata) -> Result<()> {
+        if self.index.len() as u64 != metadata.max_escrow {
+            return Err(MerkleValidationError(format!(
+                "Binary proof file has {} records instead of {}",
+                self.index.len(),
+                metadata.max_escrow
+            )));
+        }
+        self.index.sort_unstable();
+        for (recipient, mint, leaf_index, position) in self.index.iter() {
+            self.writer.write_all(recipient.as_ref())?;
+            self.writer.write_all(mint.as_ref())?;
+            self.writer.write_all(&leaf_index.to_le_bytes())?;
+            self.writer.write_all(&position.to_le_bytes())?;
+        }
+
+        let header = BinaryProofFileHeader {
+            metadata: metadata.clone(),
+            proof_len: self.proof_len.unwrap_or_default(),
+        };
+        self.writer.seek(SeekFrom::Start(0))?;
+        self.writer.write_all(&header.to_bytes())?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Reader of a binary proof file, looking up nodes with a binary search over the index. Reads
+/// are positional, so lookups only take `&self` and can run concurrently
+pub struct BinaryProofFile {
+    file: File,
+    header: BinaryProofFileHeader,
+}
+
+impl BinaryProofFile {
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = File::open(path)?;
+        let mut bytes = [0u8; HEADER_SIZE as usize];
+        file.read_exact_at(&mut bytes, 0)?;
+        let header = BinaryProofFileHeader::from_bytes(&bytes)?;
+
+        let expected_len = header.get_file_len().ok_or_else(|| {
+            MerkleValidationError(format!(
+                "Binary proof file of {} leaves overflows",
+                header.metadata.max_escrow
+            ))
+        })?;
+        let len = file.metadata()?.len();
+        if len != expected_len {
+            return Err(MerkleValidationError(format!(
+                "Binary proof file has {} bytes instead of {}",
+                len, expected_len
+            )));
+        }
+        Ok(Self { file, header })
+    }
+
+    pub fn header(&self) -> &BinaryProofFileHeader {
+        &self.header
+    }
+
+    pub fn len(&self) -> u64 {
+        self.header.metadata.max_escrow
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Node at a leaf position, with its proof
+    pub fn get_node_at(&self, position: u64) -> Result<Option<TreeNode>> {
+        if position >= self.len() {
+            return Ok(None);
+        }
+        let record_size = self.header.get_record_size();
+        let mut bytes = vec![0u8; record_size as usize];
+        self.file
+            .read_exact_at(&mut bytes, HEADER_SIZE + position * record_size)?;
+        Ok(Some(decode_record(
+            &bytes,
+            self.header.metadata.leaf_version,
+        )))
+    }
+
+    fn get_index_entry(&self, i: u64) -> Result<(Pubkey, Pubkey, u64, u64)> {
+        let mut bytes = [0u8; INDEX_ENTRY_SIZE as usize];
+        self.file.read_exact_at(
+            &mut bytes,
+            self.header.get_index_offset() + i * INDEX_ENTRY_SIZE,
+        )?;
+        Ok((
+            Pubkey::new_from_array(bytes[0..32].try_into().unwrap()),
+            Pubkey::new_from_array(bytes[32..64].try_into().unwrap()),
+            read_u64(&bytes[64..72]),
+            read_u64(&bytes[72..80]),
+        ))
+    }
+
+    /// Node of a recipient, mint and leaf index, with its proof, in `O(log n)` reads
+    pub fn get_node(
+        &self,
+        recipient: &Pubkey,
+        mint: Option<Pubkey>,
+        leaf_index: Option<u64>,
+    ) -> Result<Option<TreeNode>> {
+        let leaf_version = self.header.metadata.leaf_version;
+        if mint.is_some() != (leaf_version & 1 != 0)
+            || leaf_index.is_some() != (leaf_version & 2 != 0)
+        {
+            return Ok(None);
+        }
+        let key = (
+            *recipient,
+            mint.unwrap_or_default(),
+            leaf_index.unwrap_or_default(),
+        );
+
+        // find the first entry not less than key
+        let (mut low, mut high) = (0, self.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let (entry_recipient, entry_mint, entry_leaf_index, _) = self.get_index_entry(mid)?;
+            if (entry_recipient, entry_mint, entry_leaf_index) < key {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        if low == self.len() {
+            return Ok(None);
+        }
+        let (entry_recipient, entry_mint, entry_leaf_index, position) =
+            self.get_index_entry(low)?;
+        if (entry_recipient, entry_mint, entry_leaf_index) != key {
+            return Ok(None);
+        }
+        self.get_node_at(position)
+    }
+}
+
+impl JupLockMerkleTree {
+    /// Write a merkle tree to a filepath in the binary proof file format
+    pub fn write_to_binary_file(&self, path: &Path) -> Result<()> {
+        self.write_to_sink(&mut BinaryProofFileWriter::new(path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{new_tree_node, TempDir};
+
+    #[test]
+    fn test_binary_proof_file_lookup() {
+        let tree_nodes: Vec<TreeNode> = (0..100)
+            .map(|i| TreeNode {
+                cancel_mode: 1,
+                ..new_tree_node(Pubkey::new_unique(), None, i)
+            })
+            .collect();
+        let tree = JupLockMerkleTree::new(tree_nodes, 3).unwrap();
+        let temp_dir = TempDir::new("binary_proof_file");
+        let path = temp_dir.join("merkle_tree.bin");
+        tree.write_to_binary_file(&path).unwrap();
+
+        let file = BinaryProofFile::open(&path).unwrap();
+        assert_eq!(file.header().metadata.merkle_root, tree.merkle_root);
+        assert_eq!(file.header().metadata.version, 3);
+        assert_eq!(file.len(), 100);
+        for (i, tree_node) in tree.tree_nodes.iter().enumerate() {
+            let node = file.get_node(&tree_node.recipient, None, None).unwrap();
+            assert_eq!(node.as_ref(), Some(tree_node));
+            assert_eq!(
+                file.get_node_at(i as u64).unwrap().as_ref(),
+                Some(tree_node)
+            );
+        }
+        assert_eq!(
+            file.get_node(&Pubkey::new_unique(), None, None).unwrap(),
+            None
+        );
+        assert_eq!(
+            file.get_node(&tree.tree_nodes[0].recipient, None, Some(0))
+                .unwrap(),
+            None
+        );
+        assert_eq!(file.get_node_at(100).unwrap(), None);
+    }
+
+    #[test]
+    fn test_binary_proof_file_leaf_indexes() {
+        let recipient = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let tree = JupLockMerkleTree::new(
+            vec![
+                new_tree_node(recipient, Some(mint), 100),
+                new_tree_node(Pubkey::new_unique(), Some(mint), 200),
+                new_tree_node(recipient, Some(mint), 300),
+            ],
+            0,
+        )
+        .unwrap();
+        assert_eq!(tree.leaf_version, 3);
+        let temp_dir = TempDir::new("binary_proof_file");
+        let path = temp_dir.join("merkle_tree.bin");
+        tree.write_to_binary_file(&path).unwrap();
+
+        let file = BinaryProofFile::open(&path).unwrap();
+        assert_eq!(
+            file.get_node(&recipient, Some(mint), Some(1)).unwrap(),
+            tree.get_node(&recipient, Some(mint), Some(1))
+        );
+        assert_eq!(
+            file.get_node(&recipient, Some(mint), Some(1))
+                .unwrap()
+                .unwrap()
+                .cliff_unlock_amount,
+            300
+        );
+        assert_eq!(
+            file.get_node(&recipient, Some(mint), Some(2)).unwrap(),
+            None
+        );
+        assert_eq!(file.get_node(&recipient, None, Some(1)).unwrap(), None);
+    }
+
+    #[test]
+    fn test_binary_proof_file_mints() {
+        let recipient = Pubkey::new_unique();
+        let first_mint = Pubkey::new_unique();
+        let second_mint = Pubkey::new_unique();
+        let tree = JupLockMerkleTree::new(
+            vec![
+                new_tree_node(recipient, Some(first_mint), 100),
+                new_tree_node(recipient, Some(second_mint), 200),
+            ],
+            0,
+        )
+        .unwrap();
+        assert_eq!(tree.leaf_version, 1);
+        let temp_dir = TempDir::new("binary_proof_file");
+        let path = temp_dir.join("merkle_tree.bin");
+        tree.write_to_binary_file(&path).unwrap();
+
+        // leaves of a recipient are keyed by mint
+        let file = BinaryProofFile::open(&path).unwrap();
+        for mint in [first_mint, second_mint] {
+            assert_eq!(
+                file.get_node(&recipient, Some(mint), None).unwrap(),
+                tree.get_node(&recipient, Some(mint), None)
+            );
+        }
+        assert_eq!(
+            file.get_node(&recipient, Some(second_mint), None)
+                .unwrap()
+                .unwrap()
+                .cliff_unlock_amount,
+            200
+        );
+        assert_eq!(file.get_node(&recipient, None, None).unwrap(), None);
+    }
+
+    #[test]
+    fn test_binary_proof_file_overflowing_header() {
+        let header = BinaryProofFileHeader {
+            metadata: MerkleTreeMetadata {
+                merkle_root: [0u8; 32],
+                version: 0,
+                max_claim_amount: 0,
+                max_escrow: u64::MAX / INDEX_ENTRY_SIZE,
+                leaf_version: 0,
+            },
+            proof_len: 20,
+        };
+        let temp_dir = TempDir::new("binary_proof_file");
+        let path = temp_dir.join("merkle_tree.bin");
+        std::fs::write(&path, header.to_bytes()).unwrap();
+
+        assert!(matches!(
+            BinaryProofFile::open(&path),
+            Err(MerkleValidationError(_))
+        ));
+    }
+}
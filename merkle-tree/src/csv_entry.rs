@@ -1,11 +1,15 @@
-use std::{fs::File, path::PathBuf, result};
+use std::{fmt, fs::File, path::PathBuf, result, str::FromStr};
 
+use anchor_lang::solana_program::pubkey::Pubkey;
 use serde::{Deserialize, Serialize};
 
 use crate::error::MerkleTreeError;
 
 pub type Result<T> = result::Result<T, MerkleTreeError>;
 
+use vesting_validate::VestingParams;
+pub use vesting_validate::MAX_ACTOR_MODE;
+
 /// Represents a single entry in a CSV
 #[derive(Debug, Clone, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub struct CsvEntry {
@@ -18,6 +22,22 @@ pub struct CsvEntry {
     pub number_of_period: u64,
     pub update_recipient_mode: u8,
     pub cancel_mode: u8,
+    /// token mint, only for trees distributing several mints
+    #[serde(default)]
+    pub mint: Option<String>,
+}
+
+/// Error found in a row of a csv, `line` is the line number in the file, the header is line 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvRowError {
+    pub line: u64,
+    pub message: String,
+}
+
+impl fmt::Display for CsvRowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
 }
 
 impl CsvEntry {
@@ -27,10 +47,139 @@ impl CsvEntry {
 
         let mut entries = Vec::new();
         for result in rdr.deserialize() {
-            let record: CsvEntry = result.unwrap();
+            let record: CsvEntry = result?;
             entries.push(record);
         }
 
         Ok(entries)
     }
+
+    pub fn to_vesting_params(&self) -> VestingParams {
+        VestingParams {
+            vesting_start_time: self.vesting_start_time,
+            cliff_time: self.cliff_time,
+            frequency: self.frequency,
+            cliff_unlock_amount: self.cliff_unlock_amount,
+            amount_per_period: self.amount_per_period,
+            number_of_period: self.number_of_period,
+            update_recipient_mode: self.update_recipient_mode,
+            cancel_mode: self.cancel_mode,
+        }
+    }
+
+    /// Check the entry against the rules of `CreateVestingEscrowParameters::validate` in the
+    /// locker program, and that it parses to a tree node with a non zero total amount
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if Pubkey::from_str(&self.recipient).is_err() {
+            errors.push(format!("invalid recipient {}", self.recipient));
+        }
+        if let Some(mint) = self.mint.as_ref() {
+            if Pubkey::from_str(mint).is_err() {
+                errors.push(format!("invalid mint {}", mint));
+            }
+        }
+        errors.extend(
+            self.to_vesting_params()
+                .get_errors()
+                .iter()
+                .map(|err| err.to_string()),
+        );
+        match self
+            .amount_per_period
+            .checked_mul(self.number_of_period)
+            .and_then(|amount| amount.checked_add(self.cliff_unlock_amount))
+        {
+            None => errors.push("total amount overflows".to_string()),
+            Some(0) => errors.push("total amount is zero".to_string()),
+            Some(_) => {}
+        }
+        errors
+    }
+
+    /// Read a csv row by row and report the errors of every row, instead of failing at the first
+    /// one. Rows must either all or none have a mint
+    pub fn validate_file(path: &PathBuf) -> Result<Vec<CsvRowError>> {
+        let mut rdr = csv::Reader::from_path(path)?;
+        let headers = rdr.headers()?.clone();
+
+        let mut errors = Vec::new();
+        let mut has_mint = None;
+        for result in rdr.records() {
+            let record = match result {
+                Ok(record) => record,
+                Err(err) => {
+                    errors.push(CsvRowError {
+                        line: err.position().map_or(0, |p| p.line()),
+                        message: err.to_string(),
+                    });
+                    continue;
+                }
+            };
+            let line = record.position().map_or(0, |p| p.line());
+            let entry: CsvEntry = match record.deserialize(Some(&headers)) {
+                Ok(entry) => entry,
+                Err(err) => {
+                    errors.push(CsvRowError {
+                        line,
+                        message: err.to_string(),
+                    });
+                    continue;
+                }
+            };
+
+            for message in entry.validate() {
+                errors.push(CsvRowError { line, message });
+            }
+            if *has_mint.get_or_insert(entry.mint.is_some()) != entry.mint.is_some() {
+                errors.push(CsvRowError {
+                    line,
+                    message: "mint should be set for either all or none of the rows".to_string(),
+                });
+            }
+        }
+        Ok(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+    use std::io::Write;
+
+    #[test]
+    fn test_validate_file() {
+        let recipient = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let rows = [
+            "recipient,vesting_start_time,cliff_time,frequency,cliff_unlock_amount,amount_per_period,number_of_period,update_recipient_mode,cancel_mode,mint".to_string(),
+            // valid
+            format!("{},0,10,1,100,10,10,0,1,{}", recipient, mint),
+            // cliff before start and invalid modes
+            format!("{},20,10,1,100,10,10,4,5,{}", recipient, mint),
+            // invalid recipient
+            format!("not_a_pubkey,0,10,1,100,10,10,0,0,{}", mint),
+            // zero frequency and no mint
+            format!("{},0,10,0,100,10,10,0,0,", recipient),
+            // not a number
+            format!("{},x,10,1,100,10,10,0,0,{}", recipient, mint),
+            // zero total amount
+            format!("{},0,10,1,0,0,10,0,0,{}", recipient, mint),
+        ];
+        let temp_dir = TempDir::new("validate_csv");
+        let path = temp_dir.join("entries.csv");
+        let mut file = File::create(&path).unwrap();
+        file.write_all(rows.join("\n").as_bytes()).unwrap();
+
+        let errors = CsvEntry::validate_file(&path).unwrap();
+        let lines: Vec<u64> = errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec![3, 3, 3, 4, 5, 5, 6, 7]);
+        assert_eq!(
+            errors[0].to_string(),
+            "line 3: cliff_time 10 is before vesting_start_time 20"
+        );
+        assert_eq!(errors[4].message, "frequency is zero");
+        assert_eq!(errors[7].message, "total amount is zero");
+    }
 }
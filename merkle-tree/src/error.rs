@@ -10,4 +10,8 @@ pub enum MerkleTreeError {
     IoError(#[from] std::io::Error),
     #[error("Serde Error: {0}")]
     SerdeError(#[from] serde_json::Error),
+    #[error("Csv Error: {0}")]
+    CsvError(#[from] csv::Error),
+    #[error("Merkle Verify Error: {0}")]
+    MerkleVerifyError(#[from] merkle_verify::MerkleVerifyError),
 }
@@ -0,0 +1,357 @@
+use std::{collections::HashMap, fs::File, io::BufReader, path::PathBuf, str::FromStr};
+
+use anchor_lang::solana_program::pubkey::Pubkey;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    csv_entry::{CsvEntry, Result},
+    error::MerkleTreeError::MerkleValidationError,
+    tree_node::TreeNode,
+};
+
+/// Offset of `decimals` in the data of a spl token or token 2022 mint account
+const MINT_DECIMALS_OFFSET: usize = 44;
+/// Offset of `is_initialized` in the data of a spl token or token 2022 mint account
+const MINT_IS_INITIALIZED_OFFSET: usize = 45;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Entry of a csv written by hand, normalized into a [CsvEntry]
+/// - `vesting_start_time` is a date `2025-01-31`, a date time `2025-01-31T12:00:00Z` (UTC when
+///   the offset is omitted) or a unix timestamp
+/// - `cliff_time` is a date, a date time, a unix timestamp or a duration after
+///   `vesting_start_time`
+/// - `frequency` is a duration, e.g. `30d`, `1mo`, `1d12h` or seconds
+/// - amounts are decimal token amounts, e.g. `1.5`, converted with the decimals of the mint
+#[derive(Debug, Clone, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct FriendlyCsvEntry {
+    pub recipient: String,
+    pub vesting_start_time: String,
+    pub cliff_time: String,
+    pub frequency: String,
+    pub cliff_unlock_amount: String,
+    pub amount_per_period: String,
+    pub number_of_period: u64,
+    pub update_recipient_mode: u8,
+    pub cancel_mode: u8,
+    /// token mint, only for trees distributing several mints
+    #[serde(default)]
+    pub mint: Option<String>,
+}
+
+/// Parse a date, a date time or a unix timestamp into a unix timestamp
+pub fn parse_timestamp(value: &str) -> Result<u64> {
+    let value = value.trim();
+    let timestamp = if let Ok(timestamp) = value.parse::<u64>() {
+        return Ok(timestamp);
+    } else if let Ok(date_time) = DateTime::parse_from_rfc3339(value) {
+        date_time.timestamp()
+    } else if let Ok(date_time) = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S") {
+        date_time.and_utc().timestamp()
+    } else if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        date.and_hms_opt(0, 0, 0).unwrap().and_utc().timestamp()
+    } else {
+        return Err(MerkleValidationError(format!("Invalid date {}", value)));
+    };
+    u64::try_from(timestamp)
+        .map_err(|_| MerkleValidationError(format!("Date {} is before 1970", value)))
+}
+
+/// Parse a duration into seconds. A duration is a sequence of numbers followed by a unit among
+/// `s`, `m` (minutes), `h`, `d`, `w`, `mo` (30 days) and `y` (365 days), or a number of seconds
+pub fn parse_duration(value: &str) -> Result<u64> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Ok(seconds);
+    }
+    let invalid_duration = || MerkleValidationError(format!("Invalid duration {}", value));
+
+    let mut seconds: u64 = 0;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .ok_or_else(invalid_duration)?;
+        let unit_len = rest[number_len..]
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len() - number_len);
+        let number: u64 = rest[..number_len].parse().map_err(|_| invalid_duration())?;
+        let unit_seconds = match &rest[number_len..number_len + unit_len] {
+            "s" => 1,
+            "m" => 60,
+            "h" => 3_600,
+            "d" => SECONDS_PER_DAY,
+            "w" => 7 * SECONDS_PER_DAY,
+            "mo" => 30 * SECONDS_PER_DAY,
+            "y" => 365 * SECONDS_PER_DAY,
+            _ => return Err(invalid_duration()),
+        };
+        seconds = number
+            .checked_mul(unit_seconds)
+            .and_then(|duration| duration.checked_add(seconds))
+            .ok_or_else(invalid_duration)?;
+        rest = &rest[number_len + unit_len..];
+    }
+    Ok(seconds)
+}
+
+/// Convert a decimal token amount into base units of a mint with `decimals`
+pub fn parse_token_amount(value: &str, decimals: u8) -> Result<u64> {
+    let value = value.trim();
+    let invalid_amount = || {
+        MerkleValidationError(format!(
+            "Invalid amount {} for a mint with {} decimals",
+            value, decimals
+        ))
+    };
+    let amount = Decimal::from_str(value).map_err(|_| invalid_amount())?;
+    let amount = 10u64
+        .checked_pow(u32::from(decimals))
+        .and_then(|scale| Decimal::from(scale).checked_mul(amount))
+        .ok_or_else(invalid_amount)?;
+    if amount.is_sign_negative() || !amount.fract().is_zero() {
+        return Err(invalid_amount());
+    }
+    u64::try_from(amount).map_err(|_| invalid_amount())
+}
+
+/// Decimals of the mints of a csv, a default for rows without mint and per mint decimals read
+/// from mint account files
+#[derive(Debug, Clone, Default)]
+pub struct MintDecimals {
+    pub default_decimals: Option<u8>,
+    pub mint_decimals: HashMap<Pubkey, u8>,
+}
+
+impl MintDecimals {
+    pub fn new(default_decimals: Option<u8>) -> Self {
+        Self {
+            default_decimals,
+            mint_decimals: HashMap::new(),
+        }
+    }
+
+    /// Read decimals from a mint account saved with `solana account <MINT> --output json`
+    pub fn add_mint_file(&mut self, path: &PathBuf) -> Result<()> {
+        let account: serde_json::Value =
+            serde_json::from_reader(BufReader::new(File::open(path)?))?;
+        let invalid_mint_file =
+            || MerkleValidationError(format!("Invalid mint file {}", path.display()));
+
+        let mint = account["pubkey"]
+            .as_str()
+            .and_then(|pubkey| Pubkey::from_str(pubkey).ok())
+            .ok_or_else(invalid_mint_file)?;
+        let data = account["account"]["data"][0]
+            .as_str()
+            .and_then(|data| STANDARD.decode(data).ok())
+            .ok_or_else(invalid_mint_file)?;
+        if data.len() <= MINT_IS_INITIALIZED_OFFSET || data[MINT_IS_INITIALIZED_OFFSET] != 1 {
+            return Err(invalid_mint_file());
+        }
+        self.mint_decimals.insert(mint, data[MINT_DECIMALS_OFFSET]);
+        Ok(())
+    }
+
+    /// Decimals of a mint, read from its mint file. The default decimals only apply to rows
+    /// without mint
+    pub fn get(&self, mint: Option<&str>) -> Result<u8> {
+        match mint {
+            Some(mint) => {
+                let mint_pubkey = Pubkey::from_str(mint)
+                    .map_err(|_| MerkleValidationError(format!("Invalid pubkey {}", mint)))?;
+                self.mint_decimals
+                    .get(&mint_pubkey)
+                    .cloned()
+                    .ok_or_else(|| {
+                        MerkleValidationError(format!("Missing mint file for mint {}", mint))
+                    })
+            }
+            None => self.default_decimals.ok_or_else(|| {
+                MerkleValidationError("Decimals of rows without mint are unknown".to_string())
+            }),
+        }
+    }
+}
+
+impl FriendlyCsvEntry {
+    pub fn to_csv_entry(&self, mint_decimals: &MintDecimals) -> Result<CsvEntry> {
+        let decimals = mint_decimals.get(self.mint.as_deref())?;
+        let vesting_start_time = parse_timestamp(&self.vesting_start_time)?;
+        let cliff_time = match parse_timestamp(&self.cliff_time) {
+            Ok(cliff_time) => cliff_time,
+            Err(_) => vesting_start_time
+                .checked_add(parse_duration(&self.cliff_time)?)
+                .ok_or(MerkleValidationError(format!(
+                    "Invalid cliff time {}",
+                    self.cliff_time
+                )))?,
+        };
+        Ok(CsvEntry {
+            recipient: self.recipient.clone(),
+            vesting_start_time,
+            cliff_time,
+            frequency: parse_duration(&self.frequency)?,
+            cliff_unlock_amount: parse_token_amount(&self.cliff_unlock_amount, decimals)?,
+            amount_per_period: parse_token_amount(&self.amount_per_period, decimals)?,
+            number_of_period: self.number_of_period,
+            update_recipient_mode: self.update_recipient_mode,
+            cancel_mode: self.cancel_mode,
+            mint: self.mint.clone(),
+        })
+    }
+
+    pub fn to_tree_node(&self, mint_decimals: &MintDecimals) -> Result<TreeNode> {
+        TreeNode::from_csv(self.to_csv_entry(mint_decimals)?)
+    }
+
+    /// Read a csv of friendly entries and normalize them, errors are reported with the line of
+    /// the row
+    pub fn new_csv_entries_from_file(
+        path: &PathBuf,
+        mint_decimals: &MintDecimals,
+    ) -> Result<Vec<CsvEntry>> {
+        let mut rdr = csv::Reader::from_path(path)?;
+        let headers = rdr.headers()?.clone();
+
+        let mut entries = Vec::new();
+        for result in rdr.records() {
+            let record = result?;
+            let line = record.position().map_or(0, |p| p.line());
+            let entry = record
+                .deserialize::<FriendlyCsvEntry>(Some(&headers))
+                .map_err(|err| err.into())
+                .and_then(|entry| entry.to_csv_entry(mint_decimals))
+                .map_err(|err| MerkleValidationError(format!("line {}: {}", line, err)))?;
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+
+    pub fn new_tree_nodes_from_file(
+        path: &PathBuf,
+        mint_decimals: &MintDecimals,
+    ) -> Result<Vec<TreeNode>> {
+        Self::new_csv_entries_from_file(path, mint_decimals)?
+            .into_iter()
+            .map(TreeNode::from_csv)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+    use std::io::Write;
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("1735689600").unwrap(), 1735689600);
+        assert_eq!(parse_timestamp("2025-01-01").unwrap(), 1735689600);
+        assert_eq!(parse_timestamp("2025-01-01T01:00:00").unwrap(), 1735693200);
+        assert_eq!(parse_timestamp("2025-01-01T01:00:00Z").unwrap(), 1735693200);
+        assert_eq!(
+            parse_timestamp("2025-01-01T01:00:00+01:00").unwrap(),
+            1735689600
+        );
+        assert!(parse_timestamp("1969-12-31").is_err());
+        assert!(parse_timestamp("30d").is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("3600").unwrap(), 3600);
+        assert_eq!(parse_duration("30s").unwrap(), 30);
+        assert_eq!(parse_duration("5m").unwrap(), 300);
+        assert_eq!(parse_duration("30d").unwrap(), 2_592_000);
+        assert_eq!(parse_duration("1mo").unwrap(), 2_592_000);
+        assert_eq!(parse_duration("1w").unwrap(), 604_800);
+        assert_eq!(parse_duration("1y").unwrap(), 31_536_000);
+        assert_eq!(parse_duration("1d12h").unwrap(), 129_600);
+        assert!(parse_duration("d").is_err());
+        assert!(parse_duration("1x").is_err());
+        assert!(parse_duration("1.5d").is_err());
+        assert!(parse_duration("2025-01-01").is_err());
+    }
+
+    #[test]
+    fn test_parse_token_amount() {
+        assert_eq!(parse_token_amount("1.5", 6).unwrap(), 1_500_000);
+        assert_eq!(parse_token_amount("100", 0).unwrap(), 100);
+        assert_eq!(parse_token_amount("0.000001", 6).unwrap(), 1);
+        assert!(parse_token_amount("0.0000001", 6).is_err());
+        assert!(parse_token_amount("-1", 6).is_err());
+        assert!(parse_token_amount("18446744073709551616", 0).is_err());
+        assert!(parse_token_amount("abc", 6).is_err());
+        assert_eq!(parse_token_amount("0", 19).unwrap(), 0);
+        assert!(parse_token_amount("1", 20).is_err());
+        assert!(parse_token_amount("1", u8::MAX).is_err());
+    }
+
+    #[test]
+    fn test_friendly_csv() {
+        let dir = TempDir::new("friendly_csv");
+
+        // mint account with 9 decimals, as saved by `solana account --output json`
+        let mint = Pubkey::new_unique();
+        let mut data = vec![0u8; 82];
+        data[MINT_DECIMALS_OFFSET] = 9;
+        data[MINT_IS_INITIALIZED_OFFSET] = 1;
+        let mint_path = dir.join("mint.json");
+        let mint_account = serde_json::json!({
+            "pubkey": mint.to_string(),
+            "account": { "data": [STANDARD.encode(&data), "base64"] },
+        });
+        serde_json::to_writer(File::create(&mint_path).unwrap(), &mint_account).unwrap();
+
+        let recipient = Pubkey::new_unique();
+        let rows = [
+            "recipient,vesting_start_time,cliff_time,frequency,cliff_unlock_amount,amount_per_period,number_of_period,update_recipient_mode,cancel_mode,mint".to_string(),
+            format!("{},2025-01-01,30d,1mo,1.5,0.25,12,0,1,{}", recipient, mint),
+            format!("{},2025-01-01,2025-02-01,1d,10,1,30,0,0,{}", recipient, mint),
+        ];
+        let csv_path = dir.join("friendly.csv");
+        File::create(&csv_path)
+            .unwrap()
+            .write_all(rows.join("\n").as_bytes())
+            .unwrap();
+
+        // decimals of the mint are required, the default only applies to rows without mint
+        assert!(FriendlyCsvEntry::new_csv_entries_from_file(
+            &csv_path,
+            &MintDecimals::new(Some(6))
+        )
+        .is_err());
+
+        let mut mint_decimals = MintDecimals::new(None);
+        mint_decimals.add_mint_file(&mint_path).unwrap();
+        let entries =
+            FriendlyCsvEntry::new_csv_entries_from_file(&csv_path, &mint_decimals).unwrap();
+        assert_eq!(
+            entries[0],
+            CsvEntry {
+                recipient: recipient.to_string(),
+                vesting_start_time: 1735689600,
+                cliff_time: 1735689600 + 2_592_000,
+                frequency: 2_592_000,
+                cliff_unlock_amount: 1_500_000_000,
+                amount_per_period: 250_000_000,
+                number_of_period: 12,
+                update_recipient_mode: 0,
+                cancel_mode: 1,
+                mint: Some(mint.to_string()),
+            }
+        );
+        assert_eq!(entries[1].cliff_time, 1738368000);
+        assert_eq!(entries[1].frequency, SECONDS_PER_DAY);
+
+        let tree_nodes =
+            FriendlyCsvEntry::new_tree_nodes_from_file(&csv_path, &mint_decimals).unwrap();
+        assert_eq!(tree_nodes[0].recipient, recipient);
+        assert_eq!(tree_nodes[0].mint, Some(mint));
+    }
+}
@@ -6,15 +6,17 @@ use std::{
     result,
 };
 
-use anchor_lang::solana_program::{hash::hashv, pubkey::Pubkey};
-use indexmap::IndexMap;
-use merkle_verify::verify;
+use anchor_lang::solana_program::{hash::Hash, pubkey::Pubkey};
+use merkle_verify::{
+    hash_leaf, verify_node, verify_proof_for_root, verify_proof_len, MerkleVerifyError,
+};
 use serde::{Deserialize, Serialize};
 
 use crate::{
     csv_entry::CsvEntry,
     error::MerkleTreeError::{self, MerkleValidationError},
     merkle_tree::MerkleTree,
+    streaming_merkle_tree::{MerkleTreeMetadata, ProofSink},
     tree_node::TreeNode,
     utils::{get_proof, get_total_claim_amount},
 };
@@ -33,14 +35,142 @@ pub struct UserProof {
     pub number_of_period: u64,
     pub update_recipient_mode: u8,
     pub cancel_mode: u8,
+    /// token mint, only for trees distributing several mints
+    #[serde(default)]
+    pub mint: Option<Pubkey>,
+    /// index of the leaf among the recipient's leaves
+    pub leaf_index: Option<u64>,
     /// proof
     pub proof: Vec<[u8; 32]>,
 }
 
-// We need to discern between leaf and intermediate nodes to prevent trivial second
-// pre-image attacks.
-// https://flawed.net.nz/2018/02/21/attacking-merkle-trees-with-a-second-preimage-attack
-const LEAF_PREFIX: &[u8] = &[0];
+impl UserProof {
+    pub fn new(tree_node: &TreeNode, merkle_tree: String) -> Self {
+        Self {
+            merkle_tree,
+            vesting_start_time: tree_node.vesting_start_time,
+            cliff_time: tree_node.cliff_time,
+            frequency: tree_node.frequency,
+            cliff_unlock_amount: tree_node.cliff_unlock_amount,
+            amount_per_period: tree_node.amount_per_period,
+            number_of_period: tree_node.number_of_period,
+            update_recipient_mode: tree_node.update_recipient_mode,
+            cancel_mode: tree_node.cancel_mode,
+            mint: tree_node.mint,
+            leaf_index: tree_node.leaf_index,
+            proof: tree_node.proof.clone().unwrap_or_default(),
+        }
+    }
+
+    pub fn to_tree_node(&self, recipient: Pubkey) -> TreeNode {
+        TreeNode {
+            recipient,
+            vesting_start_time: self.vesting_start_time,
+            cliff_time: self.cliff_time,
+            frequency: self.frequency,
+            cliff_unlock_amount: self.cliff_unlock_amount,
+            amount_per_period: self.amount_per_period,
+            number_of_period: self.number_of_period,
+            update_recipient_mode: self.update_recipient_mode,
+            cancel_mode: self.cancel_mode,
+            mint: self.mint,
+            leaf_index: self.leaf_index,
+            proof: Some(self.proof.clone()),
+        }
+    }
+
+    /// Verify the proof of the leaf of `recipient` against the merkle tree of `metadata`, whose
+    /// `merkle_root` is the trusted root, e.g. checked against the root of the root escrow. The
+    /// proof length is checked against the leaf count of the tree, which for a multi mint tree is
+    /// more than `max_escrow` of the root escrow of one mint
+    pub fn verify(&self, recipient: Pubkey, metadata: &MerkleTreeMetadata) -> Result<()> {
+        let proof_root = self
+            .merkle_tree
+            .parse::<Hash>()
+            .map_err(|_| {
+                MerkleValidationError(format!("Invalid merkle tree {}", self.merkle_tree))
+            })?
+            .to_bytes();
+        verify_proof_len(&self.proof, metadata.max_escrow)?;
+        let node = self.to_tree_node(recipient).hash();
+        verify_proof_for_root(
+            &self.proof,
+            &metadata.merkle_root,
+            &proof_root,
+            &hash_leaf(node.as_ref()),
+        )?;
+        Ok(())
+    }
+}
+
+/// Leaf version 0 for recipient only, 1 with mint, 2 with leaf index, 3 with mint and leaf index
+fn get_leaf_version(tree_nodes: &[TreeNode]) -> Result<u8> {
+    let nodes_with_mint = tree_nodes.iter().filter(|n| n.mint.is_some()).count();
+    let has_mint = if nodes_with_mint == 0 {
+        false
+    } else if nodes_with_mint == tree_nodes.len() {
+        true
+    } else {
+        return Err(MerkleValidationError(
+            "Either all or none of tree nodes should have a mint".to_string(),
+        ));
+    };
+
+    let nodes_with_leaf_index = tree_nodes.iter().filter(|n| n.leaf_index.is_some()).count();
+    let has_leaf_index = if nodes_with_leaf_index == 0 {
+        false
+    } else if nodes_with_leaf_index == tree_nodes.len() {
+        true
+    } else {
+        return Err(MerkleValidationError(
+            "Either all or none of tree nodes should have a leaf index".to_string(),
+        ));
+    };
+
+    Ok(u8::from(has_mint) | (u8::from(has_leaf_index) << 1))
+}
+
+/// Assign leaf indexes by order of appearance of each recipient when a recipient has several
+/// leaves for a mint
+fn assign_leaf_indexes(tree_nodes: &mut [TreeNode]) {
+    if tree_nodes.iter().any(|n| n.leaf_index.is_some()) {
+        return;
+    }
+    let unique_nodes: HashSet<_> = tree_nodes.iter().map(|n| (n.recipient, n.mint)).collect();
+    if unique_nodes.len() == tree_nodes.len() {
+        return;
+    }
+
+    let mut next_leaf_index: HashMap<(Pubkey, Option<Pubkey>), u64> = HashMap::new();
+    for tree_node in tree_nodes.iter_mut() {
+        let leaf_index = next_leaf_index
+            .entry((tree_node.recipient, tree_node.mint))
+            .or_default();
+        tree_node.leaf_index = Some(*leaf_index);
+        *leaf_index += 1;
+    }
+}
+
+/// Sort tree nodes in the canonical order, by recipient pubkey bytes, then mint, leaf index and
+/// schedule. Nodes without leaf index get their leaf index in this order, so the canonical root
+/// of a set of csv entries doesn't depend on the order of the rows
+pub fn sort_canonical(tree_nodes: &mut [TreeNode]) {
+    tree_nodes.sort_by_key(|n| {
+        (
+            n.recipient,
+            n.mint,
+            n.leaf_index,
+            n.vesting_start_time,
+            n.cliff_time,
+            n.frequency,
+            n.cliff_unlock_amount,
+            n.amount_per_period,
+            n.number_of_period,
+            n.update_recipient_mode,
+            n.cancel_mode,
+        )
+    });
+}
 
 /// Merkle Tree which will be used to distribute tokens to claimants.
 /// Contains all the information necessary to verify claims against the Merkle Tree.
@@ -49,6 +179,10 @@ pub struct JupLockMerkleTree {
     /// The merkle root, which is uploaded on-chain
     pub merkle_root: [u8; 32],
     pub version: u64,
+    /// leaf version of the root escrow, a bitfield of bit 0 when leaves include the mint and bit 1
+    /// when they include a leaf index (0 to 3), see `LeafVersion` of the locker
+    #[serde(default)]
+    pub leaf_version: u8,
     pub max_claim_amount: u64,
     pub max_escrow: u64,
     pub tree_nodes: Vec<TreeNode>,
@@ -60,26 +194,21 @@ impl JupLockMerkleTree {
     pub fn get_max_total_claim(&self) -> u64 {
         self.max_claim_amount
     }
-    pub fn new(tree_nodes: Vec<TreeNode>, version: u64) -> Result<Self> {
-        // Combine tree nodes with the same claimant, while retaining original order
-        let mut tree_nodes_map: IndexMap<Pubkey, TreeNode> = IndexMap::new();
-        for tree_node in tree_nodes {
-            let recipient = tree_node.recipient;
-            tree_nodes_map
-                .entry(recipient)
-                .and_modify(|n| {
-                    panic!("duplicate claimant {}", n.recipient);
-                    // n.amount = n.amount.checked_add(tree_node.amount).unwrap();
-                    // n.locked_amount = n
-                    //     .locked_amount
-                    //     .checked_add(tree_node.locked_amount)
-                    //     .unwrap();
-                })
-                .or_insert_with(|| tree_node); // If not exists, insert a new entry
-        }
-
-        // Convert IndexMap back to Vec while preserving the order
-        let mut tree_nodes: Vec<TreeNode> = tree_nodes_map.values().cloned().collect();
+    pub fn new(mut tree_nodes: Vec<TreeNode>, version: u64) -> Result<Self> {
+        assign_leaf_indexes(&mut tree_nodes);
+
+        // A leaf is keyed by claimant, mint and leaf index, see [TreeNode::get_leaf_key]
+        let mut leaf_keys = HashSet::new();
+        for tree_node in tree_nodes.iter() {
+            if !leaf_keys.insert(tree_node.get_leaf_key()) {
+                return Err(MerkleValidationError(format!(
+                    "Duplicate leaf for recipient {} mint {:?} leaf index {:?}",
+                    tree_node.recipient, tree_node.mint, tree_node.leaf_index
+                )));
+            }
+        }
+
+        let leaf_version = get_leaf_version(&tree_nodes)?;
 
         let hashed_nodes = tree_nodes
             .iter()
@@ -97,6 +226,7 @@ impl JupLockMerkleTree {
                 .ok_or(MerkleTreeError::MerkleRootError)?
                 .to_bytes(),
             version,
+            leaf_version,
             max_claim_amount: get_total_claim_amount(tree_nodes.as_ref()),
             max_escrow: tree_nodes.len() as u64,
             tree_nodes,
@@ -110,22 +240,64 @@ impl JupLockMerkleTree {
         Ok(tree)
     }
 
+    /// Create a merkle tree where the leaf index of a node is its position in the tree, as
+    /// required by root escrow in bitmap distribution mode
+    pub fn new_with_leaf_positions(mut tree_nodes: Vec<TreeNode>, version: u64) -> Result<Self> {
+        for (i, tree_node) in tree_nodes.iter_mut().enumerate() {
+            tree_node.leaf_index = Some(i as u64);
+        }
+        Self::new(tree_nodes, version)
+    }
+
+    /// Whether the leaf index of every node is its position in a single mint tree, as required by
+    /// root escrow in bitmap distribution mode. Leaf indexes assigned per recipient collide in
+    /// claim bitmaps
+    pub fn has_leaf_positions(&self) -> bool {
+        self.leaf_version == 2
+            && self
+                .tree_nodes
+                .iter()
+                .enumerate()
+                .all(|(i, n)| n.leaf_index == Some(i as u64))
+    }
+
+    /// Create a merkle tree with leaves in the canonical order of [sort_canonical], anyone can
+    /// rebuild the same root from the same csv entries in any order
+    pub fn new_canonical(mut tree_nodes: Vec<TreeNode>, version: u64) -> Result<Self> {
+        sort_canonical(&mut tree_nodes);
+        Self::new(tree_nodes, version)
+    }
+
+    /// Create the merkle tree of a next version of a distribution, without the leaves served by
+    /// the root escrow of a prior version, given by their [TreeNode::get_leaf_key]. Leaf indexes
+    /// are assigned before the served leaves are removed, so leaves of a recipient keep the leaf
+    /// index they had in the prior tree when the csv lists them in the same order
+    pub fn new_excluding_served(
+        mut tree_nodes: Vec<TreeNode>,
+        version: u64,
+        served: &HashSet<(Pubkey, Option<Pubkey>, Option<u64>)>,
+    ) -> Result<Self> {
+        assign_leaf_indexes(&mut tree_nodes);
+        tree_nodes.retain(|n| !served.contains(&n.get_leaf_key()));
+        Self::new(tree_nodes, version)
+    }
+
     /// Load a merkle tree from a csv path
     pub fn new_from_csv(path: &PathBuf, version: u64) -> Result<Self> {
         let csv_entries = CsvEntry::new_from_file(path)?;
-        let tree_nodes: Vec<TreeNode> = csv_entries
+        let tree_nodes = csv_entries
             .into_iter()
-            .map(|x| TreeNode::from_csv(x))
-            .collect();
+            .map(TreeNode::from_csv)
+            .collect::<Result<Vec<TreeNode>>>()?;
         let tree = Self::new(tree_nodes, version)?;
         Ok(tree)
     }
 
     pub fn new_from_entries(csv_entries: Vec<CsvEntry>, version: u64) -> Result<Self> {
-        let tree_nodes: Vec<TreeNode> = csv_entries
+        let tree_nodes = csv_entries
             .into_iter()
-            .map(|x| TreeNode::from_csv(x))
-            .collect();
+            .map(TreeNode::from_csv)
+            .collect::<Result<Vec<TreeNode>>>()?;
         let tree = Self::new(tree_nodes, version)?;
         Ok(tree)
     }
@@ -146,14 +318,46 @@ impl JupLockMerkleTree {
         file.write_all(serialized.as_bytes()).unwrap();
     }
 
-    pub fn get_node(&self, recipient: &Pubkey) -> TreeNode {
-        for i in self.tree_nodes.iter() {
-            if i.recipient == *recipient {
-                return i.clone();
-            }
+    pub fn get_metadata(&self) -> MerkleTreeMetadata {
+        MerkleTreeMetadata {
+            merkle_root: self.merkle_root,
+            version: self.version,
+            leaf_version: self.leaf_version,
+            max_claim_amount: self.max_claim_amount,
+            max_escrow: self.max_escrow,
         }
+    }
+
+    /// Write tree nodes with their proofs to a proof sink
+    pub fn write_to_sink<S: ProofSink>(&self, sink: &mut S) -> Result<()> {
+        for tree_node in self.tree_nodes.iter() {
+            sink.write_proof(tree_node)?;
+        }
+        sink.finish(&self.get_metadata())
+    }
+
+    /// Node of the leaf keyed by recipient, mint and leaf index, see [TreeNode::get_leaf_key]
+    pub fn get_node(
+        &self,
+        recipient: &Pubkey,
+        mint: Option<Pubkey>,
+        leaf_index: Option<u64>,
+    ) -> Option<TreeNode> {
+        self.tree_nodes
+            .iter()
+            .find(|n| n.get_leaf_key() == (*recipient, mint, leaf_index))
+            .cloned()
+    }
 
-        panic!("Claimant not found in tree");
+    /// Max claim amount and max escrow of the root escrow for `mint`
+    pub fn get_max_claim_amount_and_escrow(&self, mint: &Pubkey) -> (u64, u64) {
+        let tree_nodes: Vec<TreeNode> = self
+            .tree_nodes
+            .iter()
+            .filter(|n| n.is_for_mint(mint))
+            .cloned()
+            .collect();
+        (get_total_claim_amount(&tree_nodes), tree_nodes.len() as u64)
     }
 
     fn validate(&self) -> Result<()> {
@@ -174,8 +378,12 @@ impl JupLockMerkleTree {
             )));
         }
 
-        // validate that there are no duplicate claimants
-        let unique_nodes: HashSet<_> = self.tree_nodes.iter().map(|n| n.recipient).collect();
+        // validate that there are no duplicate leaf for a claimant and mint
+        let unique_nodes: HashSet<_> = self
+            .tree_nodes
+            .iter()
+            .map(|n| (n.recipient, n.mint, n.leaf_index))
+            .collect();
 
         if unique_nodes.len() != self.tree_nodes.len() {
             return Err(MerkleValidationError(
@@ -183,6 +391,13 @@ impl JupLockMerkleTree {
             ));
         }
 
+        if get_leaf_version(&self.tree_nodes)? != self.leaf_version {
+            return Err(MerkleValidationError(format!(
+                "Tree nodes do not match leaf version {}",
+                self.leaf_version
+            )));
+        }
+
         // validate total_unlocked_amount
         let max_claim_amount = get_total_claim_amount(&self.tree_nodes);
         if max_claim_amount != self.max_claim_amount {
@@ -213,31 +428,282 @@ impl JupLockMerkleTree {
             .collect();
         let mk = MerkleTree::new(&hashed_nodes[..], true);
 
-        assert_eq!(
-            mk.get_root()
-                .ok_or(MerkleValidationError("invalid merkle proof".to_string()))?
-                .to_bytes(),
-            root
-        );
+        let computed_root = mk
+            .get_root()
+            .ok_or(MerkleValidationError("invalid merkle proof".to_string()))?
+            .to_bytes();
+        if computed_root != root {
+            return Err(MerkleVerifyError::WrongRoot {
+                expected: root,
+                actual: computed_root,
+            }
+            .into());
+        }
 
         // Verify each node against the root
-        for (i, _node) in hashed_nodes.iter().enumerate() {
-            let node = hashv(&[LEAF_PREFIX, &hashed_nodes[i]]);
+        for (i, node) in hashed_nodes.iter().enumerate() {
             let proof = get_proof(&mk, i);
-
-            if !verify(proof, root, node.to_bytes()) {
-                return Err(MerkleValidationError("invalid merkle proof".to_string()));
-            }
+            verify_proof_len(&proof, hashed_nodes.len() as u64)?;
+            verify_node(&proof, &root, node)?;
         }
 
         Ok(())
     }
 
     // Converts Merkle Tree to a map for faster key access
-    pub fn convert_to_hashmap(&self) -> HashMap<Pubkey, TreeNode> {
+    pub fn convert_to_hashmap(&self) -> HashMap<(Pubkey, Option<Pubkey>, Option<u64>), TreeNode> {
         self.tree_nodes
             .iter()
-            .map(|n| (n.recipient, n.clone()))
+            .map(|n| (n.get_leaf_key(), n.clone()))
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::new_tree_node;
+
+    #[test]
+    fn test_multi_mint_tree() {
+        let recipient = Pubkey::new_unique();
+        let first_mint = Pubkey::new_unique();
+        let second_mint = Pubkey::new_unique();
+        let tree = JupLockMerkleTree::new(
+            vec![
+                new_tree_node(recipient, Some(first_mint), 100),
+                new_tree_node(recipient, Some(second_mint), 200),
+                new_tree_node(Pubkey::new_unique(), Some(first_mint), 300),
+            ],
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(tree.leaf_version, 1);
+        assert_ne!(tree.tree_nodes[0].hash(), tree.tree_nodes[1].hash());
+        assert_eq!(tree.get_max_claim_amount_and_escrow(&first_mint), (600, 2));
+        assert_eq!(tree.get_max_claim_amount_and_escrow(&second_mint), (300, 1));
+        // leaves of a recipient are keyed by mint
+        assert_eq!(
+            tree.get_node(&recipient, Some(second_mint), None)
+                .unwrap()
+                .mint,
+            Some(second_mint)
+        );
+        assert_eq!(tree.get_node(&recipient, None, None), None);
+        assert_eq!(tree.convert_to_hashmap().len(), 3);
+
+        // proofs have the length of the whole tree, not of the leaves of one mint
+        let merkle_tree = Hash::new_from_array(tree.merkle_root).to_string();
+        let user_proof = UserProof::new(
+            &tree.get_node(&recipient, Some(second_mint), None).unwrap(),
+            merkle_tree,
+        );
+        user_proof.verify(recipient, &tree.get_metadata()).unwrap();
+    }
+
+    #[test]
+    fn test_mixed_leaf_version_tree() {
+        let result = JupLockMerkleTree::new(
+            vec![
+                new_tree_node(Pubkey::new_unique(), Some(Pubkey::new_unique()), 100),
+                new_tree_node(Pubkey::new_unique(), None, 100),
+            ],
+            0,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_leaf_positions_tree() {
+        let recipient = Pubkey::new_unique();
+        let tree = JupLockMerkleTree::new_with_leaf_positions(
+            vec![
+                new_tree_node(recipient, None, 100),
+                new_tree_node(Pubkey::new_unique(), None, 200),
+                new_tree_node(recipient, None, 300),
+            ],
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(tree.leaf_version, 2);
+        for (i, node) in tree.tree_nodes.iter().enumerate() {
+            assert_eq!(node.leaf_index, Some(i as u64));
+        }
+        assert!(tree.has_leaf_positions());
+    }
+
+    #[test]
+    fn test_multi_leaf_per_recipient_tree() {
+        let recipient = Pubkey::new_unique();
+        let other_recipient = Pubkey::new_unique();
+        let tree = JupLockMerkleTree::new(
+            vec![
+                new_tree_node(recipient, None, 100),
+                new_tree_node(other_recipient, None, 200),
+                new_tree_node(recipient, None, 100),
+            ],
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(tree.leaf_version, 2);
+        assert_eq!(tree.max_escrow, 3);
+        assert_eq!(
+            tree.get_node(&recipient, None, Some(0)).unwrap().leaf_index,
+            Some(0)
+        );
+        assert_eq!(
+            tree.get_node(&recipient, None, Some(1)).unwrap().leaf_index,
+            Some(1)
+        );
+        assert_eq!(
+            tree.get_node(&other_recipient, None, Some(0))
+                .unwrap()
+                .leaf_index,
+            Some(0)
+        );
+        // same schedule, different leaf index
+        assert_ne!(tree.tree_nodes[0].hash(), tree.tree_nodes[2].hash());
+        assert_eq!(tree.convert_to_hashmap().len(), 3);
+        // leaf indexes per recipient aren't positions
+        assert!(!tree.has_leaf_positions());
+    }
+
+    #[test]
+    fn test_multi_mint_multi_leaf_per_recipient_tree() {
+        let recipient = Pubkey::new_unique();
+        let first_mint = Pubkey::new_unique();
+        let second_mint = Pubkey::new_unique();
+        let tree = JupLockMerkleTree::new(
+            vec![
+                new_tree_node(recipient, Some(first_mint), 100),
+                new_tree_node(recipient, Some(second_mint), 200),
+                new_tree_node(recipient, Some(first_mint), 300),
+            ],
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(tree.leaf_version, 3);
+        // leaf indexes are counted per recipient and mint
+        let leaf_indexes: Vec<Option<u64>> = tree.tree_nodes.iter().map(|n| n.leaf_index).collect();
+        assert_eq!(leaf_indexes, vec![Some(0), Some(0), Some(1)]);
+    }
+
+    #[test]
+    fn test_duplicate_leaf_tree() {
+        let recipient = Pubkey::new_unique();
+        let mut tree_nodes = vec![
+            new_tree_node(recipient, None, 100),
+            new_tree_node(recipient, None, 200),
+        ];
+        for tree_node in tree_nodes.iter_mut() {
+            tree_node.leaf_index = Some(0);
+        }
+        let result = JupLockMerkleTree::new(tree_nodes, 0);
+        assert!(matches!(result, Err(MerkleValidationError(_))));
+    }
+
+    #[test]
+    fn test_canonical_tree() {
+        let recipients: Vec<Pubkey> = (0..10).map(|_| Pubkey::new_unique()).collect();
+        let mut tree_nodes: Vec<TreeNode> = recipients
+            .iter()
+            .enumerate()
+            .map(|(i, recipient)| new_tree_node(*recipient, None, i as u64))
+            .collect();
+        // second leaf of a recipient
+        tree_nodes.push(new_tree_node(recipients[3], None, 500));
+
+        let tree = JupLockMerkleTree::new_canonical(tree_nodes.clone(), 0).unwrap();
+        tree_nodes.reverse();
+        let reversed_tree = JupLockMerkleTree::new_canonical(tree_nodes.clone(), 0).unwrap();
+        assert_eq!(tree.merkle_root, reversed_tree.merkle_root);
+        assert_ne!(
+            tree.merkle_root,
+            JupLockMerkleTree::new(tree_nodes, 0).unwrap().merkle_root
+        );
+
+        let mut sorted_recipients = recipients.clone();
+        sorted_recipients.push(recipients[3]);
+        sorted_recipients.sort();
+        let tree_recipients: Vec<Pubkey> = tree.tree_nodes.iter().map(|n| n.recipient).collect();
+        assert_eq!(tree_recipients, sorted_recipients);
+        // leaf indexes are assigned in the canonical order
+        let node = tree.get_node(&recipients[3], None, Some(1)).unwrap();
+        assert_eq!(node.cliff_unlock_amount, 500);
+    }
+
+    #[test]
+    fn test_verify_user_proof() {
+        let recipient = Pubkey::new_unique();
+        let mut tree = JupLockMerkleTree::new(
+            (0..5)
+                .map(|i| new_tree_node(Pubkey::new_unique(), None, i))
+                .chain([new_tree_node(recipient, None, 100)])
+                .collect(),
+            0,
+        )
+        .unwrap();
+        let merkle_tree = Hash::new_from_array(tree.merkle_root).to_string();
+        let user_proof =
+            UserProof::new(&tree.get_node(&recipient, None, None).unwrap(), merkle_tree);
+        let metadata = tree.get_metadata();
+        user_proof.verify(recipient, &metadata).unwrap();
+
+        let other_root = [1; 32];
+        assert!(matches!(
+            user_proof.verify(
+                recipient,
+                &MerkleTreeMetadata {
+                    merkle_root: other_root,
+                    ..metadata.clone()
+                }
+            ),
+            Err(MerkleTreeError::MerkleVerifyError(
+                MerkleVerifyError::WrongRoot { expected, actual }
+            )) if expected == other_root && actual == tree.merkle_root
+        ));
+        assert!(matches!(
+            user_proof.verify(
+                recipient,
+                &MerkleTreeMetadata {
+                    max_escrow: 100,
+                    ..metadata.clone()
+                }
+            ),
+            Err(MerkleTreeError::MerkleVerifyError(
+                MerkleVerifyError::WrongProofLength {
+                    expected: 7,
+                    actual: 3,
+                }
+            ))
+        ));
+        assert!(matches!(
+            user_proof.verify(Pubkey::new_unique(), &metadata),
+            Err(MerkleTreeError::MerkleVerifyError(
+                MerkleVerifyError::LeafMismatch
+            ))
+        ));
+        // a merkle tree that isn't a hash is reported as such, not as a wrong root
+        let invalid_user_proof = UserProof {
+            merkle_tree: "not_a_hash".to_string(),
+            ..user_proof
+        };
+        assert!(matches!(
+            invalid_user_proof.verify(recipient, &metadata),
+            Err(MerkleTreeError::MerkleValidationError(_))
+        ));
+
+        // a tree with a wrong root is rejected instead of panicking
+        tree.merkle_root = other_root;
+        assert!(matches!(
+            tree.verify_proof(),
+            Err(MerkleTreeError::MerkleVerifyError(
+                MerkleVerifyError::WrongRoot { expected, actual }
+            )) if expected == other_root && actual == metadata.merkle_root
+        ));
+    }
+}
@@ -1,6 +1,14 @@
+pub mod binary_proof_file;
 pub mod csv_entry;
 pub mod error;
+pub mod friendly_csv_entry;
 pub mod jup_lock_merkle_tree;
 pub mod merkle_tree;
+pub mod merkle_tree_diff;
+pub mod proof_shards;
+pub mod schedule_builder;
+pub mod streaming_merkle_tree;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
 pub mod tree_node;
 pub mod utils;
@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+
+use anchor_lang::solana_program::{hash::Hash, pubkey::Pubkey};
+use serde::{Deserialize, Serialize};
+
+use crate::{jup_lock_merkle_tree::JupLockMerkleTree, tree_node::TreeNode};
+
+/// Vesting parameters of a leaf
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeafSchedule {
+    pub vesting_start_time: u64,
+    pub cliff_time: u64,
+    pub frequency: u64,
+    pub cliff_unlock_amount: u64,
+    pub amount_per_period: u64,
+    pub number_of_period: u64,
+    pub update_recipient_mode: u8,
+    pub cancel_mode: u8,
+}
+
+impl From<&TreeNode> for LeafSchedule {
+    fn from(tree_node: &TreeNode) -> Self {
+        Self {
+            vesting_start_time: tree_node.vesting_start_time,
+            cliff_time: tree_node.cliff_time,
+            frequency: tree_node.frequency,
+            cliff_unlock_amount: tree_node.cliff_unlock_amount,
+            amount_per_period: tree_node.amount_per_period,
+            number_of_period: tree_node.number_of_period,
+            update_recipient_mode: tree_node.update_recipient_mode,
+            cancel_mode: tree_node.cancel_mode,
+        }
+    }
+}
+
+/// Leaf added to or removed from a merkle tree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffLeaf {
+    pub recipient: String,
+    pub mint: Option<String>,
+    pub leaf_index: Option<u64>,
+    #[serde(flatten)]
+    pub schedule: LeafSchedule,
+}
+
+impl From<&TreeNode> for DiffLeaf {
+    fn from(tree_node: &TreeNode) -> Self {
+        Self {
+            recipient: tree_node.recipient.to_string(),
+            mint: tree_node.mint.map(|mint| mint.to_string()),
+            leaf_index: tree_node.leaf_index,
+            schedule: tree_node.into(),
+        }
+    }
+}
+
+/// Leaf of both merkle trees with a different schedule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangedLeaf {
+    pub recipient: String,
+    pub mint: Option<String>,
+    pub leaf_index: Option<u64>,
+    pub old_schedule: LeafSchedule,
+    pub new_schedule: LeafSchedule,
+}
+
+/// Difference between two versions of a distribution. Leaves are matched by recipient, mint and
+/// leaf index
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleTreeDiff {
+    pub old_version: u64,
+    pub new_version: u64,
+    /// base58 merkle roots
+    pub old_merkle_tree: String,
+    pub new_merkle_tree: String,
+    pub old_max_claim_amount: u64,
+    pub new_max_claim_amount: u64,
+    /// leaves of the new tree only, in order of the new tree
+    pub added: Vec<DiffLeaf>,
+    /// leaves of the old tree only, in order of the old tree
+    pub removed: Vec<DiffLeaf>,
+    /// leaves with a different schedule, in order of the old tree
+    pub changed: Vec<ChangedLeaf>,
+    pub unchanged: u64,
+}
+
+impl MerkleTreeDiff {
+    pub fn new(old_tree: &JupLockMerkleTree, new_tree: &JupLockMerkleTree) -> Self {
+        let new_nodes: HashMap<(Pubkey, Option<Pubkey>, Option<u64>), &TreeNode> = new_tree
+            .tree_nodes
+            .iter()
+            .map(|n| (n.get_leaf_key(), n))
+            .collect();
+        let old_nodes: HashMap<(Pubkey, Option<Pubkey>, Option<u64>), &TreeNode> = old_tree
+            .tree_nodes
+            .iter()
+            .map(|n| (n.get_leaf_key(), n))
+            .collect();
+
+        let mut removed = Vec::new();
+        let mut changed = Vec::new();
+        let mut unchanged = 0;
+        for old_node in old_tree.tree_nodes.iter() {
+            let Some(new_node) = new_nodes.get(&old_node.get_leaf_key()) else {
+                removed.push(old_node.into());
+                continue;
+            };
+            let old_schedule = LeafSchedule::from(old_node);
+            let new_schedule = LeafSchedule::from(*new_node);
+            if old_schedule == new_schedule {
+                unchanged += 1;
+            } else {
+                changed.push(ChangedLeaf {
+                    recipient: old_node.recipient.to_string(),
+                    mint: old_node.mint.map(|mint| mint.to_string()),
+                    leaf_index: old_node.leaf_index,
+                    old_schedule,
+                    new_schedule,
+                });
+            }
+        }
+        let added = new_tree
+            .tree_nodes
+            .iter()
+            .filter(|n| !old_nodes.contains_key(&n.get_leaf_key()))
+            .map(DiffLeaf::from)
+            .collect();
+
+        Self {
+            old_version: old_tree.version,
+            new_version: new_tree.version,
+            old_merkle_tree: Hash::new_from_array(old_tree.merkle_root).to_string(),
+            new_merkle_tree: Hash::new_from_array(new_tree.merkle_root).to_string(),
+            old_max_claim_amount: old_tree.max_claim_amount,
+            new_max_claim_amount: new_tree.max_claim_amount,
+            added,
+            removed,
+            changed,
+            unchanged,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::new_tree_node;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_merkle_tree_diff() {
+        let recipients: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let old_tree = JupLockMerkleTree::new(
+            vec![
+                new_tree_node(recipients[0], None, 100),
+                new_tree_node(recipients[1], None, 100),
+                new_tree_node(recipients[2], None, 100),
+            ],
+            0,
+        )
+        .unwrap();
+        let new_tree = JupLockMerkleTree::new(
+            vec![
+                new_tree_node(recipients[0], None, 100),
+                new_tree_node(recipients[2], None, 200),
+                new_tree_node(recipients[3], None, 100),
+            ],
+            1,
+        )
+        .unwrap();
+
+        let diff = MerkleTreeDiff::new(&old_tree, &new_tree);
+        assert!(!diff.is_empty());
+        assert_eq!((diff.old_version, diff.new_version), (0, 1));
+        assert_eq!(diff.unchanged, 1);
+        assert_eq!(diff.added, vec![DiffLeaf::from(&new_tree.tree_nodes[2])]);
+        assert_eq!(diff.removed, vec![DiffLeaf::from(&old_tree.tree_nodes[1])]);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].recipient, recipients[2].to_string());
+        assert_eq!(diff.changed[0].old_schedule.cliff_unlock_amount, 100);
+        assert_eq!(diff.changed[0].new_schedule.cliff_unlock_amount, 200);
+        assert!(MerkleTreeDiff::new(&old_tree, &old_tree).is_empty());
+
+        // next version without the recipients served by the root escrow of the old tree
+        let served = HashSet::from([(recipients[0], None, None)]);
+        let next_tree =
+            JupLockMerkleTree::new_excluding_served(new_tree.tree_nodes.clone(), 2, &served)
+                .unwrap();
+        let next_recipients: Vec<Pubkey> =
+            next_tree.tree_nodes.iter().map(|n| n.recipient).collect();
+        assert_eq!(next_recipients, vec![recipients[2], recipients[3]]);
+        assert_eq!(next_tree.max_claim_amount, 500);
+        next_tree.verify_proof().unwrap();
+
+        // only the served leaf of a recipient with several leaves is excluded
+        let tree_nodes = vec![
+            new_tree_node(recipients[0], None, 100),
+            new_tree_node(recipients[0], None, 200),
+            new_tree_node(recipients[1], None, 100),
+        ];
+        let served = HashSet::from([(recipients[0], None, Some(0))]);
+        let next_tree = JupLockMerkleTree::new_excluding_served(tree_nodes, 2, &served).unwrap();
+        let next_leaves: Vec<_> = next_tree
+            .tree_nodes
+            .iter()
+            .map(|n| n.get_leaf_key())
+            .collect();
+        assert_eq!(
+            next_leaves,
+            vec![
+                (recipients[0], None, Some(1)),
+                (recipients[1], None, Some(0))
+            ]
+        );
+        next_tree.verify_proof().unwrap();
+    }
+}
@@ -0,0 +1,173 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::{self, File},
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use anchor_lang::solana_program::hash::Hash;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    jup_lock_merkle_tree::{Result, UserProof},
+    streaming_merkle_tree::{MerkleTreeMetadata, ProofSink},
+    tree_node::TreeNode,
+};
+
+/// Manifest of proof shards, written to `manifest.json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofShardManifest {
+    #[serde(flatten)]
+    pub metadata: MerkleTreeMetadata,
+    /// base58 merkle root, set as `merkle_tree` of every user proof
+    pub merkle_tree: String,
+    /// shard file names, the shard of a recipient is named after the first byte of its pubkey
+    pub shards: Vec<String>,
+}
+
+/// Write proofs to one json file per first byte of the recipient pubkey, `{byte:02x}.json`,
+/// mapping the recipients of the shard to their user proofs, so a static site can serve the
+/// proofs of a recipient without the full tree. Proofs are spilled to json lines per shard and
+/// a single shard is held in memory when the sink is finished
+pub struct PrefixShardProofSink {
+    out_dir: PathBuf,
+    writers: HashMap<u8, BufWriter<File>>,
+}
+
+impl PrefixShardProofSink {
+    pub fn new(out_dir: &Path) -> Result<Self> {
+        fs::create_dir_all(out_dir)?;
+        Ok(Self {
+            out_dir: out_dir.to_path_buf(),
+            writers: HashMap::new(),
+        })
+    }
+
+    pub fn get_shard_name(prefix: u8) -> String {
+        format!("{:02x}.json", prefix)
+    }
+
+    pub fn get_manifest_path(out_dir: &Path) -> PathBuf {
+        out_dir.join("manifest.json")
+    }
+
+    fn get_spill_path(&self, prefix: u8) -> PathBuf {
+        self.out_dir.join(format!("{:02x}.jsonl.tmp", prefix))
+    }
+}
+
+impl ProofSink for PrefixShardProofSink {
+    fn write_proof(&mut self, tree_node: &TreeNode) -> Result<()> {
+        let prefix = tree_node.recipient.as_ref()[0];
+        if !self.writers.contains_key(&prefix) {
+            let file = File::create(self.get_spill_path(prefix))?;
+            self.writers.insert(prefix, BufWriter::new(file));
+        }
+        let writer = self.writers.get_mut(&prefix).unwrap();
+        serde_json::to_writer(&mut *writer, tree_node)?;
+        writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    fn finish(&mut self, metadata: &MerkleTreeMetadata) -> Result<()> {
+        let merkle_tree = Hash::new_from_array(metadata.merkle_root).to_string();
+        let mut prefixes: Vec<u8> = self.writers.keys().cloned().collect();
+        prefixes.sort_unstable();
+
+        let mut shards = Vec::with_capacity(prefixes.len());
+        for prefix in prefixes {
+            let mut writer = self.writers.remove(&prefix).unwrap();
+            writer.flush()?;
+            drop(writer);
+
+            let spill_path = self.get_spill_path(prefix);
+            let mut user_proofs: BTreeMap<String, Vec<UserProof>> = BTreeMap::new();
+            for line in BufReader::new(File::open(&spill_path)?).lines() {
+                let tree_node: TreeNode = serde_json::from_str(&line?)?;
+                user_proofs
+                    .entry(tree_node.recipient.to_string())
+                    .or_default()
+                    .push(UserProof::new(&tree_node, merkle_tree.clone()));
+            }
+            fs::remove_file(spill_path)?;
+
+            let shard = Self::get_shard_name(prefix);
+            let file = BufWriter::new(File::create(self.out_dir.join(&shard))?);
+            serde_json::to_writer(file, &user_proofs)?;
+            shards.push(shard);
+        }
+
+        let manifest = ProofShardManifest {
+            metadata: metadata.clone(),
+            merkle_tree,
+            shards,
+        };
+        let file = File::create(Self::get_manifest_path(&self.out_dir))?;
+        serde_json::to_writer_pretty(file, &manifest)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        jup_lock_merkle_tree::JupLockMerkleTree,
+        test_utils::{new_tree_node, TempDir},
+    };
+    use anchor_lang::solana_program::pubkey::Pubkey;
+
+    #[test]
+    fn test_prefix_shards() {
+        let recipient = Pubkey::new_unique();
+        let mut tree_nodes: Vec<TreeNode> = (0..50)
+            .map(|i| new_tree_node(Pubkey::new_unique(), None, i))
+            .collect();
+        tree_nodes.push(new_tree_node(recipient, None, 100));
+        tree_nodes.push(new_tree_node(recipient, None, 200));
+        let tree = JupLockMerkleTree::new(tree_nodes, 0).unwrap();
+
+        let temp_dir = TempDir::new("proof_shards");
+        let out_dir = temp_dir.join("proofs");
+        let mut sink = PrefixShardProofSink::new(&out_dir).unwrap();
+        tree.write_to_sink(&mut sink).unwrap();
+
+        let file = File::open(PrefixShardProofSink::get_manifest_path(&out_dir)).unwrap();
+        let manifest: ProofShardManifest = serde_json::from_reader(file).unwrap();
+        assert_eq!(manifest.metadata, tree.get_metadata());
+        // shards and manifest only
+        assert_eq!(
+            fs::read_dir(&out_dir).unwrap().count(),
+            manifest.shards.len() + 1
+        );
+
+        let mut num_proofs = 0;
+        for shard in manifest.shards.iter() {
+            let file = File::open(out_dir.join(shard)).unwrap();
+            let user_proofs: BTreeMap<String, Vec<UserProof>> =
+                serde_json::from_reader(file).unwrap();
+            for (recipient, user_proofs) in user_proofs {
+                let recipient: Pubkey = recipient.parse().unwrap();
+                assert_eq!(
+                    *shard,
+                    PrefixShardProofSink::get_shard_name(recipient.as_ref()[0])
+                );
+                for user_proof in user_proofs {
+                    assert_eq!(user_proof.merkle_tree, manifest.merkle_tree);
+                    user_proof.verify(recipient, &manifest.metadata).unwrap();
+                    num_proofs += 1;
+                }
+            }
+        }
+        assert_eq!(num_proofs, 52);
+
+        let shard = PrefixShardProofSink::get_shard_name(recipient.as_ref()[0]);
+        let file = File::open(out_dir.join(shard)).unwrap();
+        let user_proofs: BTreeMap<String, Vec<UserProof>> = serde_json::from_reader(file).unwrap();
+        let leaf_indexes: Vec<Option<u64>> = user_proofs[&recipient.to_string()]
+            .iter()
+            .map(|p| p.leaf_index)
+            .collect();
+        assert_eq!(leaf_indexes, vec![Some(0), Some(1)]);
+    }
+}
@@ -0,0 +1,253 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    csv_entry::{CsvEntry, Result},
+    error::MerkleTreeError::MerkleValidationError,
+};
+
+/// Basis points of the whole total amount
+pub const MAX_BPS: u16 = 10_000;
+
+/// Vesting parameters derived by a [ScheduleBuilder], vesting exactly the total amount of the
+/// grant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VestingSchedule {
+    pub vesting_start_time: u64,
+    pub cliff_time: u64,
+    pub frequency: u64,
+    pub cliff_unlock_amount: u64,
+    pub amount_per_period: u64,
+    pub number_of_period: u64,
+}
+
+impl VestingSchedule {
+    pub fn get_total_amount(&self) -> Option<u64> {
+        self.amount_per_period
+            .checked_mul(self.number_of_period)?
+            .checked_add(self.cliff_unlock_amount)
+    }
+
+    pub fn to_csv_entry(
+        &self,
+        recipient: String,
+        update_recipient_mode: u8,
+        cancel_mode: u8,
+        mint: Option<String>,
+    ) -> CsvEntry {
+        CsvEntry {
+            recipient,
+            vesting_start_time: self.vesting_start_time,
+            cliff_time: self.cliff_time,
+            frequency: self.frequency,
+            cliff_unlock_amount: self.cliff_unlock_amount,
+            amount_per_period: self.amount_per_period,
+            number_of_period: self.number_of_period,
+            update_recipient_mode,
+            cancel_mode,
+            mint,
+        }
+    }
+}
+
+/// Derive the vesting parameters of a grant given as a total amount, e.g. "total X tokens,
+/// 12 month cliff unlocking 25%, then monthly over 36 months"
+/// - the cliff unlocks `cliff_bps` of the total amount at `vesting_start_time + cliff_duration`
+/// - the rest vests over `duration` after the cliff, in periods of `frequency`
+/// - rounding dust of the periods is unlocked at the cliff, so the schedule vests exactly the
+///   total amount
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduleBuilder {
+    total_amount: u64,
+    duration: u64,
+    frequency: u64,
+    vesting_start_time: u64,
+    cliff_duration: u64,
+    cliff_bps: u16,
+}
+
+impl ScheduleBuilder {
+    pub fn new(total_amount: u64, duration: u64, frequency: u64) -> Self {
+        Self {
+            total_amount,
+            duration,
+            frequency,
+            vesting_start_time: 0,
+            cliff_duration: 0,
+            cliff_bps: 0,
+        }
+    }
+
+    pub fn with_vesting_start_time(mut self, vesting_start_time: u64) -> Self {
+        self.vesting_start_time = vesting_start_time;
+        self
+    }
+
+    pub fn with_cliff(mut self, cliff_duration: u64, cliff_bps: u16) -> Self {
+        self.cliff_duration = cliff_duration;
+        self.cliff_bps = cliff_bps;
+        self
+    }
+
+    /// Same schedule for another total amount
+    pub fn with_total_amount(mut self, total_amount: u64) -> Self {
+        self.total_amount = total_amount;
+        self
+    }
+
+    pub fn build(&self) -> Result<VestingSchedule> {
+        if self.total_amount == 0 {
+            return Err(MerkleValidationError("total amount is zero".to_string()));
+        }
+        if self.cliff_bps > MAX_BPS {
+            return Err(MerkleValidationError(format!(
+                "cliff bps {} is above {}",
+                self.cliff_bps, MAX_BPS
+            )));
+        }
+        if self.frequency == 0 {
+            return Err(MerkleValidationError("frequency is zero".to_string()));
+        }
+        if !self.duration.is_multiple_of(self.frequency) {
+            return Err(MerkleValidationError(format!(
+                "duration {} is not a multiple of frequency {}",
+                self.duration, self.frequency
+            )));
+        }
+        let cliff_time = self
+            .vesting_start_time
+            .checked_add(self.cliff_duration)
+            .ok_or(MerkleValidationError("cliff time overflows".to_string()))?;
+
+        // fits in u64 as cliff_bps <= MAX_BPS
+        let cliff_amount = (u128::from(self.total_amount) * u128::from(self.cliff_bps)
+            / u128::from(MAX_BPS)) as u64;
+        let number_of_period = self.duration / self.frequency;
+        let period_amount = self.total_amount - cliff_amount;
+        let (amount_per_period, cliff_unlock_amount) =
+            match period_amount.checked_div(number_of_period) {
+                Some(amount_per_period) => (
+                    amount_per_period,
+                    cliff_amount + period_amount % number_of_period,
+                ),
+                // no vesting after the cliff
+                None => (0, self.total_amount),
+            };
+
+        Ok(VestingSchedule {
+            vesting_start_time: self.vesting_start_time,
+            cliff_time,
+            frequency: self.frequency,
+            cliff_unlock_amount,
+            amount_per_period,
+            number_of_period,
+        })
+    }
+}
+
+/// Entry of a csv of grants given as total amounts, expanded with a [ScheduleBuilder]
+#[derive(Debug, Clone, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleCsvEntry {
+    pub recipient: String,
+    pub total_amount: u64,
+    /// token mint, only for trees distributing several mints
+    #[serde(default)]
+    pub mint: Option<String>,
+}
+
+impl ScheduleCsvEntry {
+    pub fn new_from_file(path: &PathBuf) -> Result<Vec<Self>> {
+        let mut rdr = csv::Reader::from_path(path)?;
+
+        let mut entries = Vec::new();
+        for result in rdr.deserialize() {
+            let record: ScheduleCsvEntry = result?;
+            entries.push(record);
+        }
+
+        Ok(entries)
+    }
+
+    pub fn to_csv_entry(
+        &self,
+        schedule_builder: &ScheduleBuilder,
+        update_recipient_mode: u8,
+        cancel_mode: u8,
+    ) -> Result<CsvEntry> {
+        let schedule = schedule_builder
+            .with_total_amount(self.total_amount)
+            .build()
+            .map_err(|err| {
+                MerkleValidationError(format!("recipient {}: {}", self.recipient, err))
+            })?;
+        Ok(schedule.to_csv_entry(
+            self.recipient.clone(),
+            update_recipient_mode,
+            cancel_mode,
+            self.mint.clone(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MONTH: u64 = 30 * 86_400;
+
+    #[test]
+    fn test_schedule_builder() {
+        // 1_000_000 tokens, 12 month cliff unlocking 25%, then monthly over 36 months
+        let schedule = ScheduleBuilder::new(1_000_000, 36 * MONTH, MONTH)
+            .with_vesting_start_time(1_000)
+            .with_cliff(12 * MONTH, 2_500)
+            .build()
+            .unwrap();
+        assert_eq!(
+            schedule,
+            VestingSchedule {
+                vesting_start_time: 1_000,
+                cliff_time: 1_000 + 12 * MONTH,
+                frequency: MONTH,
+                // 750_000 = 36 * 20_833 + 12
+                cliff_unlock_amount: 250_012,
+                amount_per_period: 20_833,
+                number_of_period: 36,
+            }
+        );
+        assert_eq!(schedule.get_total_amount(), Some(1_000_000));
+
+        // no vesting after the cliff
+        let schedule = ScheduleBuilder::new(7, 0, MONTH)
+            .with_cliff(MONTH, 100)
+            .build()
+            .unwrap();
+        assert_eq!(schedule.cliff_unlock_amount, 7);
+        assert_eq!(schedule.number_of_period, 0);
+
+        // total amount is exact for any split
+        for total_amount in [1, 3, 999_999, u64::MAX] {
+            for cliff_bps in [0, 1, 3_333, MAX_BPS] {
+                let schedule = ScheduleBuilder::new(total_amount, 7 * MONTH, MONTH)
+                    .with_cliff(0, cliff_bps)
+                    .build()
+                    .unwrap();
+                assert_eq!(schedule.get_total_amount(), Some(total_amount));
+            }
+        }
+
+        assert!(ScheduleBuilder::new(0, MONTH, MONTH).build().is_err());
+        assert!(ScheduleBuilder::new(1, MONTH, 0).build().is_err());
+        assert!(ScheduleBuilder::new(1, MONTH + 1, MONTH).build().is_err());
+        assert!(ScheduleBuilder::new(1, MONTH, MONTH)
+            .with_cliff(0, MAX_BPS + 1)
+            .build()
+            .is_err());
+        assert!(ScheduleBuilder::new(1, MONTH, MONTH)
+            .with_vesting_start_time(u64::MAX)
+            .with_cliff(1, 0)
+            .build()
+            .is_err());
+    }
+}
@@ -0,0 +1,487 @@
+use std::{
+    collections::HashSet,
+    fs::{self, File},
+    io::{BufRead, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use anchor_lang::solana_program::hash::hashv;
+use merkle_verify::{hash_leaf, verify_node, INTERMEDIATE_PREFIX};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    csv_entry::CsvEntry,
+    error::MerkleTreeError::{self, MerkleValidationError},
+    jup_lock_merkle_tree::Result,
+    tree_node::TreeNode,
+};
+
+/// Everything of a merkle tree except its nodes, which are written to a [ProofSink]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleTreeMetadata {
+    /// The merkle root, which is uploaded on-chain
+    pub merkle_root: [u8; 32],
+    pub version: u64,
+    /// leaf version of the root escrow, see [crate::jup_lock_merkle_tree::JupLockMerkleTree]
+    pub leaf_version: u8,
+    pub max_claim_amount: u64,
+    pub max_escrow: u64,
+}
+
+/// Destination of the tree nodes and their proofs built by [StreamingMerkleTreeBuilder]
+pub trait ProofSink {
+    /// Called once per tree node, in leaf order, with the proof set
+    fn write_proof(&mut self, tree_node: &TreeNode) -> Result<()>;
+    /// Called once after all proofs are written
+    fn finish(&mut self, metadata: &MerkleTreeMetadata) -> Result<()>;
+}
+
+/// Write proofs as json lines in shards of `shard_size` leaves, `proofs_{shard}.jsonl`, leaf
+/// `i` is in shard `i / shard_size`. Metadata is written to `merkle_tree_metadata.json`
+pub struct JsonLinesProofSink {
+    out_dir: PathBuf,
+    shard_size: u64,
+    num_proofs: u64,
+    writer: Option<BufWriter<File>>,
+}
+
+impl JsonLinesProofSink {
+    pub fn new(out_dir: &Path, shard_size: u64) -> Result<Self> {
+        if shard_size == 0 {
+            return Err(MerkleValidationError("Shard size is zero".to_string()));
+        }
+        fs::create_dir_all(out_dir)?;
+        Ok(Self {
+            out_dir: out_dir.to_path_buf(),
+            shard_size,
+            num_proofs: 0,
+            writer: None,
+        })
+    }
+
+    pub fn get_shard_path(out_dir: &Path, shard: u64) -> PathBuf {
+        out_dir.join(format!("proofs_{}.jsonl", shard))
+    }
+
+    pub fn get_metadata_path(out_dir: &Path) -> PathBuf {
+        out_dir.join("merkle_tree_metadata.json")
+    }
+}
+
+impl ProofSink for JsonLinesProofSink {
+    fn write_proof(&mut self, tree_node: &TreeNode) -> Result<()> {
+        if self.num_proofs.is_multiple_of(self.shard_size) {
+            if let Some(mut writer) = self.writer.take() {
+                writer.flush()?;
+            }
+            let shard = self.num_proofs / self.shard_size;
+            let file = File::create(Self::get_shard_path(&self.out_dir, shard))?;
+            self.writer = Some(BufWriter::new(file));
+        }
+        let writer = self.writer.as_mut().unwrap();
+        serde_json::to_writer(&mut *writer, tree_node)?;
+        writer.write_all(b"\n")?;
+        self.num_proofs += 1;
+        Ok(())
+    }
+
+    fn finish(&mut self, metadata: &MerkleTreeMetadata) -> Result<()> {
+        if let Some(mut writer) = self.writer.take() {
+            writer.flush()?;
+        }
+        let file = File::create(Self::get_metadata_path(&self.out_dir))?;
+        serde_json::to_writer_pretty(file, metadata)?;
+        Ok(())
+    }
+}
+
+/// Sequential reader of the hashes of a tree level, returning siblings of increasing indexes
+struct LevelReader {
+    reader: BufReader<File>,
+    len: u64,
+    pair_index: Option<u64>,
+    pair: [[u8; 32]; 2],
+}
+
+impl LevelReader {
+    fn new(path: &Path, len: u64) -> Result<Self> {
+        Ok(Self {
+            reader: BufReader::new(File::open(path)?),
+            len,
+            pair_index: None,
+            pair: [[0u8; 32]; 2],
+        })
+    }
+
+    /// Read the next pair of hashes, the last hash is duplicated when the level length is odd
+    fn read_pair(&mut self) -> Result<Option<[[u8; 32]; 2]>> {
+        let pair_index = self.pair_index.map_or(0, |i| i + 1);
+        if 2 * pair_index >= self.len {
+            return Ok(None);
+        }
+        self.reader.read_exact(&mut self.pair[0])?;
+        if 2 * pair_index + 1 < self.len {
+            self.reader.read_exact(&mut self.pair[1])?;
+        } else {
+            self.pair[1] = self.pair[0];
+        }
+        self.pair_index = Some(pair_index);
+        Ok(Some(self.pair))
+    }
+
+    fn get_sibling(&mut self, index: u64) -> Result<[u8; 32]> {
+        while self.pair_index != Some(index / 2) {
+            if self.read_pair()?.is_none() {
+                return Err(MerkleValidationError(format!(
+                    "Level has no hash at index {}",
+                    index
+                )));
+            }
+        }
+        Ok(self.pair[1 - (index % 2) as usize])
+    }
+}
+
+/// Build a merkle tree with bounded memory. Tree nodes are read once and spilled to `work_dir`
+/// with the hashes of each level, proofs are then streamed to a [ProofSink] leaf by leaf. Only
+/// a fingerprint per leaf is kept in memory to reject duplicate leaves.
+///
+/// Produces the same root and proofs as [crate::jup_lock_merkle_tree::JupLockMerkleTree::new],
+/// except that leaf indexes are not assigned for recipients with several leaves, use leaf
+/// positions or set leaf indexes in the tree nodes instead.
+pub struct StreamingMerkleTreeBuilder {
+    work_dir: PathBuf,
+    version: u64,
+    leaf_positions: bool,
+}
+
+impl StreamingMerkleTreeBuilder {
+    pub fn new(work_dir: &Path, version: u64) -> Self {
+        Self {
+            work_dir: work_dir.to_path_buf(),
+            version,
+            leaf_positions: false,
+        }
+    }
+
+    /// Use the position of leaves as leaf index, required by root escrow in bitmap mode
+    pub fn with_leaf_positions(mut self, leaf_positions: bool) -> Self {
+        self.leaf_positions = leaf_positions;
+        self
+    }
+
+    /// Build a merkle tree from a csv path, reading entries incrementally
+    pub fn build_from_csv<S: ProofSink>(
+        &self,
+        path: &PathBuf,
+        sink: &mut S,
+    ) -> Result<MerkleTreeMetadata> {
+        let rdr = csv::Reader::from_path(path)?;
+        let tree_nodes = rdr
+            .into_deserialize::<CsvEntry>()
+            .map(|entry| TreeNode::from_csv(entry?));
+        self.build(tree_nodes, sink)
+    }
+
+    pub fn build<I, S>(&self, tree_nodes: I, sink: &mut S) -> Result<MerkleTreeMetadata>
+    where
+        I: IntoIterator<Item = Result<TreeNode>>,
+        S: ProofSink,
+    {
+        fs::create_dir_all(&self.work_dir)?;
+        let nodes_path = self.work_dir.join("tree_nodes.jsonl");
+        let result =
+            self.build_levels(tree_nodes, &nodes_path)
+                .and_then(|(metadata, level_paths)| {
+                    self.write_proofs(&nodes_path, &level_paths, &metadata, sink)?;
+                    Ok(metadata)
+                });
+        self.remove_spilled_files(&nodes_path)?;
+        let metadata = result?;
+
+        println!(
+            "created jup lock version {} with {} escrows and total claim amount {} root {:?}",
+            metadata.version, metadata.max_escrow, metadata.max_claim_amount, metadata.merkle_root,
+        );
+        sink.finish(&metadata)?;
+        Ok(metadata)
+    }
+
+    fn get_level_path(&self, level: usize) -> PathBuf {
+        self.work_dir.join(format!("level_{}.bin", level))
+    }
+
+    fn remove_spilled_files(&self, nodes_path: &Path) -> Result<()> {
+        if nodes_path.exists() {
+            fs::remove_file(nodes_path)?;
+        }
+        for level in 0.. {
+            let level_path = self.get_level_path(level);
+            if !level_path.exists() {
+                break;
+            }
+            fs::remove_file(level_path)?;
+        }
+        Ok(())
+    }
+
+    /// Spill tree nodes to `nodes_path` and write the hashes of every level, the root is the only
+    /// hash of the last level
+    fn build_levels<I>(
+        &self,
+        tree_nodes: I,
+        nodes_path: &Path,
+    ) -> Result<(MerkleTreeMetadata, Vec<PathBuf>)>
+    where
+        I: IntoIterator<Item = Result<TreeNode>>,
+    {
+        let mut nodes_writer = BufWriter::new(File::create(nodes_path)?);
+        let mut level_paths = vec![self.get_level_path(0)];
+        let mut level_writer = BufWriter::new(File::create(&level_paths[0])?);
+
+        let mut leaf_keys = HashSet::new();
+        let mut leaf_version = None;
+        let mut max_claim_amount: u64 = 0;
+        let mut max_escrow: u64 = 0;
+        for tree_node in tree_nodes {
+            let mut tree_node = tree_node?;
+            if self.leaf_positions {
+                tree_node.leaf_index = Some(max_escrow);
+            }
+
+            let node_leaf_version = u8::from(tree_node.mint.is_some())
+                | (u8::from(tree_node.leaf_index.is_some()) << 1);
+            if *leaf_version.get_or_insert(node_leaf_version) != node_leaf_version {
+                return Err(MerkleValidationError(
+                    "Either all or none of tree nodes should have a mint and a leaf index"
+                        .to_string(),
+                ));
+            }
+
+            let key = hashv(&[
+                tree_node.recipient.as_ref(),
+                tree_node.mint.unwrap_or_default().as_ref(),
+                &tree_node.leaf_index.unwrap_or_default().to_le_bytes(),
+            ]);
+            if !leaf_keys.insert(key) {
+                return Err(MerkleValidationError(format!(
+                    "Duplicate recipient {} found, use leaf positions for recipients with several leaves",
+                    tree_node.recipient
+                )));
+            }
+
+            max_claim_amount = max_claim_amount
+                .checked_add(tree_node.total_amount())
+                .ok_or(MerkleValidationError(
+                    "Max claim amount overflows".to_string(),
+                ))?;
+            max_escrow += 1;
+
+            let leaf = hash_leaf(tree_node.hash().as_ref());
+            level_writer.write_all(leaf.as_ref())?;
+            serde_json::to_writer(&mut nodes_writer, &tree_node)?;
+            nodes_writer.write_all(b"\n")?;
+        }
+        nodes_writer.flush()?;
+        level_writer.flush()?;
+        drop(leaf_keys);
+
+        if max_escrow == 0 {
+            return Err(MerkleTreeError::MerkleRootError);
+        }
+        // The Merkle tree can be at most height 32, implying a max node count of 2^32 - 1
+        if max_escrow > 2u64.pow(32) - 1 {
+            return Err(MerkleValidationError(format!(
+                "Max num nodes {} is greater than 2^32 - 1",
+                max_escrow
+            )));
+        }
+
+        let mut level_len = max_escrow;
+        while level_len > 1 {
+            let mut reader = LevelReader::new(level_paths.last().unwrap(), level_len)?;
+            let level_path = self.get_level_path(level_paths.len());
+            let mut writer = BufWriter::new(File::create(&level_path)?);
+            while let Some([lsib, rsib]) = reader.read_pair()? {
+                // verification uses sorted hashing
+                let hash = if lsib <= rsib {
+                    hashv(&[INTERMEDIATE_PREFIX, &lsib, &rsib])
+                } else {
+                    hashv(&[INTERMEDIATE_PREFIX, &rsib, &lsib])
+                };
+                writer.write_all(hash.as_ref())?;
+            }
+            writer.flush()?;
+            level_paths.push(level_path);
+            level_len = level_len.div_ceil(2);
+        }
+
+        let mut merkle_root = [0u8; 32];
+        File::open(level_paths.last().unwrap())?.read_exact(&mut merkle_root)?;
+
+        let metadata = MerkleTreeMetadata {
+            merkle_root,
+            version: self.version,
+            leaf_version: leaf_version.unwrap_or_default(),
+            max_claim_amount,
+            max_escrow,
+        };
+        Ok((metadata, level_paths))
+    }
+
+    /// Read spilled tree nodes back in leaf order, attach their proof and verify it against the
+    /// root before writing it to the sink
+    fn write_proofs<S: ProofSink>(
+        &self,
+        nodes_path: &Path,
+        level_paths: &[PathBuf],
+        metadata: &MerkleTreeMetadata,
+        sink: &mut S,
+    ) -> Result<()> {
+        // the last level only holds the root
+        let mut level_readers = Vec::with_capacity(level_paths.len() - 1);
+        let mut level_len = metadata.max_escrow;
+        for level_path in level_paths[..level_paths.len() - 1].iter() {
+            level_readers.push(LevelReader::new(level_path, level_len)?);
+            level_len = level_len.div_ceil(2);
+        }
+
+        let nodes_reader = BufReader::new(File::open(nodes_path)?);
+        for (i, line) in nodes_reader.lines().enumerate() {
+            let mut tree_node: TreeNode = serde_json::from_str(&line?)?;
+            let mut proof = Vec::with_capacity(level_readers.len());
+            for (level, level_reader) in level_readers.iter_mut().enumerate() {
+                proof.push(level_reader.get_sibling((i as u64) >> level)?);
+            }
+
+            if let Err(err) = verify_node(&proof, &metadata.merkle_root, tree_node.hash().as_ref())
+            {
+                return Err(MerkleValidationError(format!(
+                    "invalid merkle proof for {}: {}",
+                    tree_node.recipient, err
+                )));
+            }
+            tree_node.proof = Some(proof);
+            sink.write_proof(&tree_node)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        jup_lock_merkle_tree::JupLockMerkleTree,
+        test_utils::{new_tree_node, TempDir},
+    };
+    use anchor_lang::solana_program::pubkey::Pubkey;
+
+    #[derive(Default)]
+    struct VecProofSink {
+        tree_nodes: Vec<TreeNode>,
+        metadata: Option<MerkleTreeMetadata>,
+    }
+
+    impl ProofSink for VecProofSink {
+        fn write_proof(&mut self, tree_node: &TreeNode) -> Result<()> {
+            self.tree_nodes.push(tree_node.clone());
+            Ok(())
+        }
+
+        fn finish(&mut self, metadata: &MerkleTreeMetadata) -> Result<()> {
+            self.metadata = Some(metadata.clone());
+            Ok(())
+        }
+    }
+
+    fn new_tree_nodes(num_nodes: u64) -> Vec<TreeNode> {
+        (0..num_nodes)
+            .map(|i| new_tree_node(Pubkey::new_unique(), None, i))
+            .collect()
+    }
+
+    #[test]
+    fn test_streaming_tree_matches_tree() {
+        for num_nodes in [1, 2, 3, 7, 8, 100] {
+            let tree_nodes = new_tree_nodes(num_nodes);
+            let tree = JupLockMerkleTree::new(tree_nodes.clone(), 0).unwrap();
+
+            let temp_dir = TempDir::new("streaming_merkle_tree");
+            let work_dir = temp_dir.join("work");
+            let mut sink = VecProofSink::default();
+            let metadata = StreamingMerkleTreeBuilder::new(&work_dir, 0)
+                .build(tree_nodes.into_iter().map(Ok), &mut sink)
+                .unwrap();
+
+            assert_eq!(metadata.merkle_root, tree.merkle_root);
+            assert_eq!(metadata.max_claim_amount, tree.max_claim_amount);
+            assert_eq!(metadata.max_escrow, tree.max_escrow);
+            assert_eq!(sink.metadata, Some(metadata));
+            assert_eq!(sink.tree_nodes, tree.tree_nodes);
+            // spilled files are removed
+            assert_eq!(fs::read_dir(&work_dir).unwrap().count(), 0);
+        }
+    }
+
+    #[test]
+    fn test_streaming_tree_leaf_positions() {
+        let recipient = Pubkey::new_unique();
+        let mut tree_nodes = new_tree_nodes(5);
+        for tree_node in tree_nodes.iter_mut() {
+            tree_node.recipient = recipient;
+        }
+        let tree = JupLockMerkleTree::new_with_leaf_positions(tree_nodes.clone(), 0).unwrap();
+
+        let temp_dir = TempDir::new("streaming_merkle_tree");
+        let work_dir = temp_dir.join("work");
+        let builder = StreamingMerkleTreeBuilder::new(&work_dir, 0);
+        let mut sink = VecProofSink::default();
+        let result = builder.build(tree_nodes.clone().into_iter().map(Ok), &mut sink);
+        assert!(result.is_err());
+
+        let mut sink = VecProofSink::default();
+        let metadata = builder
+            .with_leaf_positions(true)
+            .build(tree_nodes.into_iter().map(Ok), &mut sink)
+            .unwrap();
+        assert_eq!(metadata.leaf_version, 2);
+        assert_eq!(metadata.merkle_root, tree.merkle_root);
+        assert_eq!(sink.tree_nodes, tree.tree_nodes);
+    }
+
+    #[test]
+    fn test_json_lines_proof_sink() {
+        let tree_nodes = new_tree_nodes(25);
+        let temp_dir = TempDir::new("streaming_merkle_tree");
+        let work_dir = temp_dir.join("work");
+        let out_dir = work_dir.join("proofs");
+        let mut sink = JsonLinesProofSink::new(&out_dir, 10).unwrap();
+        let metadata = StreamingMerkleTreeBuilder::new(&work_dir, 0)
+            .build(tree_nodes.into_iter().map(Ok), &mut sink)
+            .unwrap();
+
+        let mut num_proofs = 0;
+        for shard in 0..3 {
+            let file = File::open(JsonLinesProofSink::get_shard_path(&out_dir, shard)).unwrap();
+            for line in BufReader::new(file).lines() {
+                let tree_node: TreeNode = serde_json::from_str(&line.unwrap()).unwrap();
+                assert_eq!(
+                    verify_node(
+                        tree_node.proof.as_ref().unwrap(),
+                        &metadata.merkle_root,
+                        tree_node.hash().as_ref()
+                    ),
+                    Ok(())
+                );
+                num_proofs += 1;
+            }
+        }
+        assert_eq!(num_proofs, 25);
+        assert!(!JsonLinesProofSink::get_shard_path(&out_dir, 3).exists());
+
+        let file = File::open(JsonLinesProofSink::get_metadata_path(&out_dir)).unwrap();
+        let written_metadata: MerkleTreeMetadata = serde_json::from_reader(file).unwrap();
+        assert_eq!(written_metadata, metadata);
+    }
+}
@@ -0,0 +1,53 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anchor_lang::solana_program::pubkey::Pubkey;
+
+use crate::tree_node::TreeNode;
+
+/// Tree node of a linear schedule, used by the tests of this crate and of the proof server.
+pub fn new_tree_node(
+    recipient: Pubkey,
+    mint: Option<Pubkey>,
+    cliff_unlock_amount: u64,
+) -> TreeNode {
+    TreeNode {
+        recipient,
+        vesting_start_time: 0,
+        cliff_time: 1,
+        frequency: 1,
+        cliff_unlock_amount,
+        amount_per_period: 10,
+        number_of_period: 10,
+        update_recipient_mode: 0,
+        cancel_mode: 0,
+        mint,
+        leaf_index: None,
+        proof: None,
+    }
+}
+
+/// Unique directory in the system temp dir, removed on drop.
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    pub fn new(prefix: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("{}_{}", prefix, Pubkey::new_unique()));
+        fs::create_dir_all(&path).unwrap();
+        Self { path }
+    }
+
+    pub fn join<P: AsRef<Path>>(&self, path: P) -> PathBuf {
+        self.path.join(path)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
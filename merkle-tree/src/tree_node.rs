@@ -1,6 +1,9 @@
 use std::str::FromStr;
 
-use crate::csv_entry::CsvEntry;
+use crate::{
+    csv_entry::{CsvEntry, Result},
+    error::MerkleTreeError::MerkleValidationError,
+};
 use anchor_lang::solana_program::{
     hash::{hashv, Hash},
     pubkey::Pubkey,
@@ -20,13 +23,28 @@ pub struct TreeNode {
     pub number_of_period: u64,
     pub update_recipient_mode: u8,
     pub cancel_mode: u8,
+    /// Token mint of the escrow, only set in trees distributing several mints
+    #[serde(default)]
+    pub mint: Option<Pubkey>,
+    /// Index of the leaf among the recipient's leaves, only set in trees where a recipient has
+    /// several leaves
+    #[serde(default)]
+    pub leaf_index: Option<u64>,
     pub proof: Option<Vec<[u8; 32]>>,
 }
 
 impl TreeNode {
     pub fn hash(&self) -> Hash {
-        hashv(&[
-            &self.recipient.to_bytes(),
+        let leaf_index = self.leaf_index.unwrap_or_default().to_le_bytes();
+        // mint (leaf version 1 and 3) and leaf index (leaf version 2 and 3) follow the recipient
+        let mut prefix: Vec<&[u8]> = vec![self.recipient.as_ref()];
+        if let Some(mint) = self.mint.as_ref() {
+            prefix.push(mint.as_ref());
+        }
+        if self.leaf_index.is_some() {
+            prefix.push(&leaf_index);
+        }
+        let schedule: [&[u8]; 8] = [
             &self.vesting_start_time.to_le_bytes(),
             &self.cliff_time.to_le_bytes(),
             &self.frequency.to_le_bytes(),
@@ -35,7 +53,16 @@ impl TreeNode {
             &self.number_of_period.to_le_bytes(),
             &self.update_recipient_mode.to_le_bytes(),
             &self.cancel_mode.to_le_bytes(),
-        ])
+        ];
+        hashv(&[&prefix[..], &schedule[..]].concat())
+    }
+    /// Recipient, mint and leaf index, identifying the leaf in a merkle tree
+    pub fn get_leaf_key(&self) -> (Pubkey, Option<Pubkey>, Option<u64>) {
+        (self.recipient, self.mint, self.leaf_index)
+    }
+    /// Whether this node can be claimed from the root escrow of `mint`
+    pub fn is_for_mint(&self, mint: &Pubkey) -> bool {
+        self.mint.is_none_or(|node_mint| node_mint == *mint)
     }
     /// Return total amount for this recipient
     pub fn total_amount(&self) -> u64 {
@@ -52,9 +79,13 @@ impl TreeNode {
 }
 
 impl TreeNode {
-    pub fn from_csv(entry: CsvEntry) -> Self {
+    pub fn from_csv(entry: CsvEntry) -> Result<Self> {
+        let parse_pubkey = |pubkey: &str| {
+            Pubkey::from_str(pubkey)
+                .map_err(|_| MerkleValidationError(format!("Invalid pubkey {}", pubkey)))
+        };
         let node = Self {
-            recipient: Pubkey::from_str(&entry.recipient.as_str()).unwrap(),
+            recipient: parse_pubkey(&entry.recipient)?,
             vesting_start_time: entry.vesting_start_time,
             cliff_time: entry.cliff_time,
             frequency: entry.frequency,
@@ -63,8 +94,10 @@ impl TreeNode {
             number_of_period: entry.number_of_period,
             update_recipient_mode: entry.update_recipient_mode,
             cancel_mode: entry.cancel_mode,
+            mint: entry.mint.as_deref().map(parse_pubkey).transpose()?,
+            leaf_index: None,
             proof: None,
         };
-        node
+        Ok(node)
     }
 }
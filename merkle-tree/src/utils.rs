@@ -42,6 +42,58 @@ pub fn get_root_escrow_pda(
     )
 }
 
+/// Multi mint root escrow, seeded with the default pubkey instead of a mint
+pub fn get_multi_mint_root_escrow_pda(
+    program_id: &Pubkey,
+    base: &Pubkey,
+    version: u64,
+) -> (Pubkey, u8) {
+    get_root_escrow_pda(program_id, base, &Pubkey::default(), version)
+}
+
+/// Accounting of `mint` in a multi mint root escrow
+pub fn get_root_escrow_mint_pda(
+    program_id: &Pubkey,
+    root_escrow: &Pubkey,
+    mint: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"root_escrow_mint".as_ref(),
+            root_escrow.as_ref(),
+            mint.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+/// Base of the escrow created from root escrow for a leaf, leaf index is only set for trees
+/// with several leaves per recipient and mint only for multi mint root escrows
+pub fn get_base_pda(
+    program_id: &Pubkey,
+    root_escrow: &Pubkey,
+    recipient: &Pubkey,
+    leaf_index: Option<u64>,
+    mint: Option<&Pubkey>,
+) -> (Pubkey, u8) {
+    let leaf_index_seed = leaf_index
+        .map(|leaf_index| leaf_index.to_le_bytes().to_vec())
+        .unwrap_or_default();
+    let mint_seed = mint
+        .map(|mint| mint.to_bytes().to_vec())
+        .unwrap_or_default();
+    Pubkey::find_program_address(
+        &[
+            b"base".as_ref(),
+            root_escrow.as_ref(),
+            recipient.as_ref(),
+            leaf_index_seed.as_ref(),
+            mint_seed.as_ref(),
+        ],
+        program_id,
+    )
+}
+
 #[derive(Debug)]
 pub struct MerkleValidationError {
     pub msg: String,
@@ -1,23 +1,220 @@
-use anchor_lang::solana_program::hash::hashv;
+use std::fmt;
+
+use anchor_lang::solana_program::hash::{hashv, Hash};
+
+// We need to discern between leaf and intermediate nodes to prevent trivial second
+// pre-image attacks.
+// https://flawed.net.nz/2018/02/21/attacking-merkle-trees-with-a-second-preimage-attack
+pub const LEAF_PREFIX: &[u8] = &[0];
+pub const INTERMEDIATE_PREFIX: &[u8] = &[1];
+
+/// Reason a merkle proof is rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MerkleVerifyError {
+    /// the proof was issued for another merkle root
+    WrongRoot {
+        expected: [u8; 32],
+        actual: [u8; 32],
+    },
+    /// the proof length doesn't match the depth of the merkle tree
+    WrongProofLength { expected: usize, actual: usize },
+    /// hashing the leaf along the proof doesn't give the merkle root
+    LeafMismatch,
+}
+
+impl fmt::Display for MerkleVerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongRoot { expected, actual } => write!(
+                f,
+                "proof is for merkle root {}, expected {}",
+                Hash::new_from_array(*actual),
+                Hash::new_from_array(*expected)
+            ),
+            Self::WrongProofLength { expected, actual } => {
+                write!(f, "proof has {} hashes, expected {}", actual, expected)
+            }
+            Self::LeafMismatch => write!(f, "leaf is not in the merkle tree"),
+        }
+    }
+}
+
+impl std::error::Error for MerkleVerifyError {}
+
+/// Hash of the leaf of `node`, i.e. the node hash prefixed with [LEAF_PREFIX]
+pub fn hash_leaf(node: &[u8]) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, node]).to_bytes()
+}
+
+/// Root of the merkle tree given a `leaf` and the sibling hashes on the branch from the leaf to
+/// the root. Each pair of hashes is sorted before hashing
+pub fn compute_root(proof: &[[u8; 32]], leaf: &[u8; 32]) -> [u8; 32] {
+    proof.iter().fold(*leaf, |computed_hash, proof_element| {
+        if computed_hash <= *proof_element {
+            // Hash(current computed hash + current element of the proof)
+            hashv(&[INTERMEDIATE_PREFIX, &computed_hash, proof_element]).to_bytes()
+        } else {
+            // Hash(current element of the proof + current computed hash)
+            hashv(&[INTERMEDIATE_PREFIX, proof_element, &computed_hash]).to_bytes()
+        }
+    })
+}
+
+/// Length of every proof of a merkle tree with `leaf_count` leaves, as the last node of a level
+/// with an odd length is paired with itself
+pub fn get_proof_len(leaf_count: u64) -> usize {
+    leaf_count.max(1).next_power_of_two().trailing_zeros() as usize
+}
+
+/// Verify that `proof` has the length of the proofs of a merkle tree with `leaf_count` leaves
+pub fn verify_proof_len(proof: &[[u8; 32]], leaf_count: u64) -> Result<(), MerkleVerifyError> {
+    let expected = get_proof_len(leaf_count);
+    if proof.len() != expected {
+        return Err(MerkleVerifyError::WrongProofLength {
+            expected,
+            actual: proof.len(),
+        });
+    }
+    Ok(())
+}
 
 /// modified version of https://github.com/saber-hq/merkle-distributor/blob/ac937d1901033ecb7fa3b0db22f7b39569c8e052/programs/merkle-distributor/src/merkle_proof.rs#L8
 /// This function deals with verification of Merkle trees (hash trees).
 /// Direct port of https://github.com/OpenZeppelin/openzeppelin-contracts/blob/v3.4.0/contracts/cryptography/MerkleProof.sol
-/// Returns true if a `leaf` can be proved to be a part of a Merkle tree
+/// Verify that a `leaf` can be proved to be a part of a Merkle tree
 /// defined by `root`. For this, a `proof` must be provided, containing
 /// sibling hashes on the branch from the leaf to the root of the tree. Each
 /// pair of leaves and each pair of pre-images are assumed to be sorted.
+pub fn verify_proof(
+    proof: &[[u8; 32]],
+    root: &[u8; 32],
+    leaf: &[u8; 32],
+) -> Result<(), MerkleVerifyError> {
+    if compute_root(proof, leaf) != *root {
+        return Err(MerkleVerifyError::LeafMismatch);
+    }
+    Ok(())
+}
+
+/// Verify that a `leaf` is part of the merkle tree defined by `trusted_root`, with a `proof`
+/// issued for the merkle tree of `proof_root`, e.g. the root returned along the proof by a proof
+/// api. A proof for another root is rejected before hashing
+pub fn verify_proof_for_root(
+    proof: &[[u8; 32]],
+    trusted_root: &[u8; 32],
+    proof_root: &[u8; 32],
+    leaf: &[u8; 32],
+) -> Result<(), MerkleVerifyError> {
+    if proof_root != trusted_root {
+        return Err(MerkleVerifyError::WrongRoot {
+            expected: *trusted_root,
+            actual: *proof_root,
+        });
+    }
+    verify_proof(proof, trusted_root, leaf)
+}
+
+/// Verify that the leaf of `node` is part of the merkle tree defined by `root`, hashing the
+/// leaf with [LEAF_PREFIX]
+pub fn verify_node(
+    proof: &[[u8; 32]],
+    root: &[u8; 32],
+    node: &[u8],
+) -> Result<(), MerkleVerifyError> {
+    verify_proof(proof, root, &hash_leaf(node))
+}
+
+/// Returns true if a `leaf` can be proved to be a part of a Merkle tree defined by `root`
+#[deprecated(note = "use verify_proof, which takes slices and returns the reason of a failure")]
 pub fn verify(proof: Vec<[u8; 32]>, root: [u8; 32], leaf: [u8; 32]) -> bool {
-    let mut computed_hash = leaf;
-    for proof_element in proof.into_iter() {
-        if computed_hash <= proof_element {
-            // Hash(current computed hash + current element of the proof)
-            computed_hash = hashv(&[&[1u8], &computed_hash, &proof_element]).to_bytes();
-        } else {
-            // Hash(current element of the proof + current computed hash)
-            computed_hash = hashv(&[&[1u8], &proof_element, &computed_hash]).to_bytes();
+    verify_proof(&proof, &root, &leaf).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_tree(nodes: &[[u8; 32]]) -> Vec<Vec<[u8; 32]>> {
+        let mut levels = vec![nodes.iter().map(|n| hash_leaf(n)).collect::<Vec<_>>()];
+        while levels.last().unwrap().len() > 1 {
+            let level = levels.last().unwrap();
+            let next_level = level
+                .chunks(2)
+                .map(|pair| {
+                    let (l, r) = (pair[0], *pair.last().unwrap());
+                    if l <= r {
+                        hashv(&[INTERMEDIATE_PREFIX, &l, &r]).to_bytes()
+                    } else {
+                        hashv(&[INTERMEDIATE_PREFIX, &r, &l]).to_bytes()
+                    }
+                })
+                .collect();
+            levels.push(next_level);
         }
+        levels
+    }
+
+    fn get_proof(levels: &[Vec<[u8; 32]>], index: usize) -> Vec<[u8; 32]> {
+        levels[..levels.len() - 1]
+            .iter()
+            .enumerate()
+            .map(|(level, nodes)| {
+                let i = index >> level;
+                let sibling = i ^ 1;
+                nodes[sibling.min(nodes.len() - 1)]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_verify_proof() {
+        let nodes: Vec<[u8; 32]> = (0..5u8).map(|i| hashv(&[&[i]]).to_bytes()).collect();
+        let levels = new_tree(&nodes);
+        let root = levels.last().unwrap()[0];
+
+        for (i, node) in nodes.iter().enumerate() {
+            let proof = get_proof(&levels, i);
+            assert_eq!(verify_node(&proof, &root, node), Ok(()));
+            assert_eq!(verify_proof_len(&proof, nodes.len() as u64), Ok(()));
+            assert_eq!(
+                verify_proof(&proof, &root, node),
+                Err(MerkleVerifyError::LeafMismatch)
+            );
+        }
+
+        let proof = get_proof(&levels, 0);
+        assert_eq!(
+            verify_node(&proof, &root, &nodes[1]),
+            Err(MerkleVerifyError::LeafMismatch)
+        );
+        assert_eq!(
+            verify_node(&proof[1..], &root, &nodes[0]),
+            Err(MerkleVerifyError::LeafMismatch)
+        );
+        assert_eq!(
+            verify_proof_for_root(&proof, &root, &root, &hash_leaf(&nodes[0])),
+            Ok(())
+        );
+        assert_eq!(
+            verify_proof_for_root(&proof, &root, &[1; 32], &hash_leaf(&nodes[0])),
+            Err(MerkleVerifyError::WrongRoot {
+                expected: root,
+                actual: [1; 32]
+            })
+        );
+        assert_eq!(
+            verify_proof_len(&proof[1..], nodes.len() as u64),
+            Err(MerkleVerifyError::WrongProofLength {
+                expected: 3,
+                actual: 2
+            })
+        );
+
+        assert_eq!(get_proof_len(0), 0);
+        assert_eq!(get_proof_len(1), 0);
+        assert_eq!(get_proof_len(2), 1);
+        assert_eq!(get_proof_len(4), 2);
+        assert_eq!(get_proof_len(5), 3);
+        assert_eq!(get_proof_len(2u64.pow(32) - 1), 32);
     }
-    // Check if the computed hash (root) is equal to the provided root
-    computed_hash == root
 }
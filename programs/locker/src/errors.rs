@@ -74,4 +74,46 @@ pub enum LockerError {
 
     #[msg("Invalid params")]
     InvalidParams,
+
+    #[msg("Invalid recipient shares")]
+    InvalidRecipientShares,
+
+    #[msg("Invalid escrow recipients")]
+    InvalidEscrowRecipients,
+
+    #[msg("Mint is paused")]
+    MintPaused,
+
+    #[msg("Invalid clawback deadline")]
+    InvalidClawbackDeadline,
+
+    #[msg("Clawback deadline is not reached")]
+    ClawbackDeadlineNotReached,
+
+    #[msg("Clawback deadline has passed")]
+    ClawbackDeadlinePassed,
+
+    #[msg("Root escrow is not fully distributed")]
+    RootEscrowNotFullyDistributed,
+
+    #[msg("Root escrow distribution has started")]
+    RootEscrowDistributionStarted,
+
+    #[msg("Invalid distribution mode")]
+    InvalidDistributionMode,
+
+    #[msg("Leaf is already claimed")]
+    LeafAlreadyClaimed,
+
+    #[msg("Leaf is not fully unlocked")]
+    LeafNotFullyUnlocked,
+
+    #[msg("Escrow is created from root escrow")]
+    EscrowCreatedFromRoot,
+
+    #[msg("Invalid root escrow mint")]
+    InvalidRootEscrowMint,
+
+    #[msg("Root escrow is not closed")]
+    RootEscrowNotClosed,
 }
@@ -14,6 +14,13 @@ pub struct EventCreateVestingEscrow {
     pub escrow: Pubkey,
 }
 
+#[event]
+pub struct EventCreateVestingEscrowRecipients {
+    pub escrow: Pubkey,
+    pub recipients: Vec<Pubkey>,
+    pub share_bps: Vec<u16>,
+}
+
 #[event]
 pub struct EventCreateRootEscrow {
     pub root_escrow: Pubkey,
@@ -21,6 +28,9 @@ pub struct EventCreateRootEscrow {
     pub max_escrow: u64,
     pub version: u64,
     pub root: [u8; 32],
+    pub clawback_deadline: u64,
+    pub leaf_version: u8,
+    pub distribution_mode: u8,
 }
 
 #[event]
@@ -30,6 +40,27 @@ pub struct EventClaim {
     pub escrow: Pubkey,
 }
 
+#[event]
+pub struct EventClaimMultiRecipient {
+    pub amount: u64,
+    pub current_ts: u64,
+    pub escrow: Pubkey,
+    pub recipient: Pubkey,
+}
+
+/// UI amount of tokens paid out to a recipient or creator, for mints with an extension changing
+/// how amounts are displayed
+#[event]
+pub struct EventTransferUiAmount {
+    pub amount: u64,
+    pub ui_amount: String,
+    pub current_ts: u64,
+    /// escrow or root escrow paying the tokens
+    pub escrow: Pubkey,
+    /// token account receiving the tokens
+    pub destination: Pubkey,
+}
+
 #[event]
 pub struct EventClaimV3 {
     pub amount: u64,
@@ -86,3 +117,92 @@ pub struct EventFundRootEscrow {
     pub root_escrow: Pubkey,
     pub funded_amount: u64,
 }
+
+#[event]
+pub struct EventClawbackRootEscrow {
+    pub root_escrow: Pubkey,
+    pub token_mint: Pubkey,
+    pub clawback_amount: u64,
+}
+
+#[event]
+pub struct EventUpdateRootEscrowRoot {
+    pub root_escrow: Pubkey,
+    pub old_root: [u8; 32],
+    pub new_root: [u8; 32],
+    pub max_claim_amount: u64,
+    pub max_escrow: u64,
+}
+
+#[event]
+pub struct EventCloseRootEscrow {
+    pub root_escrow: Pubkey,
+}
+
+#[event]
+pub struct EventCreateRootEscrowMint {
+    pub root_escrow: Pubkey,
+    pub token_mint: Pubkey,
+    pub max_claim_amount: u64,
+}
+
+#[event]
+pub struct EventCloseRootEscrowMint {
+    pub root_escrow: Pubkey,
+    pub token_mint: Pubkey,
+}
+
+#[event]
+pub struct EventDepositRootEscrowRentPool {
+    pub root_escrow: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct EventReimburseRootEscrowRent {
+    pub root_escrow: Pubkey,
+    pub escrow: Pubkey,
+    pub payer: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct EventClaimFromRootEscrow {
+    pub root_escrow: Pubkey,
+    pub claim_status: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub current_ts: u64,
+}
+
+#[event]
+pub struct EventClaimFromRootEscrowBitmap {
+    pub root_escrow: Pubkey,
+    pub recipient: Pubkey,
+    pub leaf_index: u64,
+    pub amount: u64,
+    pub current_ts: u64,
+}
+
+#[event]
+pub struct EventCloseRootEscrowClaimStatus {
+    pub root_escrow: Pubkey,
+    pub claim_status: Pubkey,
+    pub recipient: Pubkey,
+}
+
+#[event]
+pub struct EventCloseRootEscrowClaimBitmap {
+    pub root_escrow: Pubkey,
+    pub claim_bitmap: Pubkey,
+}
+
+#[event]
+pub struct EventCancelVestingEscrowFromRoot {
+    pub escrow: Pubkey,
+    pub root_escrow: Pubkey,
+    pub signer: Pubkey,
+    pub claimable_amount: u64,
+    pub remaining_amount: u64,
+    pub cancelled_at: u64,
+}
@@ -8,7 +8,7 @@ use util::{
 };
 
 use crate::safe_math::SafeMath;
-use crate::util::{transfer_to_user2, MemoTransferContext};
+use crate::util::{get_transfer_ui_amount_event, transfer_to_user2, MemoTransferContext};
 use crate::*;
 
 /// Accounts for [locker::cancel_vesting_escrow].
@@ -19,7 +19,8 @@ pub struct CancelVestingEscrowCtx<'info> {
     #[account(
         mut,
         has_one = token_mint,
-        constraint = escrow.load()?.cancelled_at == 0 @ LockerError::AlreadyCancelled
+        constraint = escrow.load()?.cancelled_at == 0 @ LockerError::AlreadyCancelled,
+        constraint = !escrow.load()?.is_created_from_root() @ LockerError::EscrowCreatedFromRoot
     )]
     pub escrow: AccountLoader<'info, VestingEscrow>,
 
@@ -69,46 +70,88 @@ pub struct CancelVestingEscrowCtx<'info> {
     pub token_program: Interface<'info, TokenInterface>,
 }
 
-impl<'info> CancelVestingEscrowCtx<'info> {
-    fn close_escrow_token(&self) -> Result<()> {
-        let escrow = self.escrow.load()?;
-        let escrow_seeds = escrow_seeds!(escrow);
-
-        close_account(CpiContext::new_with_signer(
-            self.token_program.to_account_info(),
-            CloseAccount {
-                account: self.escrow_token.to_account_info(),
-                destination: self.rent_receiver.to_account_info(),
-                authority: self.escrow.to_account_info(),
-            },
-            &[&escrow_seeds[..]],
-        ))?;
-
-        Ok(())
-    }
-}
-
 pub fn handle_cancel_vesting_escrow<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, CancelVestingEscrowCtx<'info>>,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
 ) -> Result<()> {
-    let mut escrow = ctx.accounts.escrow.load_mut()?;
-    let signer = ctx.accounts.signer.key();
-    escrow.validate_cancel_actor(signer)?;
+    let cancelled = process_cancel_vesting_escrow(
+        CancelVestingEscrowAccounts {
+            escrow: &ctx.accounts.escrow,
+            token_mint: &ctx.accounts.token_mint,
+            escrow_token: &ctx.accounts.escrow_token,
+            recipient_token: &ctx.accounts.recipient_token,
+            remaining_token: &ctx.accounts.creator_token,
+            rent_receiver: &ctx.accounts.rent_receiver,
+            signer: &ctx.accounts.signer,
+            memo_program: &ctx.accounts.memo_program,
+            token_program: &ctx.accounts.token_program,
+        },
+        ctx.remaining_accounts,
+        remaining_accounts_info,
+    )?;
+
+    emit_cpi!(EventCancelVestingEscrow {
+        escrow: ctx.accounts.escrow.key(),
+        signer: ctx.accounts.signer.key(),
+        claimable_amount: cancelled.claimable_amount,
+        remaining_amount: cancelled.remaining_amount,
+        cancelled_at: cancelled.cancelled_at,
+    });
+    for event in [
+        cancelled.claimable_ui_amount_event,
+        cancelled.remaining_ui_amount_event,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        emit_cpi!(event);
+    }
+    Ok(())
+}
+
+/// Accounts used to cancel a vesting escrow, shared by [locker::cancel_vesting_escrow] and the
+/// cancel endpoints of escrows created from root escrow.
+pub struct CancelVestingEscrowAccounts<'a, 'info> {
+    pub escrow: &'a AccountLoader<'info, VestingEscrow>,
+    pub token_mint: &'a InterfaceAccount<'info, Mint>,
+    pub escrow_token: &'a InterfaceAccount<'info, TokenAccount>,
+    pub recipient_token: &'a InterfaceAccount<'info, TokenAccount>,
+    /// Token account receiving the remaining amount, of the creator or of root escrow
+    pub remaining_token: &'a InterfaceAccount<'info, TokenAccount>,
+    pub rent_receiver: &'a AccountInfo<'info>,
+    pub signer: &'a Signer<'info>,
+    pub memo_program: &'a Program<'info, Memo>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+}
+
+/// Amounts of a cancelled vesting escrow, with the UI amount events of its transfers to emit
+pub struct CancelledVestingEscrow {
+    pub claimable_amount: u64,
+    pub remaining_amount: u64,
+    pub cancelled_at: u64,
+    pub claimable_ui_amount_event: Option<EventTransferUiAmount>,
+    pub remaining_ui_amount_event: Option<EventTransferUiAmount>,
+}
+
+/// Cancel the escrow, transferring the claimable amount to the recipient and the remaining
+/// amount to `remaining_token`, then close the escrow token account to `rent_receiver`
+pub fn process_cancel_vesting_escrow<'c: 'info, 'info>(
+    accounts: CancelVestingEscrowAccounts<'_, 'info>,
+    remaining_accounts: &'c [AccountInfo<'info>],
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<CancelledVestingEscrow> {
+    let mut escrow = accounts.escrow.load_mut()?;
+    escrow.validate_cancel_actor(accounts.signer.key())?;
 
     let current_ts = Clock::get()?.unix_timestamp as u64;
     let claimable_amount = escrow.get_claimable_amount(current_ts)?;
-    let remaining_amount = ctx
-        .accounts
-        .escrow_token
-        .amount
-        .safe_sub(claimable_amount)?;
+    let remaining_amount = accounts.escrow_token.amount.safe_sub(claimable_amount)?;
     escrow.cancelled_at = current_ts;
     require!(escrow.cancelled_at > 0, LockerError::CancelledAtIsZero);
     drop(escrow);
 
     // Process remaining accounts
-    let mut remaining_accounts = &ctx.remaining_accounts[..];
+    let mut remaining_accounts = remaining_accounts;
     let parsed_transfer_hook_accounts = match remaining_accounts_info {
         Some(info) => parse_remaining_accounts(
             &mut remaining_accounts,
@@ -118,51 +161,63 @@ pub fn handle_cancel_vesting_escrow<'c: 'info, 'info>(
         None => ParsedRemainingAccounts::default(),
     };
 
-    // Transfer the claimable amount to the recipient
-    transfer_to_user2(
-        &ctx.accounts.escrow,
-        &ctx.accounts.token_mint,
-        &ctx.accounts.escrow_token.to_account_info(),
-        &ctx.accounts.recipient_token,
-        &ctx.accounts.token_program,
-        Some(MemoTransferContext {
-            memo_program: &ctx.accounts.memo_program,
-            memo: TRANSFER_MEMO_CANCEL_VESTING.as_bytes(),
-        }),
-        claimable_amount,
-        parsed_transfer_hook_accounts.transfer_hook_escrow,
-    )?;
-
-    // Transfer the remaining amount to the creator
-    transfer_to_user2(
-        &ctx.accounts.escrow,
-        &ctx.accounts.token_mint,
-        &ctx.accounts.escrow_token.to_account_info(),
-        &ctx.accounts.creator_token,
-        &ctx.accounts.token_program,
-        Some(MemoTransferContext {
-            memo_program: &ctx.accounts.memo_program,
-            memo: TRANSFER_MEMO_CANCEL_VESTING.as_bytes(),
-        }),
-        remaining_amount,
-        parsed_transfer_hook_accounts.transfer_hook_escrow,
-    )?;
+    // Transfer the claimable amount to the recipient, and the remaining amount
+    for (destination, amount) in [
+        (accounts.recipient_token, claimable_amount),
+        (accounts.remaining_token, remaining_amount),
+    ] {
+        transfer_to_user2(
+            accounts.escrow,
+            accounts.token_mint,
+            &accounts.escrow_token.to_account_info(),
+            destination,
+            accounts.token_program,
+            Some(MemoTransferContext {
+                memo_program: accounts.memo_program,
+                memo: TRANSFER_MEMO_CANCEL_VESTING.as_bytes(),
+            }),
+            amount,
+            parsed_transfer_hook_accounts.transfer_hook_escrow,
+        )?;
+    }
 
     // Do fee harvesting
     harvest_fees(
-        &ctx.accounts.token_program,
-        &ctx.accounts.escrow_token.to_account_info(),
-        &ctx.accounts.token_mint,
+        accounts.token_program,
+        &accounts.escrow_token.to_account_info(),
+        accounts.token_mint,
     )?;
 
-    ctx.accounts.close_escrow_token()?;
-
-    emit_cpi!(EventCancelVestingEscrow {
-        escrow: ctx.accounts.escrow.key(),
-        signer,
+    // close escrow token
+    let escrow = accounts.escrow.load()?;
+    let escrow_seeds = escrow_seeds!(escrow);
+    close_account(CpiContext::new_with_signer(
+        accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: accounts.escrow_token.to_account_info(),
+            destination: accounts.rent_receiver.to_account_info(),
+            authority: accounts.escrow.to_account_info(),
+        },
+        &[&escrow_seeds[..]],
+    ))?;
+
+    Ok(CancelledVestingEscrow {
         claimable_amount,
         remaining_amount,
         cancelled_at: current_ts,
-    });
-    Ok(())
+        claimable_ui_amount_event: get_transfer_ui_amount_event(
+            accounts.token_mint,
+            accounts.escrow.key(),
+            accounts.recipient_token.key(),
+            claimable_amount,
+            current_ts,
+        )?,
+        remaining_ui_amount_event: get_transfer_ui_amount_event(
+            accounts.token_mint,
+            accounts.escrow.key(),
+            accounts.remaining_token.key(),
+            remaining_amount,
+            current_ts,
+        )?,
+    })
 }
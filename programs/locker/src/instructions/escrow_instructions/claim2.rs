@@ -1,4 +1,4 @@
-use crate::util::{transfer_to_user2, MemoTransferContext};
+use crate::util::{get_transfer_ui_amount_event, transfer_to_user2, MemoTransferContext};
 use crate::*;
 use anchor_spl::memo::Memo;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
@@ -13,7 +13,8 @@ pub struct Claim2Ctx<'info> {
     #[account(
         mut,
         has_one = token_mint,
-        has_one = recipient,
+        // recipients of a multi recipient escrow are checked against the escrow recipients account
+        constraint = escrow.load()?.recipient == recipient.key() || escrow.load()?.is_multi_recipient() @ ErrorCode::ConstraintHasOne,
         constraint = escrow.load()?.cancelled_at == 0 @ LockerError::AlreadyCancelled
     )]
     pub escrow: AccountLoader<'info, VestingEscrow>,
@@ -53,22 +54,48 @@ pub fn handle_claim2<'c: 'info, 'info>(
     max_amount: u64,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
 ) -> Result<()> {
-    let mut escrow = ctx.accounts.escrow.load_mut()?;
-
-    let amount = escrow.claim(max_amount)?;
-    drop(escrow);
-
     // Process remaining accounts
     let mut remaining_accounts = &ctx.remaining_accounts[..];
-    let parsed_transfer_hook_accounts = match remaining_accounts_info {
+    let parsed_remaining_accounts = match remaining_accounts_info {
         Some(info) => parse_remaining_accounts(
             &mut remaining_accounts,
             &info.slices,
-            &[AccountsType::TransferHookEscrow],
+            &[
+                AccountsType::TransferHookEscrow,
+                AccountsType::EscrowRecipients,
+            ],
         )?,
         None => ParsedRemainingAccounts::default(),
     };
 
+    let mut escrow = ctx.accounts.escrow.load_mut()?;
+    let is_multi_recipient = escrow.is_multi_recipient();
+    let amount = if is_multi_recipient {
+        // the recipient claims its share, tracked by the escrow recipients account
+        let Some([escrow_recipients_info]) = parsed_remaining_accounts.escrow_recipients else {
+            return Err(LockerError::InvalidEscrowRecipients.into());
+        };
+        require!(
+            escrow_recipients_info.key() == escrow.recipient,
+            LockerError::InvalidEscrowRecipients
+        );
+        let escrow_recipients =
+            AccountLoader::<VestingEscrowRecipients>::try_from(escrow_recipients_info)?;
+        let mut escrow_recipients = escrow_recipients.load_mut()?;
+        require!(
+            escrow_recipients.escrow == ctx.accounts.escrow.key(),
+            LockerError::InvalidEscrowRecipients
+        );
+        escrow_recipients.claim(&mut escrow, ctx.accounts.recipient.key(), max_amount)?
+    } else {
+        require!(
+            parsed_remaining_accounts.escrow_recipients.is_none(),
+            LockerError::InvalidEscrowRecipients
+        );
+        escrow.claim(max_amount)?
+    };
+    drop(escrow);
+
     transfer_to_user2(
         &ctx.accounts.escrow,
         &ctx.accounts.token_mint,
@@ -80,14 +107,32 @@ pub fn handle_claim2<'c: 'info, 'info>(
             memo: TRANSFER_MEMO_CLAIM_VESTING.as_bytes(),
         }),
         amount,
-        parsed_transfer_hook_accounts.transfer_hook_escrow,
+        parsed_remaining_accounts.transfer_hook_escrow,
     )?;
 
     let current_ts = Clock::get()?.unix_timestamp as u64;
-    emit_cpi!(EventClaim {
+    if is_multi_recipient {
+        emit_cpi!(EventClaimMultiRecipient {
+            amount,
+            current_ts,
+            escrow: ctx.accounts.escrow.key(),
+            recipient: ctx.accounts.recipient.key(),
+        });
+    } else {
+        emit_cpi!(EventClaim {
+            amount,
+            current_ts,
+            escrow: ctx.accounts.escrow.key(),
+        });
+    }
+    if let Some(event) = get_transfer_ui_amount_event(
+        &ctx.accounts.token_mint,
+        ctx.accounts.escrow.key(),
+        ctx.accounts.recipient_token.key(),
         amount,
         current_ts,
-        escrow: ctx.accounts.escrow.key(),
-    });
+    )? {
+        emit_cpi!(event);
+    }
     Ok(())
 }
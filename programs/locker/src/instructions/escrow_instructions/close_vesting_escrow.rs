@@ -4,8 +4,9 @@ use anchor_spl::{
     token_interface::{close_account, CloseAccount, Mint, TokenAccount, TokenInterface},
 };
 use util::{
-    close, harvest_fees, is_closed, parse_remaining_accounts, transfer_to_user2, AccountsType,
-    MemoTransferContext, ParsedRemainingAccounts, TRANSFER_MEMO_CLOSE_ESCROW,
+    close, get_transfer_ui_amount_event, harvest_fees, is_closed, parse_remaining_accounts,
+    transfer_to_user2, AccountsType, MemoTransferContext, ParsedRemainingAccounts,
+    TRANSFER_MEMO_CLOSE_ESCROW,
 };
 
 /// Accounts for [locker::close_vesting_escrow].
@@ -57,7 +58,44 @@ pub fn handle_close_vesting_escrow<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, CloseVestingEscrowCtx<'info>>,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
 ) -> Result<()> {
+    // Process remaining accounts
+    let mut remaining_accounts = &ctx.remaining_accounts[..];
+    let parsed_remaining_accounts = match remaining_accounts_info {
+        Some(info) => parse_remaining_accounts(
+            &mut remaining_accounts,
+            &info.slices,
+            &[
+                AccountsType::TransferHookEscrow,
+                AccountsType::EscrowRecipients,
+            ],
+        )?,
+        None => ParsedRemainingAccounts::default(),
+    };
+
     let escrow = ctx.accounts.escrow.load()?;
+    // escrow recipients of a multi recipient escrow are closed with the escrow
+    let escrow_recipients_info = if escrow.is_multi_recipient() {
+        let Some([escrow_recipients_info]) = parsed_remaining_accounts.escrow_recipients else {
+            return Err(LockerError::InvalidEscrowRecipients.into());
+        };
+        require!(
+            escrow_recipients_info.key() == escrow.recipient,
+            LockerError::InvalidEscrowRecipients
+        );
+        let escrow_recipients =
+            AccountLoader::<VestingEscrowRecipients>::try_from(escrow_recipients_info)?;
+        require!(
+            escrow_recipients.load()?.escrow == ctx.accounts.escrow.key(),
+            LockerError::InvalidEscrowRecipients
+        );
+        Some(escrow_recipients_info)
+    } else {
+        require!(
+            parsed_remaining_accounts.escrow_recipients.is_none(),
+            LockerError::InvalidEscrowRecipients
+        );
+        None
+    };
     // if escrow is not cancelled, we need to close escrow token account
     if escrow.cancelled_at == 0 {
         require!(
@@ -78,15 +116,6 @@ pub fn handle_close_vesting_escrow<'c: 'info, 'info>(
         let amount = anchor_spl::token::accessor::amount(&ctx.accounts.escrow_token)?;
         if amount > 0 {
             // Transfer the remaining amount to the creator, in case someone wrongly send token to escrow_token
-            let mut remaining_accounts = &ctx.remaining_accounts[..];
-            let parsed_transfer_hook_accounts = match remaining_accounts_info {
-                Some(info) => parse_remaining_accounts(
-                    &mut remaining_accounts,
-                    &info.slices,
-                    &[AccountsType::TransferHookEscrow],
-                )?,
-                None => ParsedRemainingAccounts::default(),
-            };
             transfer_to_user2(
                 &ctx.accounts.escrow,
                 &ctx.accounts.token_mint,
@@ -98,8 +127,17 @@ pub fn handle_close_vesting_escrow<'c: 'info, 'info>(
                     memo: TRANSFER_MEMO_CLOSE_ESCROW.as_bytes(),
                 }),
                 amount,
-                parsed_transfer_hook_accounts.transfer_hook_escrow,
+                parsed_remaining_accounts.transfer_hook_escrow,
             )?;
+            if let Some(event) = get_transfer_ui_amount_event(
+                &ctx.accounts.token_mint,
+                ctx.accounts.escrow.key(),
+                ctx.accounts.creator_token.key(),
+                amount,
+                Clock::get()?.unix_timestamp as u64,
+            )? {
+                emit_cpi!(event);
+            }
         }
 
         // Do fee harvesting
@@ -130,6 +168,13 @@ pub fn handle_close_vesting_escrow<'c: 'info, 'info>(
         )?;
     }
 
+    if let Some(escrow_recipients_info) = escrow_recipients_info {
+        close(
+            escrow_recipients_info.clone(),
+            ctx.accounts.creator.to_account_info(),
+        )?;
+    }
+
     emit_cpi!(EventCloseVestingEscrow {
         escrow: ctx.accounts.escrow.key(),
     });
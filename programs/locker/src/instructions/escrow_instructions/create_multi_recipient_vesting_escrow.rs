@@ -0,0 +1,221 @@
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::util::{
+    calculate_transfer_fee_included_amount, parse_remaining_accounts, transfer_to_escrow2,
+    validate_mint, AccountsType, ParsedRemainingAccounts,
+};
+use crate::RecipientFlag::MultipleRecipients;
+use crate::TokenProgramFlag::{UseSplToken, UseToken2022};
+use crate::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RecipientShareParameters {
+    pub recipient: Pubkey,
+    pub share_bps: u16,
+}
+
+fn validate_recipient_shares(
+    params: &CreateVestingEscrowParameters,
+    recipients: &[RecipientShareParameters],
+) -> Result<()> {
+    require!(
+        !recipients.is_empty() && recipients.len() <= MAX_ESCROW_RECIPIENTS,
+        LockerError::InvalidRecipientShares
+    );
+
+    let mut total_share_bps: u16 = 0;
+    for (i, recipient) in recipients.iter().enumerate() {
+        require!(
+            recipient.share_bps > 0 && recipient.recipient != Pubkey::default(),
+            LockerError::InvalidRecipientShares
+        );
+        require!(
+            recipients[..i]
+                .iter()
+                .all(|other| other.recipient != recipient.recipient),
+            LockerError::InvalidRecipientShares
+        );
+        total_share_bps = total_share_bps
+            .checked_add(recipient.share_bps)
+            .ok_or(LockerError::InvalidRecipientShares)?;
+    }
+    require!(
+        total_share_bps == MAX_SHARE_BPS,
+        LockerError::InvalidRecipientShares
+    );
+
+    // recipient of a shared escrow can't be updated, and cancellation would need to split the
+    // claimable amount between recipients
+    require!(
+        params.update_recipient_mode == u8::from(UpdateRecipientMode::NeitherCreatorOrRecipient),
+        LockerError::InvalidUpdateRecipientMode
+    );
+    require!(
+        params.cancel_mode == u8::from(CancelMode::NeitherCreatorOrRecipient),
+        LockerError::InvalidCancelMode
+    );
+
+    Ok(())
+}
+
+/// Accounts for [locker::create_multi_recipient_vesting_escrow].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateMultiRecipientVestingEscrowCtx<'info> {
+    /// Base.
+    #[account(mut)]
+    pub base: Signer<'info>,
+
+    /// Escrow.
+    #[account(
+        init,
+        seeds = [
+            b"escrow".as_ref(),
+            base.key().as_ref(),
+        ],
+        bump,
+        payer = sender,
+        space = 8 + VestingEscrow::INIT_SPACE
+    )]
+    pub escrow: AccountLoader<'info, VestingEscrow>,
+
+    /// Escrow recipients.
+    #[account(
+        init,
+        seeds = [
+            b"escrow_recipients".as_ref(),
+            escrow.key().as_ref(),
+        ],
+        bump,
+        payer = sender,
+        space = 8 + VestingEscrowRecipients::INIT_SPACE
+    )]
+    pub escrow_recipients: AccountLoader<'info, VestingEscrowRecipients>,
+
+    // Mint.
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Escrow Token Account.
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Sender.
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    /// Sender Token Account.
+    #[account(mut)]
+    pub sender_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// system program.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_multi_recipient_vesting_escrow<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, CreateMultiRecipientVestingEscrowCtx<'info>>,
+    params: &CreateVestingEscrowParameters,
+    recipients: &[RecipientShareParameters],
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    // Validate if token_mint is supported
+    validate_mint(&ctx.accounts.token_mint, true)?;
+    validate_recipient_shares(params, recipients)?;
+    params.validate()?;
+
+    let token_mint_info = ctx.accounts.token_mint.to_account_info();
+    let token_program_flag = match *token_mint_info.owner {
+        spl_token::ID => Ok(UseSplToken),
+        spl_token_2022::ID => Ok(UseToken2022),
+        _ => Err(LockerError::IncorrectTokenProgramId),
+    }?;
+
+    {
+        // the escrow recipient is the recipients account, which can't sign for single recipient
+        // instructions
+        let mut escrow = ctx.accounts.escrow.load_init()?;
+        escrow.init(
+            params.vesting_start_time,
+            params.cliff_time,
+            params.frequency,
+            params.cliff_unlock_amount,
+            params.amount_per_period,
+            params.number_of_period,
+            ctx.accounts.escrow_recipients.key(),
+            ctx.accounts.sender_token.mint,
+            ctx.accounts.sender.key(),
+            ctx.accounts.base.key(),
+            ctx.bumps.escrow,
+            params.update_recipient_mode,
+            params.cancel_mode,
+            token_program_flag.into(),
+        );
+        escrow.recipient_flag = MultipleRecipients.into();
+    }
+
+    let mut escrow_recipients = ctx.accounts.escrow_recipients.load_init()?;
+    escrow_recipients.init(ctx.accounts.escrow.key(), recipients);
+    drop(escrow_recipients);
+
+    // Process remaining accounts
+    let mut remaining_accounts = ctx.remaining_accounts;
+    let parsed_transfer_hook_accounts = match remaining_accounts_info {
+        Some(info) => parse_remaining_accounts(
+            &mut remaining_accounts,
+            &info.slices,
+            &[AccountsType::TransferHookEscrow],
+        )?,
+        None => ParsedRemainingAccounts::default(),
+    };
+
+    transfer_to_escrow2(
+        &ctx.accounts.sender,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.sender_token,
+        &ctx.accounts.escrow_token,
+        &ctx.accounts.token_program,
+        calculate_transfer_fee_included_amount(
+            params.get_total_deposit_amount()?,
+            &ctx.accounts.token_mint,
+        )?,
+        parsed_transfer_hook_accounts.transfer_hook_escrow,
+    )?;
+
+    let &CreateVestingEscrowParameters {
+        vesting_start_time,
+        cliff_time,
+        frequency,
+        cliff_unlock_amount,
+        amount_per_period,
+        number_of_period,
+        update_recipient_mode,
+        cancel_mode,
+    } = params;
+    emit_cpi!(EventCreateVestingEscrow {
+        vesting_start_time,
+        cliff_time,
+        frequency,
+        cliff_unlock_amount,
+        amount_per_period,
+        number_of_period,
+        recipient: ctx.accounts.escrow_recipients.key(),
+        escrow: ctx.accounts.escrow.key(),
+        update_recipient_mode,
+        cancel_mode,
+    });
+    emit_cpi!(EventCreateVestingEscrowRecipients {
+        escrow: ctx.accounts.escrow.key(),
+        recipients: recipients.iter().map(|r| r.recipient).collect(),
+        share_bps: recipients.iter().map(|r| r.share_bps).collect(),
+    });
+    Ok(())
+}
@@ -4,6 +4,7 @@ use crate::safe_math::SafeMath;
 use crate::util::token::transfer_to_escrow;
 use crate::TokenProgramFlag::UseSplToken;
 use crate::*;
+use vesting_validate::{VestingParams, VestingValidateError};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 /// Accounts for [locker::create_vesting_escrow].
@@ -27,27 +28,45 @@ impl CreateVestingEscrowParameters {
         Ok(total_amount)
     }
 
-    pub fn validate(&self) -> Result<()> {
-        require!(
-            self.cliff_time >= self.vesting_start_time,
-            LockerError::InvalidVestingStartTime
-        );
-
-        require!(
-            UpdateRecipientMode::try_from(self.update_recipient_mode).is_ok(),
-            LockerError::InvalidUpdateRecipientMode,
-        );
-
-        require!(
-            CancelMode::try_from(self.cancel_mode).is_ok(),
-            LockerError::InvalidCancelMode,
-        );
+    pub fn get_max_unlocked_amount(&self, current_ts: u64) -> Result<u64> {
+        let escrow = VestingEscrow {
+            cliff_time: self.cliff_time,
+            frequency: self.frequency,
+            cliff_unlock_amount: self.cliff_unlock_amount,
+            amount_per_period: self.amount_per_period,
+            number_of_period: self.number_of_period,
+            ..Default::default()
+        };
+        escrow.get_max_unlocked_amount(current_ts)
+    }
 
-        if self.amount_per_period > 0 && self.number_of_period > 0 {
-            require!(self.frequency != 0, LockerError::FrequencyIsZero);
+    pub fn to_vesting_params(&self) -> VestingParams {
+        VestingParams {
+            vesting_start_time: self.vesting_start_time,
+            cliff_time: self.cliff_time,
+            frequency: self.frequency,
+            cliff_unlock_amount: self.cliff_unlock_amount,
+            amount_per_period: self.amount_per_period,
+            number_of_period: self.number_of_period,
+            update_recipient_mode: self.update_recipient_mode,
+            cancel_mode: self.cancel_mode,
         }
+    }
 
-        Ok(())
+    pub fn validate(&self) -> Result<()> {
+        match self.to_vesting_params().validate() {
+            Ok(()) => Ok(()),
+            Err(VestingValidateError::InvalidVestingStartTime { .. }) => {
+                err!(LockerError::InvalidVestingStartTime)
+            }
+            Err(VestingValidateError::InvalidUpdateRecipientMode(_)) => {
+                err!(LockerError::InvalidUpdateRecipientMode)
+            }
+            Err(VestingValidateError::InvalidCancelMode(_)) => {
+                err!(LockerError::InvalidCancelMode)
+            }
+            Err(VestingValidateError::FrequencyIsZero) => err!(LockerError::FrequencyIsZero),
+        }
     }
 
     pub fn init_escrow(
@@ -59,6 +78,7 @@ impl CreateVestingEscrowParameters {
         base: Pubkey,
         escrow_bump: u8,
         token_program_flag: u8,
+        root_escrow: Pubkey,
     ) -> Result<()> {
         self.validate()?;
 
@@ -79,6 +99,7 @@ impl CreateVestingEscrowParameters {
             self.cancel_mode,
             token_program_flag,
         );
+        escrow.root_escrow = root_escrow;
 
         Ok(())
     }
@@ -142,6 +163,7 @@ pub fn handle_create_vesting_escrow(
         ctx.accounts.base.key(),
         ctx.bumps.escrow,
         UseSplToken.into(),
+        Pubkey::default(),
     )?;
 
     transfer_to_escrow(
@@ -82,6 +82,7 @@ pub fn handle_create_vesting_escrow2<'c: 'info, 'info>(
         ctx.accounts.base.key(),
         ctx.bumps.escrow,
         token_program_flag.into(),
+        Pubkey::default(),
     )?;
 
     // Process remaining accounts
@@ -19,3 +19,6 @@ pub mod close_vesting_escrow;
 pub mod create_vesting_escrow_metadata;
 pub mod update_vesting_escrow_recipient;
 pub use close_vesting_escrow::*;
+
+pub mod create_multi_recipient_vesting_escrow;
+pub use create_multi_recipient_vesting_escrow::*;
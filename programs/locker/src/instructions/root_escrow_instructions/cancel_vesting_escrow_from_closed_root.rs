@@ -0,0 +1,109 @@
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use util::is_closed;
+
+use crate::*;
+
+/// Accounts for [locker::cancel_vesting_escrow_from_closed_root].
+#[derive(Accounts)]
+#[event_cpi]
+pub struct CancelVestingEscrowFromClosedRootCtx<'info> {
+    /// Escrow.
+    #[account(
+        mut,
+        has_one = token_mint,
+        constraint = escrow.load()?.cancelled_at == 0 @ LockerError::AlreadyCancelled
+    )]
+    pub escrow: AccountLoader<'info, VestingEscrow>,
+
+    /// CHECK: Closed Root Escrow that the escrow is created from.
+    #[account(
+        address = escrow.load()?.root_escrow,
+        constraint = is_closed(&root_escrow) @ LockerError::RootEscrowNotClosed
+    )]
+    pub root_escrow: UncheckedAccount<'info>,
+
+    /// Mint.
+    #[account(mut)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Escrow Token Account.
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Creator Token Account.
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = escrow.load()?.creator,
+        token::token_program = token_program
+    )]
+    pub creator_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Receipient Token Account.
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = escrow.load()?.recipient,
+        token::token_program = token_program
+    )]
+    pub recipient_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECKED: The creator of root escrow will receive the rent of the Token Account
+    #[account(mut, address = escrow.load()?.creator)]
+    pub rent_receiver: UncheckedAccount<'info>,
+
+    /// Signer.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Memo program.
+    pub memo_program: Program<'info, Memo>,
+
+    /// Token program.
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_cancel_vesting_escrow_from_closed_root<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, CancelVestingEscrowFromClosedRootCtx<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let cancelled = process_cancel_vesting_escrow(
+        CancelVestingEscrowAccounts {
+            escrow: &ctx.accounts.escrow,
+            token_mint: &ctx.accounts.token_mint,
+            escrow_token: &ctx.accounts.escrow_token,
+            recipient_token: &ctx.accounts.recipient_token,
+            remaining_token: &ctx.accounts.creator_token,
+            rent_receiver: &ctx.accounts.rent_receiver,
+            signer: &ctx.accounts.signer,
+            memo_program: &ctx.accounts.memo_program,
+            token_program: &ctx.accounts.token_program,
+        },
+        ctx.remaining_accounts,
+        remaining_accounts_info,
+    )?;
+
+    emit_cpi!(EventCancelVestingEscrow {
+        escrow: ctx.accounts.escrow.key(),
+        signer: ctx.accounts.signer.key(),
+        claimable_amount: cancelled.claimable_amount,
+        remaining_amount: cancelled.remaining_amount,
+        cancelled_at: cancelled.cancelled_at,
+    });
+    for event in [
+        cancelled.claimable_ui_amount_event,
+        cancelled.remaining_ui_amount_event,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        emit_cpi!(event);
+    }
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use util::calculate_transfer_fee_excluded_amount;
+
+use crate::*;
+
+/// Accounts for [locker::cancel_vesting_escrow_from_root].
+#[derive(Accounts)]
+#[event_cpi]
+pub struct CancelVestingEscrowFromRootCtx<'info> {
+    /// Escrow.
+    #[account(
+        mut,
+        has_one = token_mint,
+        has_one = root_escrow,
+        constraint = escrow.load()?.cancelled_at == 0 @ LockerError::AlreadyCancelled
+    )]
+    pub escrow: AccountLoader<'info, VestingEscrow>,
+
+    /// Root Escrow that the escrow is created from.
+    #[account(mut, has_one = token_mint)]
+    pub root_escrow: AccountLoader<'info, RootEscrow>,
+
+    /// Mint.
+    #[account(mut)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Escrow Token Account.
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Root Escrow Token Account.
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = root_escrow,
+        associated_token::token_program = token_program
+    )]
+    pub root_escrow_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Receipient Token Account.
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = escrow.load()?.recipient,
+        token::token_program = token_program
+    )]
+    pub recipient_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECKED: The creator of root escrow will receive the rent of the Token Account
+    #[account(mut, address = root_escrow.load()?.creator)]
+    pub rent_receiver: UncheckedAccount<'info>,
+
+    /// Signer.
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// Memo program.
+    pub memo_program: Program<'info, Memo>,
+
+    /// Token program.
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_cancel_vesting_escrow_from_root<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, CancelVestingEscrowFromRootCtx<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let cancelled = process_cancel_vesting_escrow(
+        CancelVestingEscrowAccounts {
+            escrow: &ctx.accounts.escrow,
+            token_mint: &ctx.accounts.token_mint,
+            escrow_token: &ctx.accounts.escrow_token,
+            recipient_token: &ctx.accounts.recipient_token,
+            remaining_token: &ctx.accounts.root_escrow_token,
+            rent_receiver: &ctx.accounts.rent_receiver,
+            signer: &ctx.accounts.signer,
+            memo_program: &ctx.accounts.memo_program,
+            token_program: &ctx.accounts.token_program,
+        },
+        ctx.remaining_accounts,
+        remaining_accounts_info,
+    )?;
+
+    // returned tokens can be clawed back by creator, root escrow receives them net of transfer fee
+    let returned_amount = calculate_transfer_fee_excluded_amount(
+        cancelled.remaining_amount,
+        &ctx.accounts.token_mint,
+    )?;
+    ctx.accounts
+        .root_escrow
+        .load_mut()?
+        .return_distributed_amount(returned_amount)?;
+
+    emit_cpi!(EventCancelVestingEscrowFromRoot {
+        escrow: ctx.accounts.escrow.key(),
+        root_escrow: ctx.accounts.root_escrow.key(),
+        signer: ctx.accounts.signer.key(),
+        claimable_amount: cancelled.claimable_amount,
+        remaining_amount: cancelled.remaining_amount,
+        cancelled_at: cancelled.cancelled_at,
+    });
+    if let Some(event) = cancelled.claimable_ui_amount_event {
+        emit_cpi!(event);
+    }
+    Ok(())
+}
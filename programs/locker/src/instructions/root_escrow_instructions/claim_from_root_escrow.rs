@@ -0,0 +1,160 @@
+use crate::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use util::{
+    get_transfer_ui_amount_event, parse_remaining_accounts, transfer_from_root_escrow,
+    AccountsType, ParsedRemainingAccounts,
+};
+
+/// Accounts for [locker::claim_from_root_escrow].
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: CreateVestingEscrowFromRoot2Params)]
+pub struct ClaimFromRootEscrowCtx<'info> {
+    /// Root Escrow.
+    #[account(mut, has_one = token_mint)]
+    pub root_escrow: AccountLoader<'info, RootEscrow>,
+
+    /// Claim status of the leaf, created at the first claim.
+    #[account(
+        init_if_needed,
+        seeds = [
+            b"claim_status",
+            root_escrow.key().as_ref(),
+            recipient.key().as_ref(),
+            params.get_leaf_index_seed().as_ref(),
+        ],
+        payer = recipient,
+        space = 8 + RootEscrowClaimStatus::INIT_SPACE,
+        bump,
+    )]
+    pub claim_status: AccountLoader<'info, RootEscrowClaimStatus>,
+
+    /// Mint.
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Root Escrow Token Account.
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = root_escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub root_escrow_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Recipient, pays rent of claim status.
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+
+    /// Recipient Token Account.
+    #[account(
+        mut,
+        constraint = recipient_token.key() != root_escrow_token.key() @ LockerError::InvalidRecipientTokenAccount
+    )]
+    pub recipient_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// system program.
+    pub system_program: Program<'info, System>,
+
+    /// Token program.
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_claim_from_root_escrow<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ClaimFromRootEscrowCtx<'info>>,
+    params: &CreateVestingEscrowFromRoot2Params,
+    proof: Vec<[u8; 32]>,
+    max_amount: u64,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let current_ts = Clock::get()?.unix_timestamp as u64;
+    {
+        // verify merkle tree
+        let root_escrow = ctx.accounts.root_escrow.load()?;
+        require!(
+            !root_escrow.is_clawback_started(current_ts),
+            LockerError::ClawbackDeadlinePassed
+        );
+        root_escrow.validate_distribution_mode(DistributionMode::DirectClaim)?;
+        // claim statuses can be closed once root escrow is fully distributed
+        require!(
+            !root_escrow.is_fully_distributed(),
+            LockerError::LeafAlreadyClaimed
+        );
+        params.verify_proof(
+            &root_escrow,
+            &ctx.accounts.recipient.key(),
+            &ctx.accounts.token_mint.key(),
+            &proof,
+        )?;
+    }
+
+    let vesting_params = params.into_vesting_escrow_params();
+    vesting_params.validate()?;
+    let max_unlocked_amount = vesting_params.get_max_unlocked_amount(current_ts)?;
+
+    let (amount, is_new_claim_status) = {
+        // discriminator of claim status is only written at the end of the first claim
+        let mut claim_status = match ctx.accounts.claim_status.load_mut() {
+            Ok(claim_status) => claim_status,
+            Err(_) => ctx.accounts.claim_status.load_init()?,
+        };
+        let is_new_claim_status = !claim_status.is_initialized();
+        if is_new_claim_status {
+            claim_status.init(ctx.accounts.root_escrow.key(), ctx.accounts.recipient.key());
+        }
+        (
+            claim_status.claim(max_unlocked_amount, max_amount)?,
+            is_new_claim_status,
+        )
+    };
+
+    {
+        let mut root_escrow = ctx.accounts.root_escrow.load_mut()?;
+        root_escrow.accumulate_claimed_amount(amount)?;
+        if is_new_claim_status {
+            root_escrow.update_new_claim_status()?;
+        }
+    }
+
+    if amount > 0 {
+        // Process remaining accounts
+        let mut remaining_accounts = ctx.remaining_accounts;
+        let parsed_transfer_hook_accounts = match remaining_accounts_info {
+            Some(info) => parse_remaining_accounts(
+                &mut remaining_accounts,
+                &info.slices,
+                &[AccountsType::TransferHookEscrow],
+            )?,
+            None => ParsedRemainingAccounts::default(),
+        };
+
+        transfer_from_root_escrow(
+            &ctx.accounts.root_escrow,
+            &ctx.accounts.token_mint,
+            &ctx.accounts.root_escrow_token.to_account_info(),
+            &ctx.accounts.recipient_token,
+            &ctx.accounts.token_program,
+            amount,
+            parsed_transfer_hook_accounts.transfer_hook_escrow,
+        )?;
+        if let Some(event) = get_transfer_ui_amount_event(
+            &ctx.accounts.token_mint,
+            ctx.accounts.root_escrow.key(),
+            ctx.accounts.recipient_token.key(),
+            amount,
+            current_ts,
+        )? {
+            emit_cpi!(event);
+        }
+    }
+
+    emit_cpi!(EventClaimFromRootEscrow {
+        root_escrow: ctx.accounts.root_escrow.key(),
+        claim_status: ctx.accounts.claim_status.key(),
+        recipient: ctx.accounts.recipient.key(),
+        amount,
+        current_ts,
+    });
+
+    Ok(())
+}
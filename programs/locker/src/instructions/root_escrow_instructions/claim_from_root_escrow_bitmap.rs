@@ -0,0 +1,133 @@
+use crate::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use util::{
+    get_transfer_ui_amount_event, parse_remaining_accounts, transfer_from_root_escrow,
+    AccountsType, ParsedRemainingAccounts,
+};
+
+/// Accounts for [locker::claim_from_root_escrow_bitmap].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimFromRootEscrowBitmapCtx<'info> {
+    /// Root Escrow.
+    #[account(mut, has_one = token_mint)]
+    pub root_escrow: AccountLoader<'info, RootEscrow>,
+
+    /// Claim Bitmap of the chunk including the leaf.
+    #[account(mut, has_one = root_escrow)]
+    pub claim_bitmap: AccountLoader<'info, RootEscrowClaimBitmap>,
+
+    /// Mint.
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Root Escrow Token Account.
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = root_escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub root_escrow_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Recipient.
+    pub recipient: Signer<'info>,
+
+    /// Recipient Token Account.
+    #[account(
+        mut,
+        constraint = recipient_token.key() != root_escrow_token.key() @ LockerError::InvalidRecipientTokenAccount
+    )]
+    pub recipient_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program.
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_claim_from_root_escrow_bitmap<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ClaimFromRootEscrowBitmapCtx<'info>>,
+    params: &CreateVestingEscrowFromRoot2Params,
+    proof: Vec<[u8; 32]>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let current_ts = Clock::get()?.unix_timestamp as u64;
+    let leaf_index = params.leaf_index.ok_or(LockerError::InvalidParams)?;
+    {
+        // verify merkle tree
+        let root_escrow = ctx.accounts.root_escrow.load()?;
+        require!(
+            !root_escrow.is_clawback_started(current_ts),
+            LockerError::ClawbackDeadlinePassed
+        );
+        root_escrow.validate_distribution_mode(DistributionMode::Bitmap)?;
+        // leaf index is the position of the leaf in a single mint tree of max_escrow leaves
+        require!(
+            leaf_index < root_escrow.max_escrow,
+            LockerError::InvalidParams
+        );
+        params.verify_proof(
+            &root_escrow,
+            &ctx.accounts.recipient.key(),
+            &ctx.accounts.token_mint.key(),
+            &proof,
+        )?;
+    }
+
+    // the whole leaf is claimed at once
+    let vesting_params = params.into_vesting_escrow_params();
+    vesting_params.validate()?;
+    let amount = vesting_params.get_total_deposit_amount()?;
+    require!(
+        vesting_params.get_max_unlocked_amount(current_ts)? == amount,
+        LockerError::LeafNotFullyUnlocked
+    );
+
+    ctx.accounts
+        .claim_bitmap
+        .load_mut()?
+        .set_claimed(leaf_index)?;
+    {
+        let mut root_escrow = ctx.accounts.root_escrow.load_mut()?;
+        root_escrow.update_new_claim_status()?;
+        root_escrow.accumulate_claimed_amount(amount)?;
+    }
+
+    // Process remaining accounts
+    let mut remaining_accounts = ctx.remaining_accounts;
+    let parsed_transfer_hook_accounts = match remaining_accounts_info {
+        Some(info) => parse_remaining_accounts(
+            &mut remaining_accounts,
+            &info.slices,
+            &[AccountsType::TransferHookEscrow],
+        )?,
+        None => ParsedRemainingAccounts::default(),
+    };
+
+    transfer_from_root_escrow(
+        &ctx.accounts.root_escrow,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.root_escrow_token.to_account_info(),
+        &ctx.accounts.recipient_token,
+        &ctx.accounts.token_program,
+        amount,
+        parsed_transfer_hook_accounts.transfer_hook_escrow,
+    )?;
+
+    emit_cpi!(EventClaimFromRootEscrowBitmap {
+        root_escrow: ctx.accounts.root_escrow.key(),
+        recipient: ctx.accounts.recipient.key(),
+        leaf_index,
+        amount,
+        current_ts,
+    });
+    if let Some(event) = get_transfer_ui_amount_event(
+        &ctx.accounts.token_mint,
+        ctx.accounts.root_escrow.key(),
+        ctx.accounts.recipient_token.key(),
+        amount,
+        current_ts,
+    )? {
+        emit_cpi!(event);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::util::{
+    get_transfer_ui_amount_event, parse_remaining_accounts, transfer_from_root_escrow,
+    AccountsType, ParsedRemainingAccounts,
+};
+use crate::*;
+
+/// Accounts for [locker::clawback_multi_mint_root_escrow].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClawbackMultiMintRootEscrowCtx<'info> {
+    /// Root Escrow.
+    #[account(has_one = creator)]
+    pub root_escrow: AccountLoader<'info, RootEscrow>,
+
+    /// Root Escrow Mint.
+    #[account(mut, has_one = root_escrow, has_one = token_mint)]
+    pub root_escrow_mint: AccountLoader<'info, RootEscrowMint>,
+
+    /// Mint.
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Root Escrow Token Account.
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = root_escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub root_escrow_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Creator.
+    pub creator: Signer<'info>,
+
+    /// Creator Token Account.
+    #[account(mut)]
+    pub creator_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program.
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_clawback_multi_mint_root_escrow<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ClawbackMultiMintRootEscrowCtx<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let clawback_amount = {
+        let root_escrow = ctx.accounts.root_escrow.load()?;
+        let current_ts = Clock::get()?.unix_timestamp as u64;
+        require!(
+            root_escrow.is_clawback_started(current_ts),
+            LockerError::ClawbackDeadlineNotReached
+        );
+        ctx.accounts
+            .root_escrow_mint
+            .load_mut()?
+            .get_and_set_clawback_amount()?
+    };
+    require!(clawback_amount != 0, LockerError::AmountIsZero);
+
+    // Process remaining accounts
+    let mut remaining_accounts = ctx.remaining_accounts;
+    let parsed_transfer_hook_accounts = match remaining_accounts_info {
+        Some(info) => parse_remaining_accounts(
+            &mut remaining_accounts,
+            &info.slices,
+            &[AccountsType::TransferHookEscrow],
+        )?,
+        None => ParsedRemainingAccounts::default(),
+    };
+
+    transfer_from_root_escrow(
+        &ctx.accounts.root_escrow,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.root_escrow_token.to_account_info(),
+        &ctx.accounts.creator_token,
+        &ctx.accounts.token_program,
+        clawback_amount,
+        parsed_transfer_hook_accounts.transfer_hook_escrow,
+    )?;
+
+    emit_cpi!(EventClawbackRootEscrow {
+        root_escrow: ctx.accounts.root_escrow.key(),
+        token_mint: ctx.accounts.token_mint.key(),
+        clawback_amount,
+    });
+    if let Some(event) = get_transfer_ui_amount_event(
+        &ctx.accounts.token_mint,
+        ctx.accounts.root_escrow.key(),
+        ctx.accounts.creator_token.key(),
+        clawback_amount,
+        Clock::get()?.unix_timestamp as u64,
+    )? {
+        emit_cpi!(event);
+    }
+
+    Ok(())
+}
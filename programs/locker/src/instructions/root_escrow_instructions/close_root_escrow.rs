@@ -0,0 +1,120 @@
+use anchor_spl::token_interface::{
+    close_account, CloseAccount, Mint, TokenAccount, TokenInterface,
+};
+
+use crate::util::{
+    get_transfer_ui_amount_event, harvest_fees, parse_remaining_accounts,
+    transfer_from_root_escrow, AccountsType, ParsedRemainingAccounts,
+};
+use crate::*;
+
+/// Accounts for [locker::close_root_escrow].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseRootEscrowCtx<'info> {
+    /// Root Escrow.
+    #[account(
+        mut,
+        has_one = token_mint,
+        has_one = creator,
+        close = creator,
+    )]
+    pub root_escrow: AccountLoader<'info, RootEscrow>,
+
+    /// Mint.
+    #[account(mut)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Root Escrow Token Account.
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = root_escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub root_escrow_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Creator.
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// Creator Token Account.
+    #[account(mut)]
+    pub creator_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program.
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_close_root_escrow<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, CloseRootEscrowCtx<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    {
+        let root_escrow = ctx.accounts.root_escrow.load()?;
+        let current_ts = Clock::get()?.unix_timestamp as u64;
+        require!(
+            root_escrow.is_fully_distributed() || root_escrow.is_clawback_started(current_ts),
+            LockerError::RootEscrowNotFullyDistributed
+        );
+    }
+
+    let amount = ctx.accounts.root_escrow_token.amount;
+    if amount > 0 {
+        // Transfer the remaining amount to the creator, in case of rounding or someone wrongly
+        // send token to root_escrow_token
+        let mut remaining_accounts = ctx.remaining_accounts;
+        let parsed_transfer_hook_accounts = match remaining_accounts_info {
+            Some(info) => parse_remaining_accounts(
+                &mut remaining_accounts,
+                &info.slices,
+                &[AccountsType::TransferHookEscrow],
+            )?,
+            None => ParsedRemainingAccounts::default(),
+        };
+        transfer_from_root_escrow(
+            &ctx.accounts.root_escrow,
+            &ctx.accounts.token_mint,
+            &ctx.accounts.root_escrow_token.to_account_info(),
+            &ctx.accounts.creator_token,
+            &ctx.accounts.token_program,
+            amount,
+            parsed_transfer_hook_accounts.transfer_hook_escrow,
+        )?;
+        if let Some(event) = get_transfer_ui_amount_event(
+            &ctx.accounts.token_mint,
+            ctx.accounts.root_escrow.key(),
+            ctx.accounts.creator_token.key(),
+            amount,
+            Clock::get()?.unix_timestamp as u64,
+        )? {
+            emit_cpi!(event);
+        }
+    }
+
+    // Do fee harvesting
+    harvest_fees(
+        &ctx.accounts.token_program,
+        &ctx.accounts.root_escrow_token.to_account_info(),
+        &ctx.accounts.token_mint,
+    )?;
+
+    // close root escrow token
+    let root_escrow = ctx.accounts.root_escrow.load()?;
+    let root_escrow_seeds = root_escrow_seeds!(root_escrow);
+    close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.root_escrow_token.to_account_info(),
+            destination: ctx.accounts.creator.to_account_info(),
+            authority: ctx.accounts.root_escrow.to_account_info(),
+        },
+        &[&root_escrow_seeds[..]],
+    ))?;
+
+    emit_cpi!(EventCloseRootEscrow {
+        root_escrow: ctx.accounts.root_escrow.key(),
+    });
+
+    Ok(())
+}
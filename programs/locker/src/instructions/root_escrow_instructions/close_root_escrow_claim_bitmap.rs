@@ -0,0 +1,41 @@
+use crate::*;
+
+/// Accounts for [locker::close_root_escrow_claim_bitmap].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseRootEscrowClaimBitmapCtx<'info> {
+    /// Root Escrow.
+    #[account(has_one = creator)]
+    pub root_escrow: AccountLoader<'info, RootEscrow>,
+
+    /// Claim Bitmap.
+    #[account(
+        mut,
+        has_one = root_escrow,
+        close = creator,
+    )]
+    pub claim_bitmap: AccountLoader<'info, RootEscrowClaimBitmap>,
+
+    /// Creator.
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
+
+pub fn handle_close_root_escrow_claim_bitmap(
+    ctx: Context<CloseRootEscrowClaimBitmapCtx>,
+) -> Result<()> {
+    let root_escrow = ctx.accounts.root_escrow.load()?;
+    let current_ts = Clock::get()?.unix_timestamp as u64;
+    // no leaf can be claimed anymore
+    require!(
+        root_escrow.is_fully_distributed() || root_escrow.is_clawback_started(current_ts),
+        LockerError::RootEscrowNotFullyDistributed
+    );
+
+    emit_cpi!(EventCloseRootEscrowClaimBitmap {
+        root_escrow: ctx.accounts.root_escrow.key(),
+        claim_bitmap: ctx.accounts.claim_bitmap.key(),
+    });
+
+    Ok(())
+}
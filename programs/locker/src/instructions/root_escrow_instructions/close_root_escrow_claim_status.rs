@@ -0,0 +1,42 @@
+use crate::*;
+
+/// Accounts for [locker::close_root_escrow_claim_status].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseRootEscrowClaimStatusCtx<'info> {
+    /// Root Escrow.
+    pub root_escrow: AccountLoader<'info, RootEscrow>,
+
+    /// Claim Status.
+    #[account(
+        mut,
+        has_one = root_escrow,
+        has_one = recipient,
+        close = recipient,
+    )]
+    pub claim_status: AccountLoader<'info, RootEscrowClaimStatus>,
+
+    /// Recipient, paid rent of claim status.
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+}
+
+pub fn handle_close_root_escrow_claim_status(
+    ctx: Context<CloseRootEscrowClaimStatusCtx>,
+) -> Result<()> {
+    let root_escrow = ctx.accounts.root_escrow.load()?;
+    let current_ts = Clock::get()?.unix_timestamp as u64;
+    // a new claim would create the claim status again, so no leaf can be claimed anymore
+    require!(
+        root_escrow.is_fully_distributed() || root_escrow.is_clawback_started(current_ts),
+        LockerError::RootEscrowNotFullyDistributed
+    );
+
+    emit_cpi!(EventCloseRootEscrowClaimStatus {
+        root_escrow: ctx.accounts.root_escrow.key(),
+        claim_status: ctx.accounts.claim_status.key(),
+        recipient: ctx.accounts.recipient.key(),
+    });
+
+    Ok(())
+}
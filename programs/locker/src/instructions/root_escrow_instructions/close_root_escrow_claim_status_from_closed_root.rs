@@ -0,0 +1,39 @@
+use util::is_closed;
+
+use crate::*;
+
+/// Accounts for [locker::close_root_escrow_claim_status_from_closed_root].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseRootEscrowClaimStatusFromClosedRootCtx<'info> {
+    /// CHECK: Closed Root Escrow that the leaf is claimed from.
+    #[account(
+        address = claim_status.load()?.root_escrow,
+        constraint = is_closed(&root_escrow) @ LockerError::RootEscrowNotClosed
+    )]
+    pub root_escrow: UncheckedAccount<'info>,
+
+    /// Claim Status.
+    #[account(
+        mut,
+        has_one = recipient,
+        close = recipient,
+    )]
+    pub claim_status: AccountLoader<'info, RootEscrowClaimStatus>,
+
+    /// Recipient, paid rent of claim status.
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+}
+
+pub fn handle_close_root_escrow_claim_status_from_closed_root(
+    ctx: Context<CloseRootEscrowClaimStatusFromClosedRootCtx>,
+) -> Result<()> {
+    emit_cpi!(EventCloseRootEscrowClaimStatus {
+        root_escrow: ctx.accounts.root_escrow.key(),
+        claim_status: ctx.accounts.claim_status.key(),
+        recipient: ctx.accounts.recipient.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use crate::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateMultiMintRootEscrowParameters {
+    /// number of leaves of every mint
+    pub max_escrow: u64,
+    pub version: u64,
+    pub root: [u8; 32],
+    /// timestamp after which creator can claw back undistributed tokens, 0 to disable clawback
+    pub clawback_deadline: u64,
+    /// [LeafVersion] of the merkle tree leaves, must include the token mint
+    pub leaf_version: u8,
+}
+
+impl CreateMultiMintRootEscrowParameters {
+    fn validate(&self) -> Result<()> {
+        require!(self.max_escrow > 0, LockerError::InvalidParams);
+        require!(
+            LeafVersion::try_from(self.leaf_version)
+                .is_ok_and(|leaf_version| leaf_version.has_mint()),
+            LockerError::InvalidParams
+        );
+        if self.clawback_deadline != 0 {
+            let current_ts = Clock::get()?.unix_timestamp as u64;
+            require!(
+                self.clawback_deadline > current_ts,
+                LockerError::InvalidClawbackDeadline
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Accounts for [locker::create_multi_mint_root_escrow].
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: CreateMultiMintRootEscrowParameters)]
+pub struct CreateMultiMintRootEscrowCtx<'info> {
+    pub base: Signer<'info>,
+
+    /// Root Escrow, seeded with the default pubkey as token mint.
+    #[account(
+        init,
+        seeds = [
+            b"root_escrow".as_ref(),
+            base.key().as_ref(),
+            Pubkey::default().as_ref(),
+            params.version.to_le_bytes().as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = 8 + RootEscrow::INIT_SPACE
+    )]
+    pub root_escrow: AccountLoader<'info, RootEscrow>,
+
+    /// Payer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Creator.
+    pub creator: UncheckedAccount<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_multi_mint_root_escrow(
+    ctx: Context<CreateMultiMintRootEscrowCtx>,
+    params: &CreateMultiMintRootEscrowParameters,
+) -> Result<()> {
+    params.validate()?;
+
+    let mut root_escrow = ctx.accounts.root_escrow.load_init()?;
+    // max claim amount and token program are set per mint by create_root_escrow_mint
+    root_escrow.init(
+        Pubkey::default(),
+        ctx.accounts.creator.key(),
+        ctx.accounts.base.key(),
+        0,
+        params.max_escrow,
+        params.root,
+        ctx.bumps.root_escrow,
+        params.version,
+        0,
+        params.clawback_deadline,
+        params.leaf_version,
+        DistributionMode::Escrow.into(),
+    );
+
+    emit_cpi!(EventCreateRootEscrow {
+        root_escrow: ctx.accounts.root_escrow.key(),
+        max_claim_amount: 0,
+        max_escrow: params.max_escrow,
+        version: params.version,
+        root: params.root,
+        clawback_deadline: params.clawback_deadline,
+        leaf_version: params.leaf_version,
+        distribution_mode: DistributionMode::Escrow.into(),
+    });
+
+    Ok(())
+}
@@ -12,6 +12,13 @@ pub struct CreateRootEscrowParameters {
     pub max_escrow: u64,
     pub version: u64,
     pub root: [u8; 32],
+    /// timestamp after which creator can claw back undistributed tokens, 0 to disable clawback
+    pub clawback_deadline: u64,
+    /// [LeafVersion] of the merkle tree leaves, leaves from V1 include the token mint so that a
+    /// tree can be shared by root escrows of different mints
+    pub leaf_version: u8,
+    /// [DistributionMode] of root escrow
+    pub distribution_mode: u8,
 }
 
 impl CreateRootEscrowParameters {
@@ -20,6 +27,29 @@ impl CreateRootEscrowParameters {
             self.max_claim_amount > 0 && self.max_escrow > 0,
             LockerError::InvalidParams
         );
+        require!(
+            LeafVersion::try_from(self.leaf_version).is_ok(),
+            LockerError::InvalidParams
+        );
+        let distribution_mode = DistributionMode::try_from(self.distribution_mode)
+            .map_err(|_| LockerError::InvalidDistributionMode)?;
+        // claim bitmaps are indexed by the leaf index, which must be a position in the tree. Leaves
+        // of a tree shared by several mints have positions beyond max_escrow of one mint, so only
+        // single mint trees with leaf indexes are accepted
+        if distribution_mode == DistributionMode::Bitmap {
+            require!(
+                LeafVersion::try_from(self.leaf_version)
+                    .is_ok_and(|leaf_version| leaf_version == LeafVersion::V2),
+                LockerError::InvalidDistributionMode
+            );
+        }
+        if self.clawback_deadline != 0 {
+            let current_ts = Clock::get()?.unix_timestamp as u64;
+            require!(
+                self.clawback_deadline > current_ts,
+                LockerError::InvalidClawbackDeadline
+            );
+        }
         Ok(())
     }
 }
@@ -86,6 +116,9 @@ pub fn handle_create_root_escrow<'c: 'info, 'info>(
         ctx.bumps.root_escrow,
         params.version,
         token_program_flag.into(),
+        params.clawback_deadline,
+        params.leaf_version,
+        params.distribution_mode,
     );
 
     emit_cpi!(EventCreateRootEscrow {
@@ -93,7 +126,10 @@ pub fn handle_create_root_escrow<'c: 'info, 'info>(
         max_claim_amount: params.max_claim_amount,
         max_escrow: params.max_escrow,
         version: params.version,
-        root: params.root
+        root: params.root,
+        clawback_deadline: params.clawback_deadline,
+        leaf_version: params.leaf_version,
+        distribution_mode: params.distribution_mode,
     });
 
     Ok(())
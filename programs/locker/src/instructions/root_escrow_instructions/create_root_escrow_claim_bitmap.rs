@@ -0,0 +1,49 @@
+use crate::safe_math::SafeMath;
+use crate::*;
+
+/// Accounts for [locker::create_root_escrow_claim_bitmap].
+#[derive(Accounts)]
+#[instruction(chunk_index: u64)]
+pub struct CreateRootEscrowClaimBitmapCtx<'info> {
+    /// Root Escrow.
+    pub root_escrow: AccountLoader<'info, RootEscrow>,
+
+    /// Claim Bitmap.
+    #[account(
+        init,
+        seeds = [
+            b"claim_bitmap",
+            root_escrow.key().as_ref(),
+            chunk_index.to_le_bytes().as_ref(),
+        ],
+        payer = payer,
+        space = 8 + RootEscrowClaimBitmap::INIT_SPACE,
+        bump,
+    )]
+    pub claim_bitmap: AccountLoader<'info, RootEscrowClaimBitmap>,
+
+    /// Payer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// system program.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_root_escrow_claim_bitmap(
+    ctx: Context<CreateRootEscrowClaimBitmapCtx>,
+    chunk_index: u64,
+) -> Result<()> {
+    {
+        let root_escrow = ctx.accounts.root_escrow.load()?;
+        root_escrow.validate_distribution_mode(DistributionMode::Bitmap)?;
+        require!(
+            chunk_index.safe_mul(LEAVES_PER_CLAIM_BITMAP)? < root_escrow.max_escrow,
+            LockerError::InvalidParams
+        );
+    }
+
+    let mut claim_bitmap = ctx.accounts.claim_bitmap.load_init()?;
+    claim_bitmap.init(ctx.accounts.root_escrow.key(), chunk_index);
+    Ok(())
+}
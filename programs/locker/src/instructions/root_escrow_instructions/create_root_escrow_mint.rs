@@ -0,0 +1,75 @@
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_interface::Mint;
+use util::validate_mint;
+
+use crate::TokenProgramFlag::{UseSplToken, UseToken2022};
+use crate::*;
+
+/// Accounts for [locker::create_root_escrow_mint].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateRootEscrowMintCtx<'info> {
+    /// Root Escrow.
+    #[account(mut, has_one = creator)]
+    pub root_escrow: AccountLoader<'info, RootEscrow>,
+
+    /// Root Escrow Mint.
+    #[account(
+        init,
+        seeds = [
+            b"root_escrow_mint".as_ref(),
+            root_escrow.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        bump,
+        payer = creator,
+        space = 8 + RootEscrowMint::INIT_SPACE
+    )]
+    pub root_escrow_mint: AccountLoader<'info, RootEscrowMint>,
+
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Creator.
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_root_escrow_mint(
+    ctx: Context<CreateRootEscrowMintCtx>,
+    max_claim_amount: u64,
+) -> Result<()> {
+    require!(max_claim_amount > 0, LockerError::InvalidParams);
+    // Validate if token_mint is supported
+    // dont allow transfer fee
+    validate_mint(&ctx.accounts.token_mint, false)?;
+
+    let token_mint_info = ctx.accounts.token_mint.to_account_info();
+    let token_program_flag = match *token_mint_info.owner {
+        spl_token::ID => Ok(UseSplToken),
+        spl_token_2022::ID => Ok(UseToken2022),
+        _ => Err(LockerError::IncorrectTokenProgramId),
+    }?;
+
+    ctx.accounts.root_escrow.load_mut()?.add_mint()?;
+
+    let mut root_escrow_mint = ctx.accounts.root_escrow_mint.load_init()?;
+    root_escrow_mint.init(
+        ctx.accounts.root_escrow.key(),
+        ctx.accounts.token_mint.key(),
+        ctx.bumps.root_escrow_mint,
+        token_program_flag.into(),
+        max_claim_amount,
+    );
+
+    emit_cpi!(EventCreateRootEscrowMint {
+        root_escrow: ctx.accounts.root_escrow.key(),
+        token_mint: ctx.accounts.token_mint.key(),
+        max_claim_amount,
+    });
+
+    Ok(())
+}
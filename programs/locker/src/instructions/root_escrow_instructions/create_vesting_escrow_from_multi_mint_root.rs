@@ -0,0 +1,201 @@
+use crate::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use safe_math::SafeMath;
+use util::{
+    calculate_transfer_fee_included_amount, parse_remaining_accounts, transfer_from_root_escrow,
+    transfer_lamports, AccountsType, ParsedRemainingAccounts,
+};
+
+/// Accounts for [locker::create_vesting_escrow_from_multi_mint_root].
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: CreateVestingEscrowFromRoot2Params)]
+pub struct CreateVestingEscrowFromMultiMintRootCtx<'info> {
+    /// Root Escrow.
+    #[account(mut)]
+    pub root_escrow: AccountLoader<'info, RootEscrow>,
+
+    /// Root Escrow Mint.
+    #[account(mut, has_one = root_escrow, has_one = token_mint)]
+    pub root_escrow_mint: AccountLoader<'info, RootEscrowMint>,
+
+    /// Base account for deriving escrow PDA and enforcing uniqueness, leaves of several mints
+    /// can share recipient and leaf index.
+    #[account(
+        init,
+        seeds = [
+            b"base",
+            root_escrow.key().as_ref(),
+            recipient.key().as_ref(),
+            params.get_leaf_index_seed().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        payer = payer,
+        space = 8,
+        bump,
+    )]
+    pub base: AccountLoader<'info, Marker>,
+
+    /// Escrow.
+    #[account(
+        init,
+        seeds = [
+            b"escrow".as_ref(),
+            base.key().as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = 8 + VestingEscrow::INIT_SPACE
+    )]
+    pub escrow: AccountLoader<'info, VestingEscrow>,
+
+    /// Escrow Token Account.
+    #[account(
+        init_if_needed,
+        associated_token::mint = token_mint,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program,
+        payer = payer,
+    )]
+    pub escrow_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = root_escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub root_escrow_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Mint.
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Rent Payer, rent is reimbursed from the rent pool of root escrow
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: recipient
+    pub recipient: UncheckedAccount<'info>,
+
+    /// system program.
+    pub system_program: Program<'info, System>,
+
+    /// Token program.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    // Associated token program.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn handle_create_vesting_escrow_from_multi_mint_root<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, CreateVestingEscrowFromMultiMintRootCtx<'info>>,
+    params: &CreateVestingEscrowFromRoot2Params,
+    proof: Vec<[u8; 32]>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    // creator of root escrow is creator of vesting escrow
+    let creator = {
+        // verify merkle tree
+        let root_escrow = ctx.accounts.root_escrow.load()?;
+
+        let current_ts = Clock::get()?.unix_timestamp as u64;
+        require!(
+            !root_escrow.is_clawback_started(current_ts),
+            LockerError::ClawbackDeadlinePassed
+        );
+
+        root_escrow.validate_distribution_mode(DistributionMode::Escrow)?;
+        params.verify_proof(
+            &root_escrow,
+            &ctx.accounts.recipient.key(),
+            &ctx.accounts.token_mint.key(),
+            &proof,
+        )?;
+        root_escrow.creator
+    };
+    let token_program_flag = ctx.accounts.root_escrow_mint.load()?.token_program_flag;
+
+    // create escrow
+    let params = params.into_vesting_escrow_params();
+    params.init_escrow(
+        &ctx.accounts.escrow,
+        ctx.accounts.recipient.key(),
+        ctx.accounts.token_mint.key(),
+        creator,
+        ctx.accounts.base.key(),
+        ctx.bumps.escrow,
+        token_program_flag,
+        ctx.accounts.root_escrow.key(),
+    )?;
+
+    // Process remaining accounts
+    let mut remaining_accounts = &ctx.remaining_accounts[..];
+    let parsed_transfer_hook_accounts = match remaining_accounts_info {
+        Some(info) => parse_remaining_accounts(
+            &mut remaining_accounts,
+            &info.slices,
+            &[AccountsType::TransferHookEscrow],
+        )?,
+        None => ParsedRemainingAccounts::default(),
+    };
+
+    let total_deposit = params.get_total_deposit_amount()?;
+
+    transfer_from_root_escrow(
+        &ctx.accounts.root_escrow,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.root_escrow_token.to_account_info(),
+        &ctx.accounts.escrow_token,
+        &ctx.accounts.token_program,
+        calculate_transfer_fee_included_amount(total_deposit, &ctx.accounts.token_mint)?,
+        parsed_transfer_hook_accounts.transfer_hook_escrow,
+    )?;
+
+    ctx.accounts
+        .root_escrow_mint
+        .load_mut()?
+        .accumulate_distributed_amount(total_deposit)?;
+    let reimbursement = {
+        let mut root_escrow = ctx.accounts.root_escrow.load_mut()?;
+        root_escrow.update_new_mint_escrow()?;
+
+        // rent of escrow and escrow token is paid from the rent pool while it lasts, base is
+        // never closed so its rent is left to the payer
+        let rent = Rent::get()?;
+        let escrow_rent = rent
+            .minimum_balance(8 + VestingEscrow::INIT_SPACE)
+            .safe_add(
+                rent.minimum_balance(ctx.accounts.escrow_token.to_account_info().data_len()),
+            )?;
+        root_escrow.get_and_set_rent_reimbursement(escrow_rent)?
+    };
+    if reimbursement > 0 {
+        transfer_lamports(
+            &ctx.accounts.root_escrow.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            reimbursement,
+        )?;
+        emit_cpi!(EventReimburseRootEscrowRent {
+            root_escrow: ctx.accounts.root_escrow.key(),
+            escrow: ctx.accounts.escrow.key(),
+            payer: ctx.accounts.payer.key(),
+            amount: reimbursement,
+        });
+    }
+
+    emit_cpi!(EventCreateVestingEscrow {
+        cliff_time: params.cliff_time,
+        frequency: params.frequency,
+        cliff_unlock_amount: params.cliff_unlock_amount,
+        amount_per_period: params.amount_per_period,
+        number_of_period: params.number_of_period,
+        recipient: ctx.accounts.recipient.key(),
+        escrow: ctx.accounts.escrow.key(),
+        update_recipient_mode: params.update_recipient_mode,
+        vesting_start_time: params.vesting_start_time,
+        cancel_mode: params.cancel_mode,
+    });
+
+    Ok(())
+}
@@ -1,15 +1,12 @@
 use crate::*;
-use anchor_lang::solana_program::hash::hashv;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
-use merkle_verify::verify;
+use safe_math::SafeMath;
 use util::{
     calculate_transfer_fee_included_amount, parse_remaining_accounts, transfer_from_root_escrow,
-    AccountsType, ParsedRemainingAccounts,
+    transfer_lamports, AccountsType, ParsedRemainingAccounts,
 };
 
-const LEAF_PREFIX: &[u8] = &[0];
-
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct CreateVestingEscrowFromRootParams {
     pub vesting_start_time: u64,
@@ -22,21 +19,6 @@ pub struct CreateVestingEscrowFromRootParams {
     pub cancel_mode: u8,
 }
 
-impl CreateVestingEscrowFromRootParams {
-    pub fn into_vesting_escrow_params(&self) -> CreateVestingEscrowParameters {
-        CreateVestingEscrowParameters {
-            vesting_start_time: self.vesting_start_time,
-            cliff_time: self.cliff_time,
-            frequency: self.frequency,
-            cliff_unlock_amount: self.cliff_unlock_amount,
-            amount_per_period: self.amount_per_period,
-            number_of_period: self.number_of_period,
-            update_recipient_mode: self.update_recipient_mode,
-            cancel_mode: self.cancel_mode,
-        }
-    }
-}
-
 /// Accounts for [locker::create_vesting_escrow_from_root].
 #[event_cpi]
 #[derive(Accounts)]
@@ -96,7 +78,7 @@ pub struct CreateVestingEscrowFromRootCtx<'info> {
     /// Mint.
     pub token_mint: Box<InterfaceAccount<'info, Mint>>,
 
-    /// Rent Payer
+    /// Rent Payer, rent is reimbursed from the rent pool of root escrow
     #[account(mut)]
     pub payer: Signer<'info>,
 
@@ -119,46 +101,95 @@ pub fn handle_create_vesting_escrow_from_root<'c: 'info, 'info>(
     proof: Vec<[u8; 32]>,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
 ) -> Result<()> {
+    let (reimbursement_event, create_event) = create_and_fund_vesting_escrow_from_root(
+        CreateVestingEscrowFromRootAccounts {
+            root_escrow: &ctx.accounts.root_escrow,
+            base: &ctx.accounts.base,
+            escrow: &ctx.accounts.escrow,
+            escrow_bump: ctx.bumps.escrow,
+            escrow_token: &ctx.accounts.escrow_token,
+            root_escrow_token: &ctx.accounts.root_escrow_token,
+            token_mint: &ctx.accounts.token_mint,
+            payer: &ctx.accounts.payer,
+            recipient: &ctx.accounts.recipient,
+            token_program: &ctx.accounts.token_program,
+        },
+        ctx.remaining_accounts,
+        &params.into(),
+        proof,
+        remaining_accounts_info,
+    )?;
+    if let Some(reimbursement_event) = reimbursement_event {
+        emit_cpi!(reimbursement_event);
+    }
+    emit_cpi!(create_event);
+
+    Ok(())
+}
+
+/// Accounts shared by [locker::create_vesting_escrow_from_root] and
+/// [locker::create_vesting_escrow_from_root_v2].
+pub struct CreateVestingEscrowFromRootAccounts<'a, 'info> {
+    pub root_escrow: &'a AccountLoader<'info, RootEscrow>,
+    pub base: &'a AccountLoader<'info, Marker>,
+    pub escrow: &'a AccountLoader<'info, VestingEscrow>,
+    pub escrow_bump: u8,
+    pub escrow_token: &'a InterfaceAccount<'info, TokenAccount>,
+    pub root_escrow_token: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_mint: &'a InterfaceAccount<'info, Mint>,
+    pub payer: &'a Signer<'info>,
+    pub recipient: &'a UncheckedAccount<'info>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+}
+
+/// Verify the leaf, then create and fund the vesting escrow from root escrow. Returns the events
+/// to emit, the rent reimbursement event is None when the rent pool is empty
+pub fn create_and_fund_vesting_escrow_from_root<'c: 'info, 'info>(
+    accounts: CreateVestingEscrowFromRootAccounts<'_, 'info>,
+    remaining_accounts: &'c [AccountInfo<'info>],
+    params: &CreateVestingEscrowFromRoot2Params,
+    proof: Vec<[u8; 32]>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<(
+    Option<EventReimburseRootEscrowRent>,
+    EventCreateVestingEscrow,
+)> {
     // creator of root escrow is creator of vesting escrow
     let (token_program_flag, creator) = {
         // verify merkle tree
-        let root_escrow = ctx.accounts.root_escrow.load()?;
-
-        let node = hashv(&[
-            &ctx.accounts.recipient.key().to_bytes(),
-            &params.vesting_start_time.to_le_bytes(),
-            &params.cliff_time.to_le_bytes(),
-            &params.frequency.to_le_bytes(),
-            &params.cliff_unlock_amount.to_le_bytes(),
-            &params.amount_per_period.to_le_bytes(),
-            &params.number_of_period.to_le_bytes(),
-            &params.update_recipient_mode.to_le_bytes(),
-            &params.cancel_mode.to_le_bytes(),
-        ]);
-
-        let node = hashv(&[LEAF_PREFIX, &node.to_bytes()]);
+        let root_escrow = accounts.root_escrow.load()?;
 
+        let current_ts = Clock::get()?.unix_timestamp as u64;
         require!(
-            verify(proof, root_escrow.root, node.to_bytes()),
-            LockerError::InvalidMerkleProof
+            !root_escrow.is_clawback_started(current_ts),
+            LockerError::ClawbackDeadlinePassed
         );
+
+        root_escrow.validate_distribution_mode(DistributionMode::Escrow)?;
+        params.verify_proof(
+            &root_escrow,
+            &accounts.recipient.key(),
+            &accounts.token_mint.key(),
+            &proof,
+        )?;
         (root_escrow.token_program_flag, root_escrow.creator)
     };
 
     // create escrow
     let params = params.into_vesting_escrow_params();
     params.init_escrow(
-        &ctx.accounts.escrow,
-        ctx.accounts.recipient.key(),
-        ctx.accounts.token_mint.key(),
+        &accounts.escrow,
+        accounts.recipient.key(),
+        accounts.token_mint.key(),
         creator,
-        ctx.accounts.base.key(),
-        ctx.bumps.escrow,
+        accounts.base.key(),
+        accounts.escrow_bump,
         token_program_flag,
+        accounts.root_escrow.key(),
     )?;
 
     // Process remaining accounts
-    let mut remaining_accounts = &ctx.remaining_accounts[..];
+    let mut remaining_accounts = remaining_accounts;
     let parsed_transfer_hook_accounts = match remaining_accounts_info {
         Some(info) => parse_remaining_accounts(
             &mut remaining_accounts,
@@ -171,30 +202,56 @@ pub fn handle_create_vesting_escrow_from_root<'c: 'info, 'info>(
     let total_deposit = params.get_total_deposit_amount()?;
 
     transfer_from_root_escrow(
-        &ctx.accounts.root_escrow,
-        &ctx.accounts.token_mint,
-        &ctx.accounts.root_escrow_token.to_account_info(),
-        &ctx.accounts.escrow_token,
-        &ctx.accounts.token_program,
-        calculate_transfer_fee_included_amount(total_deposit, &ctx.accounts.token_mint)?,
+        &accounts.root_escrow,
+        &accounts.token_mint,
+        &accounts.root_escrow_token.to_account_info(),
+        &accounts.escrow_token,
+        &accounts.token_program,
+        calculate_transfer_fee_included_amount(total_deposit, &accounts.token_mint)?,
         parsed_transfer_hook_accounts.transfer_hook_escrow,
     )?;
 
-    let mut root_escrow = ctx.accounts.root_escrow.load_mut()?;
-    root_escrow.update_new_escrow(total_deposit)?;
-
-    emit_cpi!(EventCreateVestingEscrow {
-        cliff_time: params.cliff_time,
-        frequency: params.frequency,
-        cliff_unlock_amount: params.cliff_unlock_amount,
-        amount_per_period: params.amount_per_period,
-        number_of_period: params.number_of_period,
-        recipient: ctx.accounts.recipient.key(),
-        escrow: ctx.accounts.escrow.key(),
-        update_recipient_mode: params.update_recipient_mode,
-        vesting_start_time: params.vesting_start_time,
-        cancel_mode: params.cancel_mode,
-    });
+    let reimbursement = {
+        let mut root_escrow = accounts.root_escrow.load_mut()?;
+        root_escrow.update_new_escrow(total_deposit)?;
 
-    Ok(())
+        // rent of escrow and escrow token is paid from the rent pool while it lasts, base is
+        // never closed so its rent is left to the payer
+        let rent = Rent::get()?;
+        let escrow_rent = rent
+            .minimum_balance(8 + VestingEscrow::INIT_SPACE)
+            .safe_add(rent.minimum_balance(accounts.escrow_token.to_account_info().data_len()))?;
+        root_escrow.get_and_set_rent_reimbursement(escrow_rent)?
+    };
+    let reimbursement_event = if reimbursement > 0 {
+        transfer_lamports(
+            &accounts.root_escrow.to_account_info(),
+            &accounts.payer.to_account_info(),
+            reimbursement,
+        )?;
+        Some(EventReimburseRootEscrowRent {
+            root_escrow: accounts.root_escrow.key(),
+            escrow: accounts.escrow.key(),
+            payer: accounts.payer.key(),
+            amount: reimbursement,
+        })
+    } else {
+        None
+    };
+
+    Ok((
+        reimbursement_event,
+        EventCreateVestingEscrow {
+            cliff_time: params.cliff_time,
+            frequency: params.frequency,
+            cliff_unlock_amount: params.cliff_unlock_amount,
+            amount_per_period: params.amount_per_period,
+            number_of_period: params.number_of_period,
+            recipient: accounts.recipient.key(),
+            escrow: accounts.escrow.key(),
+            update_recipient_mode: params.update_recipient_mode,
+            vesting_start_time: params.vesting_start_time,
+            cancel_mode: params.cancel_mode,
+        },
+    ))
 }
@@ -0,0 +1,209 @@
+use crate::*;
+use anchor_lang::solana_program::hash::{hashv, Hash};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use merkle_verify::verify_node;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateVestingEscrowFromRoot2Params {
+    pub vesting_start_time: u64,
+    pub cliff_time: u64,
+    pub frequency: u64,
+    pub cliff_unlock_amount: u64,
+    pub amount_per_period: u64,
+    pub number_of_period: u64,
+    pub update_recipient_mode: u8,
+    pub cancel_mode: u8,
+    /// index of the leaf among the leaves of the recipient, only for leaf version 2 and 3
+    pub leaf_index: Option<u64>,
+}
+
+impl CreateVestingEscrowFromRoot2Params {
+    pub fn get_leaf_index_seed(&self) -> Vec<u8> {
+        self.leaf_index
+            .map(|leaf_index| leaf_index.to_le_bytes().to_vec())
+            .unwrap_or_default()
+    }
+
+    pub fn get_node(&self, leaf_version: LeafVersion, recipient: &Pubkey, mint: &Pubkey) -> Hash {
+        let leaf_index = self.leaf_index.unwrap_or_default().to_le_bytes();
+        let mut prefix: Vec<&[u8]> = vec![recipient.as_ref()];
+        if leaf_version.has_mint() {
+            prefix.push(mint.as_ref());
+        }
+        if leaf_version.has_leaf_index() {
+            prefix.push(&leaf_index);
+        }
+        let schedule: [&[u8]; 8] = [
+            &self.vesting_start_time.to_le_bytes(),
+            &self.cliff_time.to_le_bytes(),
+            &self.frequency.to_le_bytes(),
+            &self.cliff_unlock_amount.to_le_bytes(),
+            &self.amount_per_period.to_le_bytes(),
+            &self.number_of_period.to_le_bytes(),
+            &self.update_recipient_mode.to_le_bytes(),
+            &self.cancel_mode.to_le_bytes(),
+        ];
+        hashv(&[&prefix[..], &schedule[..]].concat())
+    }
+
+    /// Verify the leaf of `recipient` for `token_mint` is in the merkle tree of root escrow
+    pub fn verify_proof(
+        &self,
+        root_escrow: &RootEscrow,
+        recipient: &Pubkey,
+        token_mint: &Pubkey,
+        proof: &[[u8; 32]],
+    ) -> Result<()> {
+        let leaf_version = LeafVersion::try_from(root_escrow.leaf_version)
+            .map_err(|_| LockerError::InvalidParams)?;
+        require!(
+            self.leaf_index.is_some() == leaf_version.has_leaf_index(),
+            LockerError::InvalidParams
+        );
+        let node = self.get_node(leaf_version, recipient, token_mint);
+
+        verify_node(proof, &root_escrow.root, node.as_ref())
+            .map_err(|_| LockerError::InvalidMerkleProof)?;
+        Ok(())
+    }
+
+    pub fn into_vesting_escrow_params(&self) -> CreateVestingEscrowParameters {
+        CreateVestingEscrowParameters {
+            vesting_start_time: self.vesting_start_time,
+            cliff_time: self.cliff_time,
+            frequency: self.frequency,
+            cliff_unlock_amount: self.cliff_unlock_amount,
+            amount_per_period: self.amount_per_period,
+            number_of_period: self.number_of_period,
+            update_recipient_mode: self.update_recipient_mode,
+            cancel_mode: self.cancel_mode,
+        }
+    }
+}
+
+impl From<&CreateVestingEscrowFromRootParams> for CreateVestingEscrowFromRoot2Params {
+    fn from(params: &CreateVestingEscrowFromRootParams) -> Self {
+        Self {
+            vesting_start_time: params.vesting_start_time,
+            cliff_time: params.cliff_time,
+            frequency: params.frequency,
+            cliff_unlock_amount: params.cliff_unlock_amount,
+            amount_per_period: params.amount_per_period,
+            number_of_period: params.number_of_period,
+            update_recipient_mode: params.update_recipient_mode,
+            cancel_mode: params.cancel_mode,
+            leaf_index: None,
+        }
+    }
+}
+
+/// Accounts for [locker::create_vesting_escrow_from_root_v2].
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: CreateVestingEscrowFromRoot2Params)]
+pub struct CreateVestingEscrowFromRoot2Ctx<'info> {
+    /// Root Escrow.
+    #[account(
+        mut,
+        has_one = token_mint,
+    )]
+    pub root_escrow: AccountLoader<'info, RootEscrow>,
+
+    /// Base account for deriving escrow PDA and enforcing uniqueness.
+    #[account(
+        init,
+        seeds = [
+            b"base",
+            root_escrow.key().as_ref(),
+            recipient.key().as_ref(),
+            params.get_leaf_index_seed().as_ref(),
+        ],
+        payer = payer,
+        space = 8,
+        bump,
+    )]
+    pub base: AccountLoader<'info, Marker>,
+
+    /// Escrow.
+    #[account(
+        init,
+        seeds = [
+            b"escrow".as_ref(),
+            base.key().as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = 8 + VestingEscrow::INIT_SPACE
+    )]
+    pub escrow: AccountLoader<'info, VestingEscrow>,
+
+    /// Escrow Token Account.
+    #[account(
+        init_if_needed,
+        associated_token::mint = token_mint,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program,
+        payer = payer,
+    )]
+    pub escrow_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = root_escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub root_escrow_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Mint.
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Rent Payer, rent is reimbursed from the rent pool of root escrow
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: recipient
+    pub recipient: UncheckedAccount<'info>,
+
+    /// system program.
+    pub system_program: Program<'info, System>,
+
+    /// Token program.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    // Associated token program.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn handle_create_vesting_escrow_from_root2<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, CreateVestingEscrowFromRoot2Ctx<'info>>,
+    params: &CreateVestingEscrowFromRoot2Params,
+    proof: Vec<[u8; 32]>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let (reimbursement_event, create_event) = create_and_fund_vesting_escrow_from_root(
+        CreateVestingEscrowFromRootAccounts {
+            root_escrow: &ctx.accounts.root_escrow,
+            base: &ctx.accounts.base,
+            escrow: &ctx.accounts.escrow,
+            escrow_bump: ctx.bumps.escrow,
+            escrow_token: &ctx.accounts.escrow_token,
+            root_escrow_token: &ctx.accounts.root_escrow_token,
+            token_mint: &ctx.accounts.token_mint,
+            payer: &ctx.accounts.payer,
+            recipient: &ctx.accounts.recipient,
+            token_program: &ctx.accounts.token_program,
+        },
+        ctx.remaining_accounts,
+        params,
+        proof,
+        remaining_accounts_info,
+    )?;
+    if let Some(reimbursement_event) = reimbursement_event {
+        emit_cpi!(reimbursement_event);
+    }
+    emit_cpi!(create_event);
+
+    Ok(())
+}
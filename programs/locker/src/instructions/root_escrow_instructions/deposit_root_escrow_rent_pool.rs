@@ -0,0 +1,47 @@
+use crate::*;
+
+/// Accounts for [locker::deposit_root_escrow_rent_pool].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositRootEscrowRentPoolCtx<'info> {
+    /// Root Escrow.
+    #[account(mut, has_one = creator)]
+    pub root_escrow: AccountLoader<'info, RootEscrow>,
+
+    /// Creator.
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// system program.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_deposit_root_escrow_rent_pool(
+    ctx: Context<DepositRootEscrowRentPoolCtx>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount != 0, LockerError::AmountIsZero);
+
+    {
+        let mut root_escrow = ctx.accounts.root_escrow.load_mut()?;
+        root_escrow.deposit_rent_pool(amount)?;
+    }
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.creator.to_account_info(),
+                to: ctx.accounts.root_escrow.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    emit_cpi!(EventDepositRootEscrowRentPool {
+        root_escrow: ctx.accounts.root_escrow.key(),
+        amount,
+    });
+
+    Ok(())
+}
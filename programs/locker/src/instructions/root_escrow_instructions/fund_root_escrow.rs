@@ -46,6 +46,7 @@ pub struct FundRootEscrowCtx<'info> {
 pub fn handle_fund_root_escrow<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, FundRootEscrowCtx<'info>>,
     max_amount: u64,
+    rent_deposit: u64,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
 ) -> Result<()> {
     // Process remaining accounts
@@ -61,9 +62,34 @@ pub fn handle_fund_root_escrow<'c: 'info, 'info>(
 
     let funded_amount = {
         let mut root_escrow = ctx.accounts.root_escrow.load_mut()?;
+        root_escrow.deposit_rent_pool(rent_deposit)?;
         root_escrow.get_and_set_fund_amount(max_amount)?
     };
-    require!(funded_amount != 0, LockerError::AmountIsZero);
+    require!(
+        funded_amount != 0 || rent_deposit != 0,
+        LockerError::AmountIsZero
+    );
+
+    if rent_deposit > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.root_escrow.to_account_info(),
+                },
+            ),
+            rent_deposit,
+        )?;
+        emit_cpi!(EventDepositRootEscrowRentPool {
+            root_escrow: ctx.accounts.root_escrow.key(),
+            amount: rent_deposit,
+        });
+    }
+
+    if funded_amount == 0 {
+        return Ok(());
+    }
 
     transfer_to_escrow2(
         &ctx.accounts.payer,
@@ -3,5 +3,49 @@ pub mod create_vesting_escrow_from_root;
 
 pub use create_root_escrow::*;
 pub use create_vesting_escrow_from_root::*;
+pub mod create_vesting_escrow_from_root2;
+pub use create_vesting_escrow_from_root2::*;
 pub mod fund_root_escrow;
 pub use fund_root_escrow::*;
+pub mod clawback_root_escrow;
+pub use clawback_root_escrow::*;
+pub mod close_root_escrow;
+pub use close_root_escrow::*;
+pub mod update_root_escrow_root;
+pub use update_root_escrow_root::*;
+pub mod deposit_root_escrow_rent_pool;
+pub use deposit_root_escrow_rent_pool::*;
+pub mod claim_from_root_escrow;
+pub use claim_from_root_escrow::*;
+pub mod close_root_escrow_claim_status;
+pub use close_root_escrow_claim_status::*;
+pub mod close_root_escrow_claim_status_from_closed_root;
+pub use close_root_escrow_claim_status_from_closed_root::*;
+pub mod create_root_escrow_claim_bitmap;
+pub use create_root_escrow_claim_bitmap::*;
+pub mod claim_from_root_escrow_bitmap;
+pub use claim_from_root_escrow_bitmap::*;
+pub mod close_root_escrow_claim_bitmap;
+pub use close_root_escrow_claim_bitmap::*;
+pub mod cancel_vesting_escrow_from_root;
+pub use cancel_vesting_escrow_from_root::*;
+pub mod cancel_vesting_escrow_from_closed_root;
+pub use cancel_vesting_escrow_from_closed_root::*;
+pub mod cancel_vesting_escrow_from_closed_root_escrow_mint;
+pub use cancel_vesting_escrow_from_closed_root_escrow_mint::*;
+pub mod create_multi_mint_root_escrow;
+pub use create_multi_mint_root_escrow::*;
+pub mod create_root_escrow_mint;
+pub use create_root_escrow_mint::*;
+pub mod close_root_escrow_mint;
+pub use close_root_escrow_mint::*;
+pub mod fund_multi_mint_root_escrow;
+pub use fund_multi_mint_root_escrow::*;
+pub mod create_vesting_escrow_from_multi_mint_root;
+pub use create_vesting_escrow_from_multi_mint_root::*;
+pub mod clawback_multi_mint_root_escrow;
+pub use clawback_multi_mint_root_escrow::*;
+pub mod update_multi_mint_root_escrow_root;
+pub use update_multi_mint_root_escrow_root::*;
+pub mod cancel_vesting_escrow_from_multi_mint_root;
+pub use cancel_vesting_escrow_from_multi_mint_root::*;
@@ -0,0 +1,43 @@
+use crate::*;
+
+/// Accounts for [locker::update_multi_mint_root_escrow_root].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateMultiMintRootEscrowRootCtx<'info> {
+    /// Root Escrow.
+    #[account(mut, has_one = creator)]
+    pub root_escrow: AccountLoader<'info, RootEscrow>,
+
+    /// Root Escrow Mint whose max claim amount is updated.
+    #[account(mut, has_one = root_escrow)]
+    pub root_escrow_mint: AccountLoader<'info, RootEscrowMint>,
+
+    /// Creator.
+    pub creator: Signer<'info>,
+}
+
+pub fn handle_update_multi_mint_root_escrow_root(
+    ctx: Context<UpdateMultiMintRootEscrowRootCtx>,
+    params: &UpdateRootEscrowRootParameters,
+) -> Result<()> {
+    params.validate()?;
+
+    let mut root_escrow = ctx.accounts.root_escrow.load_mut()?;
+    let old_root = root_escrow.root;
+    // max claim amounts of a multi mint root escrow are kept per mint
+    root_escrow.update_root(params.root, 0, params.max_escrow)?;
+    ctx.accounts
+        .root_escrow_mint
+        .load_mut()?
+        .update_max_claim_amount(params.max_claim_amount)?;
+
+    emit_cpi!(EventUpdateRootEscrowRoot {
+        root_escrow: ctx.accounts.root_escrow.key(),
+        old_root,
+        new_root: params.root,
+        max_claim_amount: params.max_claim_amount,
+        max_escrow: params.max_escrow,
+    });
+
+    Ok(())
+}
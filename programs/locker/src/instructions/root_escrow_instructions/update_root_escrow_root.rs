@@ -0,0 +1,55 @@
+use crate::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateRootEscrowRootParameters {
+    pub root: [u8; 32],
+    pub max_claim_amount: u64,
+    pub max_escrow: u64,
+}
+
+impl UpdateRootEscrowRootParameters {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.max_claim_amount > 0 && self.max_escrow > 0,
+            LockerError::InvalidParams
+        );
+        Ok(())
+    }
+}
+
+/// Accounts for [locker::update_root_escrow_root].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateRootEscrowRootCtx<'info> {
+    /// Root Escrow.
+    #[account(
+        mut,
+        has_one = creator,
+        constraint = !root_escrow.load()?.is_multi_mint() @ LockerError::InvalidRootEscrowMint,
+    )]
+    pub root_escrow: AccountLoader<'info, RootEscrow>,
+
+    /// Creator.
+    pub creator: Signer<'info>,
+}
+
+pub fn handle_update_root_escrow_root(
+    ctx: Context<UpdateRootEscrowRootCtx>,
+    params: &UpdateRootEscrowRootParameters,
+) -> Result<()> {
+    params.validate()?;
+
+    let mut root_escrow = ctx.accounts.root_escrow.load_mut()?;
+    let old_root = root_escrow.root;
+    root_escrow.update_root(params.root, params.max_claim_amount, params.max_escrow)?;
+
+    emit_cpi!(EventUpdateRootEscrowRoot {
+        root_escrow: ctx.accounts.root_escrow.key(),
+        old_root,
+        new_root: params.root,
+        max_claim_amount: params.max_claim_amount,
+        max_escrow: params.max_escrow,
+    });
+
+    Ok(())
+}
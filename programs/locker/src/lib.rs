@@ -127,6 +127,9 @@ pub mod locker {
 
     /// Claim maximum amount from the vesting escrow
     /// This instruction supports both splToken and token2022
+    /// For a multi recipient vesting escrow, the recipient claims its share of the vested amount,
+    /// and the escrow recipients account must be passed in an EscrowRecipients slice of
+    /// remaining accounts
     /// # Arguments
     ///
     /// * ctx - The accounts needed by instruction.
@@ -144,6 +147,7 @@ pub mod locker {
     /// Cancel a vesting escrow.
     ///   - The claimable token will be transferred to recipient
     ///   - The remaining token will be transferred to the creator
+    ///   - Escrows created from root escrow are cancelled with cancel_vesting_escrow_from_root
     /// This instruction supports both splToken and token2022
     /// # Arguments
     ///
@@ -160,6 +164,8 @@ pub mod locker {
     /// Close vesting escrow
     ///  - Close vesting escrow and escrow ATA and escrow metadata if recipient already claimed all tokens
     ///  - Rent receiver must be escrow's creator
+    ///  - The escrow recipients account of a multi recipient escrow must be passed in an
+    ///    EscrowRecipients slice of remaining accounts, and is closed with the escrow
     /// This instruction supports both splToken and token2022
     /// # Arguments
     ///
@@ -173,6 +179,32 @@ pub mod locker {
         handle_close_vesting_escrow(ctx, remaining_accounts_info)
     }
 
+    /// Create a vesting escrow shared by multiple recipients
+    /// Each recipient claims its share of the vested amount independently with claim_v2
+    /// Recipients of the escrow can't be updated and the escrow can't be cancelled, as the
+    /// claimable amount would have to be split between recipients
+    /// This instruction supports both splToken and token2022
+    /// # Arguments
+    ///
+    /// * ctx - The accounts needed by instruction.
+    /// * params - The params needed by instruction, update_recipient_mode and cancel_mode must be 0
+    /// * recipients - The recipients and their share of the escrow in basis points, sum to 10000
+    /// * remaining_accounts_info: additional accounts needed by instruction
+    ///
+    pub fn create_multi_recipient_vesting_escrow<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CreateMultiRecipientVestingEscrowCtx<'info>>,
+        params: CreateVestingEscrowParameters,
+        recipients: Vec<RecipientShareParameters>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        handle_create_multi_recipient_vesting_escrow(
+            ctx,
+            &params,
+            &recipients,
+            remaining_accounts_info,
+        )
+    }
+
     // Instructions for RootEscrow
     /// Create root escrow
     pub fn create_root_escrow<'c: 'info, 'info>(
@@ -188,7 +220,18 @@ pub mod locker {
         max_amount: u64,
         remaining_accounts_info: Option<RemainingAccountsInfo>,
     ) -> Result<()> {
-        handle_fund_root_escrow(ctx, max_amount, remaining_accounts_info)
+        handle_fund_root_escrow(ctx, max_amount, 0, remaining_accounts_info)
+    }
+
+    /// Fund root escrow, rent_deposit lamports are added to the rent pool paying rent of escrows
+    /// created from root escrow
+    pub fn fund_root_escrow_v2<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, FundRootEscrowCtx<'info>>,
+        max_amount: u64,
+        rent_deposit: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        handle_fund_root_escrow(ctx, max_amount, rent_deposit, remaining_accounts_info)
     }
 
     /// Crate vesting escrow from root
@@ -200,4 +243,258 @@ pub mod locker {
     ) -> Result<()> {
         handle_create_vesting_escrow_from_root(ctx, &params, proof, remaining_accounts_info)
     }
+
+    /// Crate vesting escrow from root, for root escrow whose leaves may include a leaf index
+    /// # Arguments
+    ///
+    /// * ctx - The accounts needed by instruction.
+    /// * params - The vesting schedule of the leaf, leaf_index is required for leaf version 2 and 3
+    /// * proof - The merkle proof of the leaf
+    /// * remaining_accounts_info: additional accounts needed by instruction
+    ///
+    pub fn create_vesting_escrow_from_root_v2<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CreateVestingEscrowFromRoot2Ctx<'info>>,
+        params: CreateVestingEscrowFromRoot2Params,
+        proof: Vec<[u8; 32]>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        handle_create_vesting_escrow_from_root2(ctx, &params, proof, remaining_accounts_info)
+    }
+
+    /// Claim vested tokens of a leaf directly from root escrow, for root escrow in direct claim mode
+    /// # Arguments
+    ///
+    /// * ctx - The accounts needed by instruction.
+    /// * params - The vesting schedule of the leaf
+    /// * proof - The merkle proof of the leaf
+    /// * max_amount - The maximum amount claimed by the recipient
+    /// * remaining_accounts_info: additional accounts needed by instruction
+    ///
+    pub fn claim_from_root_escrow<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClaimFromRootEscrowCtx<'info>>,
+        params: CreateVestingEscrowFromRoot2Params,
+        proof: Vec<[u8; 32]>,
+        max_amount: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        handle_claim_from_root_escrow(ctx, &params, proof, max_amount, remaining_accounts_info)
+    }
+
+    /// Close claim status of a leaf once root escrow is fully distributed or after the clawback
+    /// deadline, returning its rent to the recipient. Once root escrow is closed, use
+    /// close_root_escrow_claim_status_from_closed_root
+    pub fn close_root_escrow_claim_status(
+        ctx: Context<CloseRootEscrowClaimStatusCtx>,
+    ) -> Result<()> {
+        handle_close_root_escrow_claim_status(ctx)
+    }
+
+    /// Close claim status of a leaf whose root escrow is closed, returning its rent to the
+    /// recipient
+    pub fn close_root_escrow_claim_status_from_closed_root(
+        ctx: Context<CloseRootEscrowClaimStatusFromClosedRootCtx>,
+    ) -> Result<()> {
+        handle_close_root_escrow_claim_status_from_closed_root(ctx)
+    }
+
+    /// Create claim bitmap of root escrow in bitmap mode, tracking claims of leaves from
+    /// chunk_index * LEAVES_PER_CLAIM_BITMAP
+    pub fn create_root_escrow_claim_bitmap(
+        ctx: Context<CreateRootEscrowClaimBitmapCtx>,
+        chunk_index: u64,
+    ) -> Result<()> {
+        handle_create_root_escrow_claim_bitmap(ctx, chunk_index)
+    }
+
+    /// Claim a fully unlocked leaf directly from root escrow, for root escrow in bitmap mode
+    /// # Arguments
+    ///
+    /// * ctx - The accounts needed by instruction.
+    /// * params - The vesting schedule of the leaf, leaf_index is the position of the leaf
+    /// * proof - The merkle proof of the leaf
+    /// * remaining_accounts_info: additional accounts needed by instruction
+    ///
+    pub fn claim_from_root_escrow_bitmap<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClaimFromRootEscrowBitmapCtx<'info>>,
+        params: CreateVestingEscrowFromRoot2Params,
+        proof: Vec<[u8; 32]>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        handle_claim_from_root_escrow_bitmap(ctx, &params, proof, remaining_accounts_info)
+    }
+
+    /// Close claim bitmap of root escrow once no leaf can be claimed, must be done before
+    /// closing root escrow
+    pub fn close_root_escrow_claim_bitmap(
+        ctx: Context<CloseRootEscrowClaimBitmapCtx>,
+    ) -> Result<()> {
+        handle_close_root_escrow_claim_bitmap(ctx)
+    }
+
+    /// Cancel a vesting escrow created from root escrow.
+    ///   - The claimable token will be transferred to recipient
+    ///   - The remaining token will be transferred back to the root escrow
+    /// This instruction supports both splToken and token2022
+    /// # Arguments
+    ///
+    /// * ctx - The accounts needed by instruction.
+    /// * remaining_accounts_info: additional accounts needed by instruction
+    ///
+    pub fn cancel_vesting_escrow_from_root<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CancelVestingEscrowFromRootCtx<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        handle_cancel_vesting_escrow_from_root(ctx, remaining_accounts_info)
+    }
+
+    /// Cancel a vesting escrow created from a closed root escrow.
+    ///   - The claimable token will be transferred to recipient
+    ///   - The remaining token will be transferred to the creator
+    /// This instruction supports both splToken and token2022
+    /// # Arguments
+    ///
+    /// * ctx - The accounts needed by instruction.
+    /// * remaining_accounts_info: additional accounts needed by instruction
+    ///
+    pub fn cancel_vesting_escrow_from_closed_root<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CancelVestingEscrowFromClosedRootCtx<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        handle_cancel_vesting_escrow_from_closed_root(ctx, remaining_accounts_info)
+    }
+
+    /// Cancel a vesting escrow created from multi mint root escrow whose root escrow mint is
+    /// closed.
+    ///   - The claimable token will be transferred to recipient
+    ///   - The remaining token will be transferred to the creator
+    /// This instruction supports both splToken and token2022
+    /// # Arguments
+    ///
+    /// * ctx - The accounts needed by instruction.
+    /// * remaining_accounts_info: additional accounts needed by instruction
+    ///
+    pub fn cancel_vesting_escrow_from_closed_root_escrow_mint<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CancelVestingEscrowFromClosedRootEscrowMintCtx<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        handle_cancel_vesting_escrow_from_closed_root_escrow_mint(ctx, remaining_accounts_info)
+    }
+
+    /// Claw back tokens that were not distributed from root escrow, after the clawback deadline
+    pub fn clawback_root_escrow<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClawbackRootEscrowCtx<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        handle_clawback_root_escrow(ctx, remaining_accounts_info)
+    }
+
+    /// Close root escrow and its token account once fully distributed or after the clawback deadline,
+    /// a multi mint root escrow is closed by closing its mints with close_root_escrow_mint
+    pub fn close_root_escrow<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CloseRootEscrowCtx<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        handle_close_root_escrow(ctx, remaining_accounts_info)
+    }
+
+    /// Update merkle root of root escrow, before any escrow is created from it
+    pub fn update_root_escrow_root(
+        ctx: Context<UpdateRootEscrowRootCtx>,
+        params: UpdateRootEscrowRootParameters,
+    ) -> Result<()> {
+        handle_update_root_escrow_root(ctx, &params)
+    }
+
+    /// Deposit SOL to rent pool of root escrow, paying rent of escrows created from root escrow
+    pub fn deposit_root_escrow_rent_pool(
+        ctx: Context<DepositRootEscrowRentPoolCtx>,
+        amount: u64,
+    ) -> Result<()> {
+        handle_deposit_root_escrow_rent_pool(ctx, amount)
+    }
+
+    /// Create root escrow distributing several mints from one merkle tree, in escrow distribution
+    /// mode. Each mint is added with create_root_escrow_mint, then the root escrow is used with the
+    /// multi mint instructions taking the root_escrow_mint of the mint
+    pub fn create_multi_mint_root_escrow(
+        ctx: Context<CreateMultiMintRootEscrowCtx>,
+        params: CreateMultiMintRootEscrowParameters,
+    ) -> Result<()> {
+        handle_create_multi_mint_root_escrow(ctx, &params)
+    }
+
+    /// Add a mint to multi mint root escrow, before any escrow is created from it
+    pub fn create_root_escrow_mint(
+        ctx: Context<CreateRootEscrowMintCtx>,
+        max_claim_amount: u64,
+    ) -> Result<()> {
+        handle_create_root_escrow_mint(ctx, max_claim_amount)
+    }
+
+    /// Close a mint of multi mint root escrow and its token account once fully distributed or
+    /// after the clawback deadline, closing the last mint closes the root escrow
+    pub fn close_root_escrow_mint<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CloseRootEscrowMintCtx<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        handle_close_root_escrow_mint(ctx, remaining_accounts_info)
+    }
+
+    /// Fund a mint of multi mint root escrow
+    pub fn fund_multi_mint_root_escrow<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, FundMultiMintRootEscrowCtx<'info>>,
+        max_amount: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        handle_fund_multi_mint_root_escrow(ctx, max_amount, remaining_accounts_info)
+    }
+
+    /// Create vesting escrow from a leaf of multi mint root escrow
+    pub fn create_vesting_escrow_from_multi_mint_root<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CreateVestingEscrowFromMultiMintRootCtx<'info>>,
+        params: CreateVestingEscrowFromRoot2Params,
+        proof: Vec<[u8; 32]>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        handle_create_vesting_escrow_from_multi_mint_root(
+            ctx,
+            &params,
+            proof,
+            remaining_accounts_info,
+        )
+    }
+
+    /// Cancel a vesting escrow created from multi mint root escrow.
+    ///   - The claimable token will be transferred to recipient
+    ///   - The remaining token will be transferred back to the root escrow, for its root escrow mint
+    /// This instruction supports both splToken and token2022
+    /// # Arguments
+    ///
+    /// * ctx - The accounts needed by instruction.
+    /// * remaining_accounts_info: additional accounts needed by instruction
+    ///
+    pub fn cancel_vesting_escrow_from_multi_mint_root<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CancelVestingEscrowFromMultiMintRootCtx<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        handle_cancel_vesting_escrow_from_multi_mint_root(ctx, remaining_accounts_info)
+    }
+
+    /// Claw back tokens of a mint that were not distributed from multi mint root escrow, after the
+    /// clawback deadline
+    pub fn clawback_multi_mint_root_escrow<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ClawbackMultiMintRootEscrowCtx<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        handle_clawback_multi_mint_root_escrow(ctx, remaining_accounts_info)
+    }
+
+    /// Update merkle root of multi mint root escrow and max claim amount of one of its mints,
+    /// before any escrow is created from it
+    pub fn update_multi_mint_root_escrow_root(
+        ctx: Context<UpdateMultiMintRootEscrowRootCtx>,
+        params: UpdateRootEscrowRootParameters,
+    ) -> Result<()> {
+        handle_update_multi_mint_root_escrow_root(ctx, &params)
+    }
 }
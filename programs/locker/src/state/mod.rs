@@ -1,7 +1,15 @@
 pub use root_escrow::*;
+pub use root_escrow_claim_bitmap::*;
+pub use root_escrow_claim_status::*;
+pub use root_escrow_mint::*;
 pub use vesting_escrow::*;
 pub use vesting_escrow_metadata::*;
+pub use vesting_escrow_recipients::*;
 
 pub mod root_escrow;
+pub mod root_escrow_claim_bitmap;
+pub mod root_escrow_claim_status;
+pub mod root_escrow_mint;
 pub mod vesting_escrow;
 pub mod vesting_escrow_metadata;
+pub mod vesting_escrow_recipients;
@@ -1,8 +1,38 @@
+use num_enum::{IntoPrimitive, TryFromPrimitive};
 use safe_math::SafeMath;
 use static_assertions::const_assert_eq;
 
 use crate::*;
 
+/// How recipients receive their tokens from root escrow
+#[derive(Copy, Clone, Debug, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+pub enum DistributionMode {
+    Escrow,      // a vesting escrow is created for each leaf
+    DirectClaim, // recipients claim from root escrow, tracked by a claim status per leaf
+    Bitmap, // recipients claim fully unlocked leaves from root escrow, tracked by claim bitmaps
+}
+
+/// Fields hashed in a merkle tree leaf
+#[derive(Copy, Clone, Debug, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+pub enum LeafVersion {
+    V0, // recipient and schedule
+    V1, // recipient, token mint and schedule
+    V2, // recipient, leaf index and schedule
+    V3, // recipient, token mint, leaf index and schedule
+}
+
+impl LeafVersion {
+    pub fn has_mint(&self) -> bool {
+        matches!(self, LeafVersion::V1 | LeafVersion::V3)
+    }
+
+    pub fn has_leaf_index(&self) -> bool {
+        matches!(self, LeafVersion::V2 | LeafVersion::V3)
+    }
+}
+
 #[account(zero_copy)]
 #[derive(Default, InitSpace, Debug)]
 pub struct RootEscrow {
@@ -18,8 +48,12 @@ pub struct RootEscrow {
     pub bump: u8,
     /// token program flag
     pub token_program_flag: u8,
+    /// leaf version
+    pub leaf_version: u8,
+    /// distribution mode
+    pub distribution_mode: u8,
     /// padding
-    pub padding_0: [u8; 6],
+    pub padding_0: [u8; 4],
     /// max claim amount
     pub max_claim_amount: u64,
     /// max escrow
@@ -32,10 +66,18 @@ pub struct RootEscrow {
     pub total_distribute_amount: u64,
     /// version
     pub version: u64,
+    /// timestamp after which creator can claw back undistributed tokens, 0 means disabled
+    pub clawback_deadline: u64,
+    /// total clawed back amount
+    pub total_clawback_amount: u64,
+    /// lamports deposited to pay rent of escrows created from root escrow
+    pub rent_pool_amount: u64,
+    /// number of open [RootEscrowMint] of a multi mint root escrow
+    pub total_mint: u64,
     /// padding
-    pub padding: u64,
+    pub padding_1: u64,
     /// buffer
-    pub buffer: [u128; 5],
+    pub buffer: [u128; 3],
 }
 
 const_assert_eq!(RootEscrow::INIT_SPACE, 272);
@@ -52,6 +94,9 @@ impl RootEscrow {
         bump: u8,
         version: u64,
         token_program_flag: u8,
+        clawback_deadline: u64,
+        leaf_version: u8,
+        distribution_mode: u8,
     ) {
         self.token_mint = token_mint;
         self.creator = creator;
@@ -62,6 +107,71 @@ impl RootEscrow {
         self.token_program_flag = token_program_flag;
         self.version = version;
         self.max_escrow = max_escrow;
+        self.clawback_deadline = clawback_deadline;
+        self.leaf_version = leaf_version;
+        self.distribution_mode = distribution_mode;
+    }
+
+    /// A multi mint root escrow has no token mint, amounts of each mint are tracked by a
+    /// [RootEscrowMint] and `max_escrow` counts the leaves of every mint
+    pub fn is_multi_mint(&self) -> bool {
+        self.token_mint == Pubkey::default()
+    }
+
+    pub fn add_mint(&mut self) -> Result<()> {
+        require!(self.is_multi_mint(), LockerError::InvalidParams);
+        require!(
+            self.total_escrow_created == 0,
+            LockerError::RootEscrowDistributionStarted
+        );
+        self.total_mint = self.total_mint.safe_add(1)?;
+        Ok(())
+    }
+
+    pub fn remove_mint(&mut self) -> Result<()> {
+        self.total_mint = self.total_mint.safe_sub(1)?;
+        Ok(())
+    }
+
+    pub fn validate_distribution_mode(&self, distribution_mode: DistributionMode) -> Result<()> {
+        require!(
+            self.distribution_mode == u8::from(distribution_mode),
+            LockerError::InvalidDistributionMode
+        );
+        Ok(())
+    }
+
+    pub fn is_fully_distributed(&self) -> bool {
+        if self.distribution_mode == u8::from(DistributionMode::Escrow) {
+            self.total_escrow_created == self.max_escrow
+        } else {
+            self.total_distribute_amount == self.max_claim_amount
+        }
+    }
+
+    pub fn is_clawback_started(&self, current_ts: u64) -> bool {
+        self.clawback_deadline != 0 && current_ts >= self.clawback_deadline
+    }
+
+    pub fn update_root(
+        &mut self,
+        root: [u8; 32],
+        max_claim_amount: u64,
+        max_escrow: u64,
+    ) -> Result<()> {
+        require!(
+            self.total_escrow_created == 0,
+            LockerError::RootEscrowDistributionStarted
+        );
+        // funded tokens above the new max claim amount couldn't be distributed
+        require!(
+            max_claim_amount >= self.total_funded_amount,
+            LockerError::InvalidParams
+        );
+        self.root = root;
+        self.max_claim_amount = max_claim_amount;
+        self.max_escrow = max_escrow;
+        Ok(())
     }
 
     pub fn get_and_set_fund_amount(&mut self, max_amount: u64) -> Result<u64> {
@@ -78,6 +188,53 @@ impl RootEscrow {
         self.total_distribute_amount = self.total_distribute_amount.safe_add(total_deposit)?;
         Ok(())
     }
+
+    /// Escrows of every mint count towards `max_escrow` of a multi mint root escrow, the
+    /// distributed amount is tracked by the [RootEscrowMint]
+    pub fn update_new_mint_escrow(&mut self) -> Result<()> {
+        self.total_escrow_created = self.total_escrow_created.safe_add(1)?;
+        Ok(())
+    }
+
+    pub fn deposit_rent_pool(&mut self, amount: u64) -> Result<()> {
+        self.rent_pool_amount = self.rent_pool_amount.safe_add(amount)?;
+        Ok(())
+    }
+
+    /// The rent pool reimburses the payer of `create_vesting_escrow_from_root`, whoever signs it,
+    /// for the rent of the escrow and escrow token accounts, refunded when the escrow is cancelled
+    /// or closed. Rent of accounts never closed, like the base marker or claim statuses, isn't
+    /// reimbursed, so the pool only advances rent that is recovered later.
+    pub fn get_and_set_rent_reimbursement(&mut self, rent: u64) -> Result<u64> {
+        let reimbursement = rent.min(self.rent_pool_amount);
+        self.rent_pool_amount = self.rent_pool_amount.safe_sub(reimbursement)?;
+        Ok(reimbursement)
+    }
+
+    pub fn update_new_claim_status(&mut self) -> Result<()> {
+        self.total_escrow_created = self.total_escrow_created.safe_add(1)?;
+        Ok(())
+    }
+
+    pub fn accumulate_claimed_amount(&mut self, claimed_amount: u64) -> Result<()> {
+        self.total_distribute_amount = self.total_distribute_amount.safe_add(claimed_amount)?;
+        Ok(())
+    }
+
+    /// Unvested tokens of a cancelled escrow are returned to root escrow
+    pub fn return_distributed_amount(&mut self, returned_amount: u64) -> Result<()> {
+        self.total_distribute_amount = self.total_distribute_amount.safe_sub(returned_amount)?;
+        Ok(())
+    }
+
+    pub fn get_and_set_clawback_amount(&mut self) -> Result<u64> {
+        let clawback_amount = self
+            .total_funded_amount
+            .safe_sub(self.total_distribute_amount)?
+            .safe_sub(self.total_clawback_amount)?;
+        self.total_clawback_amount = self.total_clawback_amount.safe_add(clawback_amount)?;
+        Ok(clawback_amount)
+    }
 }
 // #[test]
 // fn test_size() {
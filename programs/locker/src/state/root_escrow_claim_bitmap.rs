@@ -0,0 +1,65 @@
+use static_assertions::const_assert_eq;
+
+use crate::*;
+
+/// Number of leaves tracked by a claim bitmap
+pub const LEAVES_PER_CLAIM_BITMAP: u64 = 65536;
+
+#[account(zero_copy)]
+#[derive(InitSpace, Debug)]
+pub struct RootEscrowClaimBitmap {
+    /// root escrow
+    pub root_escrow: Pubkey,
+    /// chunk index, the bitmap tracks leaves from chunk_index * LEAVES_PER_CLAIM_BITMAP
+    pub chunk_index: u64,
+    /// claimed flags of leaves
+    pub bits: [u64; 1024],
+}
+
+const_assert_eq!(RootEscrowClaimBitmap::INIT_SPACE, 8232);
+
+impl RootEscrowClaimBitmap {
+    pub fn init(&mut self, root_escrow: Pubkey, chunk_index: u64) {
+        self.root_escrow = root_escrow;
+        self.chunk_index = chunk_index;
+    }
+
+    pub fn set_claimed(&mut self, leaf_index: u64) -> Result<()> {
+        require!(
+            leaf_index / LEAVES_PER_CLAIM_BITMAP == self.chunk_index,
+            LockerError::InvalidParams
+        );
+        let offset = leaf_index % LEAVES_PER_CLAIM_BITMAP;
+        let word = &mut self.bits[(offset / 64) as usize];
+        let mask = 1u64 << (offset % 64);
+        require!(*word & mask == 0, LockerError::LeafAlreadyClaimed);
+        *word |= mask;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod claim_bitmap_test {
+    use super::*;
+
+    #[test]
+    fn test_set_claimed() {
+        let mut claim_bitmap = RootEscrowClaimBitmap {
+            root_escrow: Pubkey::default(),
+            chunk_index: 1,
+            bits: [0; 1024],
+        };
+
+        claim_bitmap.set_claimed(LEAVES_PER_CLAIM_BITMAP).unwrap();
+        claim_bitmap
+            .set_claimed(2 * LEAVES_PER_CLAIM_BITMAP - 1)
+            .unwrap();
+        assert_eq!(claim_bitmap.bits[0], 1);
+        assert_eq!(claim_bitmap.bits[1023], 1 << 63);
+
+        // leaf is claimed only once
+        assert!(claim_bitmap.set_claimed(LEAVES_PER_CLAIM_BITMAP).is_err());
+        // leaf of another chunk
+        assert!(claim_bitmap.set_claimed(0).is_err());
+    }
+}
@@ -0,0 +1,41 @@
+use static_assertions::const_assert_eq;
+
+use crate::*;
+
+use self::safe_math::SafeMath;
+
+#[account(zero_copy)]
+#[derive(Default, InitSpace, Debug)]
+pub struct RootEscrowClaimStatus {
+    /// root escrow
+    pub root_escrow: Pubkey,
+    /// recipient address
+    pub recipient: Pubkey,
+    /// total claimed amount
+    pub total_claimed_amount: u64,
+    /// padding
+    pub padding_0: u64,
+    /// buffer
+    pub buffer: [u128; 4],
+}
+
+const_assert_eq!(RootEscrowClaimStatus::INIT_SPACE, 144);
+
+impl RootEscrowClaimStatus {
+    pub fn init(&mut self, root_escrow: Pubkey, recipient: Pubkey) {
+        self.root_escrow = root_escrow;
+        self.recipient = recipient;
+    }
+
+    /// Claim status is initialized at the first claim of its leaf
+    pub fn is_initialized(&self) -> bool {
+        self.root_escrow != Pubkey::default()
+    }
+
+    pub fn claim(&mut self, max_unlocked_amount: u64, max_amount: u64) -> Result<u64> {
+        let claimable_amount = max_unlocked_amount.safe_sub(self.total_claimed_amount)?;
+        let amount = claimable_amount.min(max_amount);
+        self.total_claimed_amount = self.total_claimed_amount.safe_add(amount)?;
+        Ok(amount)
+    }
+}
@@ -0,0 +1,91 @@
+use safe_math::SafeMath;
+use static_assertions::const_assert_eq;
+
+use crate::*;
+
+/// Accounting of a mint distributed by a multi mint root escrow, the root escrow owns a token
+/// account per mint
+#[account(zero_copy)]
+#[derive(Default, InitSpace, Debug)]
+pub struct RootEscrowMint {
+    /// root escrow
+    pub root_escrow: Pubkey,
+    /// token mint
+    pub token_mint: Pubkey,
+    /// bump
+    pub bump: u8,
+    /// token program flag
+    pub token_program_flag: u8,
+    /// padding
+    pub padding_0: [u8; 6],
+    /// max claim amount of the leaves of the mint
+    pub max_claim_amount: u64,
+    /// total funded amount
+    pub total_funded_amount: u64,
+    /// total distributed amount
+    pub total_distribute_amount: u64,
+    /// total clawed back amount
+    pub total_clawback_amount: u64,
+    /// padding
+    pub padding_1: u64,
+    /// buffer
+    pub buffer: [u128; 4],
+}
+
+const_assert_eq!(RootEscrowMint::INIT_SPACE, 176);
+
+impl RootEscrowMint {
+    pub fn init(
+        &mut self,
+        root_escrow: Pubkey,
+        token_mint: Pubkey,
+        bump: u8,
+        token_program_flag: u8,
+        max_claim_amount: u64,
+    ) {
+        self.root_escrow = root_escrow;
+        self.token_mint = token_mint;
+        self.bump = bump;
+        self.token_program_flag = token_program_flag;
+        self.max_claim_amount = max_claim_amount;
+    }
+
+    pub fn update_max_claim_amount(&mut self, max_claim_amount: u64) -> Result<()> {
+        // funded tokens above the new max claim amount couldn't be distributed
+        require!(
+            max_claim_amount >= self.total_funded_amount,
+            LockerError::InvalidParams
+        );
+        self.max_claim_amount = max_claim_amount;
+        Ok(())
+    }
+
+    pub fn get_and_set_fund_amount(&mut self, max_amount: u64) -> Result<u64> {
+        let max_amount_should_be_funded =
+            self.max_claim_amount.safe_sub(self.total_funded_amount)?;
+        let funded_amount = max_amount_should_be_funded.min(max_amount);
+
+        self.total_funded_amount = self.total_funded_amount.safe_add(funded_amount)?;
+        Ok(funded_amount)
+    }
+
+    pub fn accumulate_distributed_amount(&mut self, total_deposit: u64) -> Result<()> {
+        self.total_distribute_amount = self.total_distribute_amount.safe_add(total_deposit)?;
+        Ok(())
+    }
+
+    /// Unvested tokens of a cancelled escrow are returned to root escrow
+    pub fn return_distributed_amount(&mut self, returned_amount: u64) -> Result<()> {
+        self.total_distribute_amount = self.total_distribute_amount.safe_sub(returned_amount)?;
+        Ok(())
+    }
+
+    pub fn get_and_set_clawback_amount(&mut self) -> Result<u64> {
+        let clawback_amount = self
+            .total_funded_amount
+            .safe_sub(self.total_distribute_amount)?
+            .safe_sub(self.total_clawback_amount)?;
+        self.total_clawback_amount = self.total_clawback_amount.safe_add(clawback_amount)?;
+        Ok(clawback_amount)
+    }
+}
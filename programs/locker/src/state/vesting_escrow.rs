@@ -30,6 +30,13 @@ pub enum TokenProgramFlag {
     UseToken2022, //1
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+pub enum RecipientFlag {
+    SingleRecipient,    //0
+    MultipleRecipients, //1
+}
+
 #[account(zero_copy)]
 #[derive(Default, InitSpace, Debug)]
 pub struct Marker {}
@@ -53,8 +60,10 @@ pub struct VestingEscrow {
     pub cancel_mode: u8,
     /// token program flag
     pub token_program_flag: u8,
+    /// recipient flag
+    pub recipient_flag: u8,
     /// padding
-    pub padding_0: [u8; 4],
+    pub padding_0: [u8; 3],
     /// cliff time
     pub cliff_time: u64,
     /// frequency
@@ -73,11 +82,14 @@ pub struct VestingEscrow {
     pub cancelled_at: u64,
     /// buffer
     pub padding_1: u64,
+    /// root escrow that the escrow is created from, default pubkey for escrows not created from root
+    /// escrow
+    pub root_escrow: Pubkey,
     /// buffer
-    pub buffer: [u128; 5],
+    pub buffer: [u128; 3],
 }
 
-const_assert_eq!(VestingEscrow::INIT_SPACE, 288); //  32 * 4 + 8 * 8 + 16 * 6
+const_assert_eq!(VestingEscrow::INIT_SPACE, 288); //  32 * 5 + 8 * 8 + 16 * 4
 
 impl VestingEscrow {
     pub fn init(
@@ -153,6 +165,14 @@ impl VestingEscrow {
         Ok(amount)
     }
 
+    pub fn is_created_from_root(&self) -> bool {
+        self.root_escrow != Pubkey::default()
+    }
+
+    pub fn is_multi_recipient(&self) -> bool {
+        self.recipient_flag == u8::from(RecipientFlag::MultipleRecipients)
+    }
+
     pub fn update_recipient(&mut self, new_recipient: Pubkey) {
         self.recipient = new_recipient;
     }
@@ -201,6 +221,15 @@ mod escrow_test {
 
     use super::*;
 
+    #[test]
+    fn test_actor_modes_match_vesting_validate() {
+        for mode in 0..=u8::MAX {
+            let valid = mode <= vesting_validate::MAX_ACTOR_MODE;
+            assert_eq!(UpdateRecipientMode::try_from(mode).is_ok(), valid);
+            assert_eq!(CancelMode::try_from(mode).is_ok(), valid);
+        }
+    }
+
     proptest! {
     #[test]
     fn test_get_max_unlocked_amount(
@@ -0,0 +1,148 @@
+use static_assertions::const_assert_eq;
+
+use crate::*;
+
+use self::safe_math::SafeMath;
+
+/// Maximum number of recipients that can share a vesting escrow
+pub const MAX_ESCROW_RECIPIENTS: usize = 10;
+
+/// Total share of all recipients, in basis points
+pub const MAX_SHARE_BPS: u16 = 10_000;
+
+#[zero_copy]
+#[derive(Default, InitSpace, Debug)]
+pub struct EscrowRecipient {
+    /// recipient address
+    pub recipient: Pubkey,
+    /// share of the vested amount in basis points
+    pub share_bps: u16,
+    /// padding
+    pub padding_0: [u8; 6],
+    /// total claimed amount
+    pub total_claimed_amount: u64,
+}
+
+const_assert_eq!(EscrowRecipient::INIT_SPACE, 48);
+
+/// Recipients sharing a vesting escrow.
+#[account(zero_copy)]
+#[derive(Default, InitSpace, Debug)]
+pub struct VestingEscrowRecipients {
+    /// The [VestingEscrow].
+    pub escrow: Pubkey,
+    /// number of recipients
+    pub num_recipients: u8,
+    /// padding
+    pub padding_0: [u8; 7],
+    /// recipients
+    pub recipients: [EscrowRecipient; MAX_ESCROW_RECIPIENTS],
+}
+
+const_assert_eq!(VestingEscrowRecipients::INIT_SPACE, 520); // 32 + 8 + 48 * 10
+
+impl VestingEscrowRecipients {
+    pub fn init(&mut self, escrow: Pubkey, recipients: &[RecipientShareParameters]) {
+        self.escrow = escrow;
+        self.num_recipients = recipients.len() as u8;
+        for (slot, params) in self.recipients.iter_mut().zip(recipients.iter()) {
+            slot.recipient = params.recipient;
+            slot.share_bps = params.share_bps;
+        }
+    }
+
+    fn get_recipient_index(&self, recipient: Pubkey) -> Result<usize> {
+        self.recipients[..self.num_recipients as usize]
+            .iter()
+            .position(|r| r.recipient == recipient)
+            .ok_or(LockerError::NotPermitToDoThisAction.into())
+    }
+
+    /// Share of `unlocked_amount` belonging to recipient at `index`.
+    /// Shares are computed from the cumulative basis points, so the sum over all recipients is
+    /// exactly `unlocked_amount` and no rounding dust is left in the escrow.
+    fn get_unlocked_share(&self, index: usize, unlocked_amount: u64) -> Result<u64> {
+        let lower_bps = self.recipients[..index]
+            .iter()
+            .map(|r| u128::from(r.share_bps))
+            .sum::<u128>();
+        let upper_bps = lower_bps.safe_add(u128::from(self.recipients[index].share_bps))?;
+
+        let unlocked_amount = u128::from(unlocked_amount);
+        let max_share_bps = u128::from(MAX_SHARE_BPS);
+        let lower = unlocked_amount
+            .safe_mul(lower_bps)?
+            .safe_div(max_share_bps)?;
+        let upper = unlocked_amount
+            .safe_mul(upper_bps)?
+            .safe_div(max_share_bps)?;
+
+        let share = upper.safe_sub(lower)?;
+        u64::try_from(share).map_err(|_| LockerError::MathOverflow.into())
+    }
+
+    pub fn get_claimable_amount(
+        &self,
+        escrow: &VestingEscrow,
+        recipient: Pubkey,
+        current_ts: u64,
+    ) -> Result<u64> {
+        let index = self.get_recipient_index(recipient)?;
+        let unlocked_amount = escrow.get_max_unlocked_amount(current_ts)?;
+        let unlocked_share = self.get_unlocked_share(index, unlocked_amount)?;
+        let claimable_amount =
+            unlocked_share.safe_sub(self.recipients[index].total_claimed_amount)?;
+        Ok(claimable_amount)
+    }
+
+    pub fn claim(
+        &mut self,
+        escrow: &mut VestingEscrow,
+        recipient: Pubkey,
+        max_amount: u64,
+    ) -> Result<u64> {
+        let current_ts = Clock::get()?.unix_timestamp as u64;
+        let claimable_amount = self.get_claimable_amount(escrow, recipient, current_ts)?;
+
+        let amount = claimable_amount.min(max_amount);
+        let index = self.get_recipient_index(recipient)?;
+        let escrow_recipient = &mut self.recipients[index];
+        escrow_recipient.total_claimed_amount =
+            escrow_recipient.total_claimed_amount.safe_add(amount)?;
+        escrow.accumulate_claimed_amount(amount)?;
+
+        Ok(amount)
+    }
+}
+
+#[cfg(test)]
+mod escrow_recipients_test {
+    use proptest::proptest;
+
+    use super::*;
+
+    proptest! {
+    #[test]
+    fn test_unlocked_shares_sum_to_unlocked_amount(
+        unlocked_amount in 0..u64::MAX,
+        first_share_bps in 0..=MAX_SHARE_BPS,
+        second_share_bps in 0..=MAX_SHARE_BPS,
+    ) {
+        let second_share_bps = second_share_bps.min(MAX_SHARE_BPS - first_share_bps);
+        let third_share_bps = MAX_SHARE_BPS - first_share_bps - second_share_bps;
+
+        let mut escrow_recipients = VestingEscrowRecipients {
+            num_recipients: 3,
+            ..Default::default()
+        };
+        escrow_recipients.recipients[0].share_bps = first_share_bps;
+        escrow_recipients.recipients[1].share_bps = second_share_bps;
+        escrow_recipients.recipients[2].share_bps = third_share_bps;
+
+        let total_share = (0..3)
+            .map(|i| escrow_recipients.get_unlocked_share(i, unlocked_amount).unwrap())
+            .sum::<u64>();
+        assert_eq!(total_share, unlocked_amount);
+        }
+    }
+}
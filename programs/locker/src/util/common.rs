@@ -16,6 +16,16 @@ pub fn close<'info>(info: AccountInfo<'info>, sol_destination: AccountInfo<'info
     info.realloc(0, false).map_err(Into::into)
 }
 
+pub fn transfer_lamports<'info>(
+    info: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    **info.lamports.borrow_mut() = info.lamports().checked_sub(amount).unwrap();
+    **destination.lamports.borrow_mut() = destination.lamports().checked_add(amount).unwrap();
+    Ok(())
+}
+
 pub fn is_closed(info: &AccountInfo) -> bool {
     info.owner == &System::id() && info.data_is_empty()
 }
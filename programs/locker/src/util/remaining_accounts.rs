@@ -5,6 +5,7 @@ use crate::LockerError;
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
 pub enum AccountsType {
     TransferHookEscrow,
+    EscrowRecipients,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -21,6 +22,7 @@ pub struct RemainingAccountsInfo {
 #[derive(Default)]
 pub struct ParsedRemainingAccounts<'a, 'info> {
     pub transfer_hook_escrow: Option<&'a [AccountInfo<'info>]>,
+    pub escrow_recipients: Option<&'a [AccountInfo<'info>]>,
 }
 
 pub fn parse_remaining_accounts<'a, 'info>(
@@ -58,6 +60,12 @@ pub fn parse_remaining_accounts<'a, 'info>(
                 }
                 parsed_remaining_accounts.transfer_hook_escrow = Some(accounts);
             }
+            AccountsType::EscrowRecipients => {
+                if parsed_remaining_accounts.escrow_recipients.is_some() {
+                    return Err(LockerError::DuplicatedRemainingAccountTypes.into());
+                }
+                parsed_remaining_accounts.escrow_recipients = Some(accounts);
+            }
         }
     }
 
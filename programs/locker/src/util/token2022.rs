@@ -1,4 +1,4 @@
-use anchor_lang::{prelude::*, solana_program};
+use anchor_lang::{prelude::*, solana_program, solana_program::program_pack::Pack};
 use anchor_spl::memo;
 use anchor_spl::memo::{BuildMemo, Memo};
 use anchor_spl::token::Token;
@@ -7,15 +7,110 @@ use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::{
 };
 use anchor_spl::token_2022::spl_token_2022::{
     self,
-    extension::{self, StateWithExtensions},
+    extension::{self, Extension},
 };
-use anchor_spl::token_interface::spl_token_2022::extension::BaseStateWithExtensions;
 use anchor_spl::token_interface::{
     harvest_withheld_tokens_to_mint, HarvestWithheldTokensToMint, Mint, TokenAccount,
     TokenInterface,
 };
+use bytemuck::{Pod, Zeroable};
+
+use crate::{EventTransferUiAmount, LockerError, RootEscrow, VestingEscrow};
+
+// Extension types added after the spl-token-2022 v6 of anchor-spl, their TLV entries are parsed
+// by hand with the layout of spl-token-2022 v7
+const SCALED_UI_AMOUNT_EXTENSION_TYPE: u16 = 25;
+const PAUSABLE_EXTENSION_TYPE: u16 = 26;
+
+/// ScaledUiAmountConfig mint extension of spl-token-2022 v7
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+struct ScaledUiAmountConfig {
+    authority: [u8; 32],
+    /// f64 multiplier of raw amounts, outside of the decimals
+    multiplier: [u8; 8],
+    /// i64 timestamp at which `new_multiplier` is effective
+    new_multiplier_effective_timestamp: [u8; 8],
+    /// f64 multiplier once `new_multiplier_effective_timestamp` is reached
+    new_multiplier: [u8; 8],
+}
 
-use crate::{LockerError, RootEscrow, VestingEscrow};
+impl ScaledUiAmountConfig {
+    /// Same as `ScaledUiAmountConfig::amount_to_ui_amount` of spl-token-2022 v7
+    fn amount_to_ui_amount(&self, amount: u64, decimals: u8, unix_timestamp: i64) -> String {
+        let multiplier =
+            if unix_timestamp >= i64::from_le_bytes(self.new_multiplier_effective_timestamp) {
+                f64::from_le_bytes(self.new_multiplier)
+            } else {
+                f64::from_le_bytes(self.multiplier)
+            };
+        let scaled_amount = (amount as f64) * (multiplier / 10_f64.powi(decimals as i32));
+        let ui_amount = format!("{:.*}", decimals as usize, scaled_amount);
+        if decimals > 0 {
+            ui_amount
+                .trim_end_matches('0')
+                .trim_end_matches('.')
+                .to_string()
+        } else {
+            ui_amount
+        }
+    }
+}
+
+/// PausableConfig mint extension of spl-token-2022 v7
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+struct PausableConfig {
+    authority: [u8; 32],
+    paused: u8,
+}
+
+/// TLV entries of the data of a token 2022 mint or token account, after the base state padded
+/// to the length of a token account and the account type
+fn get_tlv_data(data: &[u8]) -> &[u8] {
+    data.get(spl_token_2022::state::Account::LEN + 1..)
+        .unwrap_or_default()
+}
+
+/// Type and value of the TLV entries of `tlv_data`. Entries are walked by hand as spl-token-2022
+/// v6 fails on extension types it doesn't know
+fn get_tlv_entries(tlv_data: &[u8]) -> Result<Vec<(u16, &[u8])>> {
+    let mut entries = vec![];
+    let mut type_start = 0;
+    // trailing bytes too short for a type and a length are left by a realloc
+    while type_start + 4 <= tlv_data.len() {
+        let extension_type = u16::from_le_bytes([tlv_data[type_start], tlv_data[type_start + 1]]);
+        // nothing is written after an uninitialized entry
+        if extension_type == u16::from(extension::ExtensionType::Uninitialized) {
+            break;
+        }
+        let length =
+            u16::from_le_bytes([tlv_data[type_start + 2], tlv_data[type_start + 3]]) as usize;
+        let value_start = type_start + 4;
+        let value = tlv_data
+            .get(value_start..value_start + length)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        entries.push((extension_type, value));
+        type_start = value_start + length;
+    }
+    Ok(entries)
+}
+
+fn get_extension_by_type<V: Pod>(tlv_data: &[u8], extension_type: u16) -> Result<Option<V>> {
+    let Some((_, value)) = get_tlv_entries(tlv_data)?
+        .into_iter()
+        .find(|(t, _)| *t == extension_type)
+    else {
+        return Ok(None);
+    };
+    let value = bytemuck::try_pod_read_unaligned::<V>(value)
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    Ok(Some(value))
+}
+
+fn get_extension<V: Extension + Pod>(tlv_data: &[u8]) -> Result<Option<V>> {
+    get_extension_by_type(tlv_data, u16::from(V::TYPE))
+}
 
 #[derive(Clone, Copy)]
 pub struct MemoTransferContext<'a, 'info> {
@@ -32,6 +127,8 @@ pub fn transfer_to_escrow2<'a, 'c: 'info, 'info>(
     amount: u64,
     transfer_hook_accounts: Option<&'c [AccountInfo<'info>]>,
 ) -> Result<()> {
+    require_mint_not_paused(token_mint)?;
+
     let mut instruction = spl_token_2022::instruction::transfer_checked(
         token_program.key,
         &sender_token.key(),
@@ -102,6 +199,8 @@ pub fn transfer_to_user2<'c: 'info, 'info>(
         }
     }
 
+    require_mint_not_paused(token_mint)?;
+
     let mut instruction = spl_token_2022::instruction::transfer_checked(
         token_program.key,
         &escrow_token.key(),
@@ -161,6 +260,8 @@ pub fn transfer_from_root_escrow<'c: 'info, 'info>(
     let root_escrow_state = root_escrow.load()?;
     let root_escrow_seeds = root_escrow_seeds!(root_escrow_state);
 
+    require_mint_not_paused(token_mint)?;
+
     let mut instruction = spl_token_2022::instruction::transfer_checked(
         token_program.key,
         &escrow_token.key(),
@@ -224,11 +325,16 @@ pub fn validate_mint(
     }
 
     let token_mint_data = token_mint_info.try_borrow_data()?;
-    let token_mint_unpacked =
-        StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&token_mint_data)?;
-
-    let extensions = token_mint_unpacked.get_extension_types()?;
-    for extension in extensions {
+    for (extension_type, _) in get_tlv_entries(get_tlv_data(&token_mint_data))? {
+        match extension_type {
+            // only changes the UI amount, see [get_ui_amount]
+            SCALED_UI_AMOUNT_EXTENSION_TYPE => continue,
+            // transfers fail with [LockerError::MintPaused] while the mint is paused
+            PAUSABLE_EXTENSION_TYPE => continue,
+            _ => {}
+        }
+        let extension = extension::ExtensionType::try_from(extension_type)
+            .map_err(|_| LockerError::UnsupportedMint)?;
         match extension {
             // supported
             extension::ExtensionType::TransferFeeConfig => {
@@ -236,6 +342,8 @@ pub fn validate_mint(
             }
             extension::ExtensionType::TokenMetadata => {}
             extension::ExtensionType::MetadataPointer => {}
+            // only changes the UI amount, see [get_ui_amount]
+            extension::ExtensionType::InterestBearingConfig => {}
             // partially supported
             extension::ExtensionType::ConfidentialTransferMint => {
                 // Supported, but non-confidential transfer only
@@ -251,9 +359,8 @@ pub fn validate_mint(
             extension::ExtensionType::DefaultAccountState => {}
             extension::ExtensionType::GroupMemberPointer => {}
             extension::ExtensionType::GroupPointer => {}
-            // Not stable yet to support
-            // extension::ExtensionType::TokenGroup => {}
-            // extension::ExtensionType::TokenGroupMember => {}
+            extension::ExtensionType::TokenGroup => {}
+            extension::ExtensionType::TokenGroupMember => {}
             // mint has unknown or unsupported extensions
             _ => {
                 return Err(LockerError::UnsupportedMint.into());
@@ -263,6 +370,62 @@ pub fn validate_mint(
 
     Ok(())
 }
+
+/// UI amount of `amount` for mints with an extension changing how amounts are displayed
+/// (InterestBearingConfig or ScaledUiAmount), returns None for other mints.
+pub fn get_ui_amount(
+    token_mint: &InterfaceAccount<Mint>,
+    amount: u64,
+    unix_timestamp: i64,
+) -> Result<Option<String>> {
+    let token_mint_info = token_mint.to_account_info();
+    if *token_mint_info.owner == Token::id() {
+        return Ok(None);
+    }
+
+    let token_mint_data = token_mint_info.try_borrow_data()?;
+    let tlv_data = get_tlv_data(&token_mint_data);
+    let decimals = token_mint.decimals;
+    if let Some(interest_bearing_config) =
+        get_extension::<extension::interest_bearing_mint::InterestBearingConfig>(tlv_data)?
+    {
+        let ui_amount = interest_bearing_config
+            .amount_to_ui_amount(amount, decimals, unix_timestamp)
+            .ok_or(LockerError::MathOverflow)?;
+        return Ok(Some(ui_amount));
+    }
+    if let Some(scaled_ui_amount_config) =
+        get_extension_by_type::<ScaledUiAmountConfig>(tlv_data, SCALED_UI_AMOUNT_EXTENSION_TYPE)?
+    {
+        let ui_amount =
+            scaled_ui_amount_config.amount_to_ui_amount(amount, decimals, unix_timestamp);
+        return Ok(Some(ui_amount));
+    }
+
+    Ok(None)
+}
+
+/// [EventTransferUiAmount] of `amount` paid by `escrow` to `destination`, returns None for mints
+/// without UI amount, see [get_ui_amount]
+pub fn get_transfer_ui_amount_event(
+    token_mint: &InterfaceAccount<Mint>,
+    escrow: Pubkey,
+    destination: Pubkey,
+    amount: u64,
+    current_ts: u64,
+) -> Result<Option<EventTransferUiAmount>> {
+    let event = get_ui_amount(token_mint, amount, current_ts as i64)?.map(|ui_amount| {
+        EventTransferUiAmount {
+            amount,
+            ui_amount,
+            current_ts,
+            escrow,
+            destination,
+        }
+    });
+    Ok(event)
+}
+
 fn get_epoch_transfer_fee<'info>(
     token_mint: &InterfaceAccount<'info, Mint>,
 ) -> Result<Option<TransferFee>> {
@@ -272,10 +435,8 @@ fn get_epoch_transfer_fee<'info>(
     }
 
     let token_mint_data = token_mint_info.try_borrow_data()?;
-    let token_mint_unpacked =
-        StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&token_mint_data)?;
-    if let Ok(transfer_fee_config) =
-        token_mint_unpacked.get_extension::<extension::transfer_fee::TransferFeeConfig>()
+    if let Some(transfer_fee_config) =
+        get_extension::<TransferFeeConfig>(get_tlv_data(&token_mint_data))?
     {
         let epoch = Clock::get()?.epoch;
         return Ok(Some(transfer_fee_config.get_epoch_fee(epoch).clone()));
@@ -330,6 +491,23 @@ pub fn calculate_transfer_fee_included_amount<'info>(
     Ok(transfer_fee_excluded_amount)
 }
 
+/// Amount received by the destination of a transfer of `transfer_fee_included_amount`
+pub fn calculate_transfer_fee_excluded_amount<'info>(
+    transfer_fee_included_amount: u64,
+    token_mint: &InterfaceAccount<'info, Mint>,
+) -> Result<u64> {
+    if let Some(epoch_transfer_fee) = get_epoch_transfer_fee(token_mint)? {
+        let transfer_fee = epoch_transfer_fee
+            .calculate_fee(transfer_fee_included_amount)
+            .ok_or(LockerError::MathOverflow)?;
+        return transfer_fee_included_amount
+            .checked_sub(transfer_fee)
+            .ok_or(LockerError::MathOverflow.into());
+    }
+
+    Ok(transfer_fee_included_amount)
+}
+
 pub fn harvest_fees<'c: 'info, 'info>(
     token_program_id: &Interface<'info, TokenInterface>,
     token_account: &AccountInfo<'info>,
@@ -341,10 +519,10 @@ pub fn harvest_fees<'c: 'info, 'info>(
     }
 
     let token_mint_data = mint_info.try_borrow_data()?;
-    let token_mint_unpacked =
-        StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&token_mint_data)?;
     let mut is_harvestable = false;
-    if let Ok(_transfer_fee_config) = token_mint_unpacked.get_extension::<TransferFeeConfig>() {
+    if let Some(_transfer_fee_config) =
+        get_extension::<TransferFeeConfig>(get_tlv_data(&token_mint_data))?
+    {
         is_harvestable = true;
     }
     // need to do this because Rust says we are still borrowing the data
@@ -374,18 +552,36 @@ pub fn is_transfer_memo_required(token_account: &InterfaceAccount<TokenAccount>)
     }
 
     let token_account_data = token_account_info.try_borrow_data()?;
-    let token_account_unpacked =
-        StateWithExtensions::<spl_token_2022::state::Account>::unpack(&token_account_data)?;
+    // token accounts of a Pausable mint have an extension unknown to spl-token-2022 v6
     let extension =
-        token_account_unpacked.get_extension::<extension::memo_transfer::MemoTransfer>();
+        get_extension::<extension::memo_transfer::MemoTransfer>(get_tlv_data(&token_account_data))?;
 
-    if let Ok(memo_transfer) = extension {
+    if let Some(memo_transfer) = extension {
         Ok(memo_transfer.require_incoming_transfer_memos.into())
     } else {
         Ok(false)
     }
 }
 
+/// Fails with [LockerError::MintPaused] if the mint has the Pausable extension and is paused,
+/// instead of the error of the token program on transfer
+fn require_mint_not_paused(token_mint: &InterfaceAccount<Mint>) -> Result<()> {
+    let token_mint_info = token_mint.to_account_info();
+    if *token_mint_info.owner == Token::id() {
+        return Ok(());
+    }
+
+    let token_mint_data = token_mint_info.try_borrow_data()?;
+    if let Some(pausable_config) = get_extension_by_type::<PausableConfig>(
+        get_tlv_data(&token_mint_data),
+        PAUSABLE_EXTENSION_TYPE,
+    )? {
+        require!(pausable_config.paused == 0, LockerError::MintPaused);
+    }
+
+    Ok(())
+}
+
 fn get_transfer_hook_program_id<'info>(
     token_mint: &InterfaceAccount<'info, Mint>,
 ) -> Result<Option<Pubkey>> {
@@ -395,9 +591,79 @@ fn get_transfer_hook_program_id<'info>(
     }
 
     let token_mint_data = token_mint_info.try_borrow_data()?;
-    let token_mint_unpacked =
-        StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&token_mint_data)?;
-    Ok(extension::transfer_hook::get_program_id(
-        &token_mint_unpacked,
-    ))
+    let transfer_hook =
+        get_extension::<extension::transfer_hook::TransferHook>(get_tlv_data(&token_mint_data))?;
+    Ok(transfer_hook.and_then(|transfer_hook| transfer_hook.program_id.into()))
+}
+
+#[cfg(test)]
+mod token2022_test {
+    use super::*;
+
+    fn push_tlv_entry(tlv_data: &mut Vec<u8>, extension_type: u16, value: &[u8]) {
+        tlv_data.extend_from_slice(&extension_type.to_le_bytes());
+        tlv_data.extend_from_slice(&(value.len() as u16).to_le_bytes());
+        tlv_data.extend_from_slice(value);
+    }
+
+    #[test]
+    fn test_get_extension_after_unknown_extension() {
+        let pausable_config = PausableConfig {
+            authority: [1; 32],
+            paused: 1,
+        };
+        let transfer_fee_config = TransferFeeConfig::default();
+        let mut tlv_data = vec![];
+        push_tlv_entry(
+            &mut tlv_data,
+            PAUSABLE_EXTENSION_TYPE,
+            bytemuck::bytes_of(&pausable_config),
+        );
+        push_tlv_entry(
+            &mut tlv_data,
+            u16::from(extension::ExtensionType::TransferFeeConfig),
+            bytemuck::bytes_of(&transfer_fee_config),
+        );
+        // uninitialized entry and a trailing byte
+        tlv_data.extend_from_slice(&[0; 5]);
+
+        let types: Vec<u16> = get_tlv_entries(&tlv_data)
+            .unwrap()
+            .iter()
+            .map(|(t, _)| *t)
+            .collect();
+        assert_eq!(types, vec![PAUSABLE_EXTENSION_TYPE, 1]);
+        assert!(get_extension::<TransferFeeConfig>(&tlv_data)
+            .unwrap()
+            .is_some());
+        assert_eq!(
+            get_extension_by_type::<PausableConfig>(&tlv_data, PAUSABLE_EXTENSION_TYPE)
+                .unwrap()
+                .unwrap()
+                .paused,
+            1
+        );
+        assert!(get_extension_by_type::<ScaledUiAmountConfig>(
+            &tlv_data,
+            SCALED_UI_AMOUNT_EXTENSION_TYPE
+        )
+        .unwrap()
+        .is_none());
+
+        // value longer than the data
+        assert!(get_tlv_entries(&tlv_data[..20]).is_err());
+    }
+
+    #[test]
+    fn test_scaled_ui_amount() {
+        let config = ScaledUiAmountConfig {
+            authority: [0; 32],
+            multiplier: 2f64.to_le_bytes(),
+            new_multiplier_effective_timestamp: 100i64.to_le_bytes(),
+            new_multiplier: 0.5f64.to_le_bytes(),
+        };
+        assert_eq!(config.amount_to_ui_amount(1_500_000, 6, 0), "3");
+        assert_eq!(config.amount_to_ui_amount(1_500_000, 6, 100), "0.75");
+        assert_eq!(config.amount_to_ui_amount(15, 0, 0), "30");
+    }
 }
@@ -0,0 +1,156 @@
+pub mod proof_store;
+
+use std::{
+    net::SocketAddr,
+    path::PathBuf,
+    str::FromStr,
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime},
+};
+
+use anchor_lang::solana_program::pubkey::Pubkey;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use clap::Parser;
+use serde::Deserialize;
+
+use crate::proof_store::ProofStore;
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+pub struct Args {
+    /// Merkle tree path, a json merkle tree or a binary proof file ending with .bin
+    #[clap(long, env)]
+    pub merkle_tree_path: PathBuf,
+
+    /// Address the server listens on
+    #[clap(long, env, default_value = "0.0.0.0:7001")]
+    pub bind_addr: SocketAddr,
+
+    /// Interval in seconds to check whether the merkle tree file changed and reload it
+    #[clap(long, env, default_value = "5")]
+    pub reload_interval: u64,
+}
+
+type SharedProofStore = Arc<RwLock<Arc<ProofStore>>>;
+
+#[derive(Debug, Deserialize)]
+struct ProofQuery {
+    mint: Option<String>,
+    leaf_index: Option<u64>,
+}
+
+enum ApiError {
+    InvalidRecipient(String),
+    InvalidMint(String),
+    ProofNotFound(Pubkey),
+    Internal(anyhow::Error),
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            ApiError::InvalidRecipient(recipient) => (
+                StatusCode::BAD_REQUEST,
+                format!("invalid recipient {}", recipient),
+            ),
+            ApiError::InvalidMint(mint) => {
+                (StatusCode::BAD_REQUEST, format!("invalid mint {}", mint))
+            }
+            ApiError::ProofNotFound(recipient) => (
+                StatusCode::NOT_FOUND,
+                format!("proof not found for {}", recipient),
+            ),
+            ApiError::Internal(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+        };
+        (status, message).into_response()
+    }
+}
+
+fn get_store(state: &SharedProofStore) -> Arc<ProofStore> {
+    state.read().unwrap().clone()
+}
+
+async fn get_root(State(state): State<SharedProofStore>) -> impl IntoResponse {
+    Json(get_store(&state).get_root())
+}
+
+async fn get_proof(
+    State(state): State<SharedProofStore>,
+    Path(recipient): Path<String>,
+    Query(query): Query<ProofQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let recipient =
+        Pubkey::from_str(&recipient).map_err(|_| ApiError::InvalidRecipient(recipient))?;
+    let mint = query
+        .mint
+        .map(|mint| Pubkey::from_str(&mint).map_err(|_| ApiError::InvalidMint(mint)))
+        .transpose()?;
+    let store = get_store(&state);
+    let proof =
+        tokio::task::spawn_blocking(move || store.get_proof(&recipient, mint, query.leaf_index))
+            .await
+            .map_err(|err| ApiError::Internal(err.into()))?
+            .map_err(ApiError::Internal)?
+            .ok_or(ApiError::ProofNotFound(recipient))?;
+    Ok(Json(proof))
+}
+
+fn get_modified(path: &PathBuf) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Reload the proof store when the merkle tree file changes, keep serving the previous one if
+/// the new file can't be loaded
+async fn reload_on_change(state: SharedProofStore, path: PathBuf, interval: Duration) {
+    let mut modified = get_modified(&path);
+    loop {
+        tokio::time::sleep(interval).await;
+        let new_modified = get_modified(&path);
+        if new_modified == modified {
+            continue;
+        }
+        modified = new_modified;
+
+        let load_path = path.clone();
+        match tokio::task::spawn_blocking(move || ProofStore::load(&load_path)).await {
+            Ok(Ok(store)) => {
+                println!("reloaded merkle tree {:?}", store.get_root());
+                *state.write().unwrap() = Arc::new(store);
+            }
+            Ok(Err(err)) => println!("failed to reload merkle tree: {}", err),
+            Err(err) => println!("failed to reload merkle tree: {}", err),
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+
+    let store = ProofStore::load(&args.merkle_tree_path)?;
+    println!("loaded merkle tree {:?}", store.get_root());
+    let state: SharedProofStore = Arc::new(RwLock::new(Arc::new(store)));
+
+    tokio::spawn(reload_on_change(
+        state.clone(),
+        args.merkle_tree_path.clone(),
+        Duration::from_secs(args.reload_interval),
+    ));
+
+    let app = Router::new()
+        .route("/root", get(get_root))
+        .route("/proof/:recipient", get(get_proof))
+        .with_state(state);
+
+    println!("listening on {}", args.bind_addr);
+    axum::Server::bind(&args.bind_addr)
+        .serve(app.into_make_service())
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use std::{collections::HashMap, path::Path};
+
+use anchor_lang::solana_program::{hash::Hash, pubkey::Pubkey};
+use merkle_tree::{
+    binary_proof_file::BinaryProofFile,
+    jup_lock_merkle_tree::{JupLockMerkleTree, UserProof},
+    streaming_merkle_tree::MerkleTreeMetadata,
+    tree_node::TreeNode,
+};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RootResponse {
+    #[serde(flatten)]
+    pub metadata: MerkleTreeMetadata,
+    /// base58 merkle root, set as `merkle_tree` of user proofs
+    pub merkle_tree: String,
+}
+
+enum ProofSource {
+    Tree(HashMap<(Pubkey, Option<Pubkey>, Option<u64>), TreeNode>),
+    Binary(BinaryProofFile),
+}
+
+/// Proofs of a merkle tree, loaded from a json merkle tree or a binary proof file (`.bin`)
+pub struct ProofStore {
+    metadata: MerkleTreeMetadata,
+    source: ProofSource,
+}
+
+impl ProofStore {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        if path.extension().is_some_and(|ext| ext == "bin") {
+            let file = BinaryProofFile::open(path)?;
+            Ok(Self {
+                metadata: file.header().metadata.clone(),
+                source: ProofSource::Binary(file),
+            })
+        } else {
+            let tree = JupLockMerkleTree::new_from_file(&path.to_path_buf())?;
+            Ok(Self {
+                metadata: tree.get_metadata(),
+                source: ProofSource::Tree(tree.convert_to_hashmap()),
+            })
+        }
+    }
+
+    pub fn get_root(&self) -> RootResponse {
+        RootResponse {
+            metadata: self.metadata.clone(),
+            merkle_tree: self.get_merkle_tree(),
+        }
+    }
+
+    fn get_merkle_tree(&self) -> String {
+        Hash::new_from_array(self.metadata.merkle_root).to_string()
+    }
+
+    /// Proof of a recipient leaf, `mint` selects the leaf in trees with mints and is ignored in
+    /// other trees, `leaf_index` defaults to the first leaf of the recipient in trees with leaf
+    /// indexes. Lookups in a binary proof file read from disk, so async callers should run them
+    /// on a blocking thread
+    pub fn get_proof(
+        &self,
+        recipient: &Pubkey,
+        mint: Option<Pubkey>,
+        leaf_index: Option<u64>,
+    ) -> anyhow::Result<Option<UserProof>> {
+        let mint = mint.filter(|_| self.metadata.leaf_version & 1 != 0);
+        let leaf_index = if self.metadata.leaf_version & 2 != 0 {
+            Some(leaf_index.unwrap_or_default())
+        } else {
+            leaf_index
+        };
+        let tree_node = match &self.source {
+            ProofSource::Tree(tree_nodes) => {
+                tree_nodes.get(&(*recipient, mint, leaf_index)).cloned()
+            }
+            ProofSource::Binary(file) => file.get_node(recipient, mint, leaf_index)?,
+        };
+        Ok(tree_node.map(|tree_node| UserProof::new(&tree_node, self.get_merkle_tree())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use merkle_tree::test_utils::{new_tree_node, TempDir};
+
+    #[test]
+    fn test_json_and_binary_store() {
+        let recipient = Pubkey::new_unique();
+        let tree = JupLockMerkleTree::new(
+            vec![
+                new_tree_node(recipient, None, 100),
+                new_tree_node(Pubkey::new_unique(), None, 200),
+                new_tree_node(recipient, None, 300),
+            ],
+            0,
+        )
+        .unwrap();
+        let dir = TempDir::new("proof_store");
+        let json_path = dir.join("merkle_tree.json");
+        let binary_path = dir.join("merkle_tree.bin");
+        tree.write_to_file(&json_path);
+        tree.write_to_binary_file(&binary_path).unwrap();
+
+        for path in [json_path, binary_path] {
+            let store = ProofStore::load(&path).unwrap();
+            assert_eq!(store.get_root().metadata, tree.get_metadata());
+
+            let proof = store.get_proof(&recipient, None, None).unwrap().unwrap();
+            assert_eq!(proof.cliff_unlock_amount, 100);
+            assert_eq!(proof.proof, tree.tree_nodes[0].proof.clone().unwrap());
+            let proof = store.get_proof(&recipient, None, Some(1)).unwrap().unwrap();
+            assert_eq!(proof.cliff_unlock_amount, 300);
+            assert!(store
+                .get_proof(&recipient, None, Some(2))
+                .unwrap()
+                .is_none());
+            assert!(store
+                .get_proof(&Pubkey::new_unique(), None, None)
+                .unwrap()
+                .is_none());
+        }
+    }
+
+    #[test]
+    fn test_multi_mint_store() {
+        let recipient = Pubkey::new_unique();
+        let first_mint = Pubkey::new_unique();
+        let second_mint = Pubkey::new_unique();
+        let tree = JupLockMerkleTree::new(
+            vec![
+                new_tree_node(recipient, Some(first_mint), 100),
+                new_tree_node(recipient, Some(second_mint), 200),
+            ],
+            0,
+        )
+        .unwrap();
+        let dir = TempDir::new("proof_store");
+        let json_path = dir.join("merkle_tree.json");
+        let binary_path = dir.join("merkle_tree.bin");
+        tree.write_to_file(&json_path);
+        tree.write_to_binary_file(&binary_path).unwrap();
+
+        for path in [json_path, binary_path] {
+            let store = ProofStore::load(&path).unwrap();
+            let proof = store
+                .get_proof(&recipient, Some(second_mint), None)
+                .unwrap()
+                .unwrap();
+            assert_eq!(proof.cliff_unlock_amount, 200);
+            assert_eq!(proof.mint, Some(second_mint));
+            assert!(store.get_proof(&recipient, None, None).unwrap().is_none());
+        }
+    }
+}
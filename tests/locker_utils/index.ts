@@ -3,6 +3,7 @@ import {
   BN,
   Program,
   setProvider,
+  utils,
   Wallet,
   web3,
   workspace,
@@ -52,6 +53,36 @@ export function createLockerProgram(wallet?: Wallet): Program<Locker> {
   return workspace.Locker as Program<Locker>;
 }
 
+// Events emitted with emit_cpi! by the transaction, decoded from the inner instructions invoking
+// the locker program
+export async function getCpiEvents(
+  program: Program<Locker>,
+  signature: string
+) {
+  const tx = await program.provider.connection.getTransaction(signature, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  const accountKeys = tx.transaction.message.staticAccountKeys;
+  const events = [];
+  for (const innerInstructions of tx.meta.innerInstructions ?? []) {
+    for (const ix of innerInstructions.instructions) {
+      if (!accountKeys[ix.programIdIndex].equals(program.programId)) {
+        continue;
+      }
+      // event data follows the 8 bytes of the event instruction tag
+      const data = utils.bytes.bs58.decode(ix.data);
+      const event = program.coder.events.decode(
+        Buffer.from(data.subarray(8)).toString("base64")
+      );
+      if (event) {
+        events.push(event);
+      }
+    }
+  }
+  return events;
+}
+
 export function deriveEscrow(base: web3.PublicKey, programId: web3.PublicKey) {
   return web3.PublicKey.findProgramAddressSync(
     [Buffer.from("escrow"), base.toBuffer()],
@@ -71,17 +102,86 @@ export function deriveRootEscrow(
   return pk
 }
 
+export function deriveMultiMintRootEscrow(
+  base: web3.PublicKey,
+  version: number,
+) {
+  return deriveRootEscrow(base, web3.PublicKey.default, version);
+}
+
+export function deriveRootEscrowMint(
+  rootEscrow: web3.PublicKey,
+  mint: web3.PublicKey,
+) {
+  let [pk, _] = web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("root_escrow_mint"), rootEscrow.toBuffer(), mint.toBuffer()],
+    LOCKER_PROGRAM_ID
+  );
+  return pk
+}
+
+// mint is only part of the seeds for escrows of multi mint root escrows
 export function deriveBase(
   rootEscrow: web3.PublicKey,
   recipient: web3.PublicKey,
+  leafIndex?: BN,
+  mint?: web3.PublicKey,
+) {
+  let [pk, _] = web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("base"),
+      rootEscrow.toBuffer(),
+      recipient.toBuffer(),
+      leafIndex ? leafIndex.toArrayLike(Buffer, "le", 8) : Buffer.alloc(0),
+      mint ? mint.toBuffer() : Buffer.alloc(0),
+    ],
+    LOCKER_PROGRAM_ID
+  );
+  return pk
+}
+
+export function deriveRootEscrowClaimStatus(
+  rootEscrow: web3.PublicKey,
+  recipient: web3.PublicKey,
+  leafIndex?: BN,
+) {
+  let [pk, _] = web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("claim_status"),
+      rootEscrow.toBuffer(),
+      recipient.toBuffer(),
+      leafIndex ? leafIndex.toArrayLike(Buffer, "le", 8) : Buffer.alloc(0),
+    ],
+    LOCKER_PROGRAM_ID
+  );
+  return pk
+}
+
+export function deriveRootEscrowClaimBitmap(
+  rootEscrow: web3.PublicKey,
+  chunkIndex: BN,
 ) {
   let [pk, _] = web3.PublicKey.findProgramAddressSync(
-    [Buffer.from("base"), rootEscrow.toBuffer(), recipient.toBuffer()],
+    [
+      Buffer.from("claim_bitmap"),
+      rootEscrow.toBuffer(),
+      chunkIndex.toArrayLike(Buffer, "le", 8),
+    ],
     LOCKER_PROGRAM_ID
   );
   return pk
 }
 
+export function deriveEscrowRecipients(
+  escrow: web3.PublicKey,
+  programId: web3.PublicKey
+) {
+  return web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("escrow_recipients"), escrow.toBuffer()],
+    programId
+  );
+}
+
 export function deriveEscrowMetadata(
   escrow: web3.PublicKey,
   programId: web3.PublicKey
@@ -523,6 +623,232 @@ export async function claimTokenV2(params: ClaimTokenParamsV2) {
     .remainingAccounts(remainingAccounts ? remainingAccounts : [])
     .signers([recipient])
     .rpc();
+  return tx;
+}
+
+// Multi recipient instructions
+export interface RecipientShare {
+  recipient: web3.PublicKey;
+  shareBps: number;
+}
+
+export interface CreateMultiRecipientVestingPlanParams {
+  ownerKeypair: web3.Keypair;
+  tokenMint: web3.PublicKey;
+  isAssertion: boolean;
+  vestingStartTime: BN;
+  cliffTime: BN;
+  frequency: BN;
+  cliffUnlockAmount: BN;
+  amountPerPeriod: BN;
+  numberOfPeriod: BN;
+  recipients: RecipientShare[];
+  tokenProgram: web3.PublicKey;
+}
+
+export async function createMultiRecipientVestingPlan(
+  params: CreateMultiRecipientVestingPlanParams
+) {
+  let {
+    isAssertion,
+    tokenMint,
+    ownerKeypair,
+    vestingStartTime,
+    cliffTime,
+    frequency,
+    cliffUnlockAmount,
+    amountPerPeriod,
+    numberOfPeriod,
+    recipients,
+    tokenProgram,
+  } = params;
+  const program = createLockerProgram(new Wallet(ownerKeypair));
+
+  const baseKP = web3.Keypair.generate();
+
+  let [escrow] = deriveEscrow(baseKP.publicKey, program.programId);
+  let [escrowRecipients] = deriveEscrowRecipients(escrow, program.programId);
+
+  const senderToken = getAssociatedTokenAddressSync(
+    tokenMint,
+    ownerKeypair.publicKey,
+    false,
+    tokenProgram,
+    ASSOCIATED_TOKEN_PROGRAM_ID
+  );
+
+  const escrowToken = getAssociatedTokenAddressSync(
+    tokenMint,
+    escrow,
+    true,
+    tokenProgram,
+    ASSOCIATED_TOKEN_PROGRAM_ID
+  );
+
+  let remainingAccountsInfo = null;
+  let remainingAccounts: AccountMeta[] = [];
+  if (tokenProgram == TOKEN_2022_PROGRAM_ID) {
+    let inputTransferHookAccounts =
+      await TokenExtensionUtil.getExtraAccountMetasForTransferHook(
+        program.provider.connection,
+        tokenMint,
+        senderToken,
+        escrowToken,
+        ownerKeypair.publicKey,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+    [remainingAccountsInfo, remainingAccounts] = new RemainingAccountsBuilder()
+      .addSlice(
+        RemainingAccountsType.TransferHookEscrow,
+        inputTransferHookAccounts
+      )
+      .build();
+  }
+
+  await program.methods
+    .createMultiRecipientVestingEscrow(
+      {
+        vestingStartTime,
+        cliffTime,
+        frequency,
+        cliffUnlockAmount,
+        amountPerPeriod,
+        numberOfPeriod,
+        updateRecipientMode: 0,
+        cancelMode: 0,
+      },
+      recipients,
+      remainingAccountsInfo
+    )
+    .accounts({
+      base: baseKP.publicKey,
+      escrow,
+      escrowRecipients,
+      tokenMint,
+      escrowToken,
+      sender: ownerKeypair.publicKey,
+      senderToken,
+      tokenProgram,
+      systemProgram: web3.SystemProgram.programId,
+    })
+    .remainingAccounts(remainingAccounts ? remainingAccounts : [])
+    .preInstructions([
+      createAssociatedTokenAccountInstruction(
+        ownerKeypair.publicKey,
+        escrowToken,
+        escrow,
+        tokenMint,
+        tokenProgram,
+        ASSOCIATED_TOKEN_PROGRAM_ID
+      ),
+    ])
+    .signers([baseKP, ownerKeypair])
+    .rpc();
+
+  if (isAssertion) {
+    const escrowState = await program.account.vestingEscrow.fetch(escrow);
+    expect(escrowState.recipient.toString()).eq(escrowRecipients.toString());
+    expect(escrowState.recipientFlag).eq(1);
+    const escrowRecipientsState =
+      await program.account.vestingEscrowRecipients.fetch(escrowRecipients);
+    expect(escrowRecipientsState.escrow.toString()).eq(escrow.toString());
+    expect(escrowRecipientsState.numRecipients).eq(recipients.length);
+    recipients.forEach((recipient, i) => {
+      expect(escrowRecipientsState.recipients[i].recipient.toString()).eq(
+        recipient.recipient.toString()
+      );
+      expect(escrowRecipientsState.recipients[i].shareBps).eq(
+        recipient.shareBps
+      );
+    });
+  }
+
+  return escrow;
+}
+
+export interface ClaimMultiRecipientParams {
+  isAssertion: boolean;
+  escrow: web3.PublicKey;
+  recipient: web3.Keypair;
+  maxAmount: BN;
+  recipientToken: web3.PublicKey;
+}
+
+export async function claimMultiRecipient(params: ClaimMultiRecipientParams) {
+  let { isAssertion, escrow, recipient, maxAmount, recipientToken } = params;
+  const program = createLockerProgram(new Wallet(recipient));
+  const escrowState = await program.account.vestingEscrow.fetch(escrow);
+  const tokenProgram =
+    escrowState.tokenProgramFlag == ESCROW_USE_SPL_TOKEN
+      ? TOKEN_PROGRAM_ID
+      : TOKEN_2022_PROGRAM_ID;
+
+  const escrowToken = getAssociatedTokenAddressSync(
+    escrowState.tokenMint,
+    escrow,
+    true,
+    tokenProgram,
+    ASSOCIATED_TOKEN_PROGRAM_ID
+  );
+
+  // the escrow recipients account tracks the claimed amount of each recipient
+  const remainingAccountsBuilder = new RemainingAccountsBuilder().addSlice(
+    RemainingAccountsType.EscrowRecipients,
+    [{ pubkey: escrowState.recipient, isSigner: false, isWritable: true }]
+  );
+  if (tokenProgram == TOKEN_2022_PROGRAM_ID) {
+    let claimTransferHookAccounts =
+      await TokenExtensionUtil.getExtraAccountMetasForTransferHook(
+        program.provider.connection,
+        escrowState.tokenMint,
+        escrowToken,
+        recipientToken,
+        escrow,
+        TOKEN_2022_PROGRAM_ID
+      );
+    remainingAccountsBuilder.addSlice(
+      RemainingAccountsType.TransferHookEscrow,
+      claimTransferHookAccounts
+    );
+  }
+  const [remainingAccountsInfo, remainingAccounts] =
+    remainingAccountsBuilder.build();
+
+  const recipientTokenBalanceBefore = await getTokenBalance(
+    program.provider.connection,
+    recipientToken
+  );
+
+  await program.methods
+    .claimV2(maxAmount, remainingAccountsInfo)
+    .accounts({
+      escrow,
+      tokenMint: escrowState.tokenMint,
+      escrowToken,
+      recipient: recipient.publicKey,
+      recipientToken,
+      memoProgram: MEMO_PROGRAM,
+      tokenProgram,
+    })
+    .remainingAccounts(remainingAccounts ? remainingAccounts : [])
+    .signers([recipient])
+    .rpc();
+
+  if (isAssertion) {
+    const escrowRecipientsState =
+      await program.account.vestingEscrowRecipients.fetch(escrowState.recipient);
+    const escrowRecipient = escrowRecipientsState.recipients.find(
+      (r) => r.recipient.toString() == recipient.publicKey.toString()
+    );
+    const recipientTokenBalance = await getTokenBalance(
+      program.provider.connection,
+      recipientToken
+    );
+    expect(escrowRecipient.totalClaimedAmount.toNumber()).gte(
+      recipientTokenBalance - recipientTokenBalanceBefore
+    );
+  }
 }
 
 // Root escrow instructions
@@ -535,6 +861,9 @@ export interface CreateRootEscrowParams {
   version: BN,
   root: Buffer;
   tokenProgram: web3.PublicKey;
+  clawbackDeadline?: BN;
+  leafVersion?: number;
+  distributionMode?: number;
 }
 
 export async function createRootEscrow(params: CreateRootEscrowParams) {
@@ -547,6 +876,9 @@ export async function createRootEscrow(params: CreateRootEscrowParams) {
     version,
     root,
     tokenProgram,
+    clawbackDeadline,
+    leafVersion,
+    distributionMode,
   } = params;
   const program = createLockerProgram(new Wallet(ownerKeypair));
 
@@ -561,6 +893,9 @@ export async function createRootEscrow(params: CreateRootEscrowParams) {
         maxEscrow,
         version,
         root: Array.from(new Uint8Array(root)),
+        clawbackDeadline: clawbackDeadline ?? new BN(0),
+        leafVersion: leafVersion ?? 0,
+        distributionMode: distributionMode ?? 0,
       },
     )
     .accounts({
@@ -596,63 +931,889 @@ export async function createRootEscrow(params: CreateRootEscrowParams) {
   return rootEscrow;
 }
 
-
-export interface FundRootEscrowParams {
-  isAssertion: boolean;
-  rootEscrow: web3.PublicKey,
-  payerKP: web3.Keypair;
+export interface CreateMultiMintRootEscrowParams {
+  ownerKeypair: web3.Keypair;
+  maxEscrow: BN,
+  version: BN,
+  root: Buffer;
+  clawbackDeadline?: BN;
+  leafVersion: number;
 }
 
-export async function fundRootEscrow(params: FundRootEscrowParams) {
-  let { isAssertion, rootEscrow, payerKP } = params;
-  const program = createLockerProgram(new Wallet(payerKP));
-  let rootEscrowState = await program.account.rootEscrow.fetch(rootEscrow);
-  let tokenProgram = rootEscrowState.tokenProgramFlag == 0 ? TOKEN_PROGRAM_ID : TOKEN_2022_PROGRAM_ID;
-  const payerToken = getAssociatedTokenAddressSync(
-    rootEscrowState.tokenMint,
-    payerKP.publicKey,
-    false,
-    tokenProgram,
-    ASSOCIATED_TOKEN_PROGRAM_ID
-  );
-  const rootEscrowToken = getAssociatedTokenAddressSync(
-    rootEscrowState.tokenMint,
-    rootEscrow,
-    true,
-    tokenProgram,
-    ASSOCIATED_TOKEN_PROGRAM_ID
-  );
+export async function createMultiMintRootEscrow(params: CreateMultiMintRootEscrowParams) {
+  let { ownerKeypair, maxEscrow, version, root, clawbackDeadline, leafVersion } = params;
+  const program = createLockerProgram(new Wallet(ownerKeypair));
 
-  let remainingAccountsInfo = null;
-  let remainingAccounts: AccountMeta[] = [];
-  if (rootEscrowState.tokenProgramFlag == 1) {
-    let inputTransferHookAccounts =
-      await TokenExtensionUtil.getExtraAccountMetasForTransferHook(
-        program.provider.connection,
+  const baseKP = web3.Keypair.generate();
+
+  let rootEscrow = deriveMultiMintRootEscrow(baseKP.publicKey, version.toNumber());
+
+  await program.methods
+    .createMultiMintRootEscrow({
+      maxEscrow,
+      version,
+      root: Array.from(new Uint8Array(root)),
+      clawbackDeadline: clawbackDeadline ?? new BN(0),
+      leafVersion,
+    })
+    .accounts({
+      base: baseKP.publicKey,
+      rootEscrow,
+      payer: ownerKeypair.publicKey,
+      creator: ownerKeypair.publicKey,
+      systemProgram: web3.SystemProgram.programId,
+    })
+    .signers([baseKP, ownerKeypair])
+    .rpc();
+
+  const rootEscrowState = await program.account.rootEscrow.fetch(rootEscrow);
+  expect(rootEscrowState.tokenMint.toString()).eq(web3.PublicKey.default.toString());
+  expect(rootEscrowState.maxEscrow.toString()).eq(maxEscrow.toString());
+
+  return rootEscrow;
+}
+
+export interface CreateRootEscrowMintParams {
+  rootEscrow: web3.PublicKey;
+  creator: web3.Keypair;
+  tokenMint: web3.PublicKey;
+  maxClaimAmount: BN;
+}
+
+export async function createRootEscrowMint(params: CreateRootEscrowMintParams) {
+  let { rootEscrow, creator, tokenMint, maxClaimAmount } = params;
+  const program = createLockerProgram(new Wallet(creator));
+  const rootEscrowMint = deriveRootEscrowMint(rootEscrow, tokenMint);
+
+  await program.methods
+    .createRootEscrowMint(maxClaimAmount)
+    .accounts({
+      rootEscrow,
+      rootEscrowMint,
+      tokenMint,
+      creator: creator.publicKey,
+      systemProgram: web3.SystemProgram.programId,
+    })
+    .signers([creator])
+    .rpc();
+
+  const rootEscrowMintState = await program.account.rootEscrowMint.fetch(
+    rootEscrowMint
+  );
+  expect(rootEscrowMintState.rootEscrow.toString()).eq(rootEscrow.toString());
+  expect(rootEscrowMintState.tokenMint.toString()).eq(tokenMint.toString());
+  expect(rootEscrowMintState.maxClaimAmount.toString()).eq(
+    maxClaimAmount.toString()
+  );
+
+  return rootEscrowMint;
+}
+
+// Mint, root escrow mint and token program of root escrow, tokenMint selects the mint of a multi
+// mint root escrow
+async function getRootEscrowMint(
+  program: Program<Locker>,
+  rootEscrow: web3.PublicKey,
+  tokenMint?: web3.PublicKey,
+) {
+  const rootEscrowState = await program.account.rootEscrow.fetch(rootEscrow);
+  if (!rootEscrowState.tokenMint.equals(web3.PublicKey.default)) {
+    return {
+      tokenMint: rootEscrowState.tokenMint,
+      rootEscrowMint: null,
+      tokenProgramFlag: rootEscrowState.tokenProgramFlag,
+      maxClaimAmount: rootEscrowState.maxClaimAmount,
+    };
+  }
+  const rootEscrowMint = deriveRootEscrowMint(rootEscrow, tokenMint);
+  const rootEscrowMintState = await program.account.rootEscrowMint.fetch(
+    rootEscrowMint
+  );
+  return {
+    tokenMint,
+    rootEscrowMint,
+    tokenProgramFlag: rootEscrowMintState.tokenProgramFlag,
+    maxClaimAmount: rootEscrowMintState.maxClaimAmount,
+  };
+}
+
+
+export interface FundRootEscrowParams {
+  isAssertion: boolean;
+  rootEscrow: web3.PublicKey,
+  payerKP: web3.Keypair;
+  tokenMint?: web3.PublicKey;
+  rentDeposit?: BN;
+}
+
+export async function fundRootEscrow(params: FundRootEscrowParams) {
+  let { isAssertion, rootEscrow, payerKP, rentDeposit } = params;
+  const program = createLockerProgram(new Wallet(payerKP));
+  let { tokenMint, rootEscrowMint, tokenProgramFlag, maxClaimAmount } =
+    await getRootEscrowMint(program, rootEscrow, params.tokenMint);
+  let rootEscrowState = { tokenMint, tokenProgramFlag };
+  let tokenProgram = rootEscrowState.tokenProgramFlag == 0 ? TOKEN_PROGRAM_ID : TOKEN_2022_PROGRAM_ID;
+  const payerToken = getAssociatedTokenAddressSync(
+    rootEscrowState.tokenMint,
+    payerKP.publicKey,
+    false,
+    tokenProgram,
+    ASSOCIATED_TOKEN_PROGRAM_ID
+  );
+  const rootEscrowToken = getAssociatedTokenAddressSync(
+    rootEscrowState.tokenMint,
+    rootEscrow,
+    true,
+    tokenProgram,
+    ASSOCIATED_TOKEN_PROGRAM_ID
+  );
+
+  let remainingAccountsInfo = null;
+  let remainingAccounts: AccountMeta[] = [];
+  if (rootEscrowState.tokenProgramFlag == 1) {
+    let inputTransferHookAccounts =
+      await TokenExtensionUtil.getExtraAccountMetasForTransferHook(
+        program.provider.connection,
+        rootEscrowState.tokenMint,
+        payerToken,
+        rootEscrowToken,
+        payerKP.publicKey,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+    [remainingAccountsInfo, remainingAccounts] = new RemainingAccountsBuilder()
+      .addSlice(
+        RemainingAccountsType.TransferHookEscrow,
+        inputTransferHookAccounts
+      )
+      .build();
+  }
+  const accounts = {
+    rootEscrow,
+    tokenMint: rootEscrowState.tokenMint,
+    rootEscrowToken,
+    payer: payerKP.publicKey,
+    payerToken,
+    tokenProgram,
+    systemProgram: web3.SystemProgram.programId,
+    associateTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+  };
+  const builder = rootEscrowMint
+    ? program.methods
+      .fundMultiMintRootEscrow(maxClaimAmount, remainingAccountsInfo)
+      .accounts({ ...accounts, rootEscrowMint })
+    : rentDeposit
+      ? program.methods
+        .fundRootEscrowV2(maxClaimAmount, rentDeposit, remainingAccountsInfo)
+        .accounts(accounts)
+      : program.methods
+        .fundRootEscrow(maxClaimAmount, remainingAccountsInfo)
+        .accounts(accounts);
+  await builder.remainingAccounts(remainingAccounts ? remainingAccounts : []).signers([payerKP]).rpc().catch(console.log).then(console.log);
+}
+
+export interface UpdateRootEscrowRootParams {
+  isAssertion: boolean;
+  rootEscrow: web3.PublicKey;
+  creator: web3.Keypair;
+  root: Buffer;
+  maxClaimAmount: BN;
+  maxEscrow: BN;
+  rootEscrowMint?: web3.PublicKey;
+}
+
+export async function updateRootEscrowRoot(params: UpdateRootEscrowRootParams) {
+  let { isAssertion, rootEscrow, creator, root, maxClaimAmount, maxEscrow, rootEscrowMint } =
+    params;
+  const program = createLockerProgram(new Wallet(creator));
+
+  const updateParams = {
+    root: Array.from(new Uint8Array(root)),
+    maxClaimAmount,
+    maxEscrow,
+  };
+  const builder = rootEscrowMint
+    ? program.methods.updateMultiMintRootEscrowRoot(updateParams).accounts({
+      rootEscrow,
+      rootEscrowMint,
+      creator: creator.publicKey,
+    })
+    : program.methods.updateRootEscrowRoot(updateParams).accounts({
+      rootEscrow,
+      creator: creator.publicKey,
+    });
+  await builder.signers([creator]).rpc();
+
+  if (isAssertion) {
+    const rootEscrowState = rootEscrowMint
+      ? await program.account.rootEscrowMint.fetch(rootEscrowMint)
+      : await program.account.rootEscrow.fetch(rootEscrow);
+    expect(rootEscrowState.maxClaimAmount.toString()).eq(
+      maxClaimAmount.toString()
+    );
+  }
+  if (isAssertion) {
+    const rootEscrowState = await program.account.rootEscrow.fetch(rootEscrow);
+    expect(Buffer.from(rootEscrowState.root).toString("hex")).eq(
+      root.toString("hex")
+    );
+    expect(rootEscrowState.maxEscrow.toString()).eq(maxEscrow.toString());
+  }
+}
+
+export interface ClaimFromRootEscrowParams {
+  isAssertion: boolean;
+  rootEscrow: web3.PublicKey;
+  vestingStartTime: BN;
+  cliffTime: BN;
+  frequency: BN;
+  cliffUnlockAmount: BN;
+  amountPerPeriod: BN;
+  numberOfPeriod: BN;
+  updateRecipientMode: number,
+  cancelMode: number,
+  recipient: web3.Keypair;
+  proof: Array<number>[];
+  maxAmount: BN;
+  leafIndex?: BN;
+}
+
+export async function claimFromRootEscrow(params: ClaimFromRootEscrowParams) {
+  let {
+    isAssertion,
+    rootEscrow,
+    frequency,
+    vestingStartTime,
+    recipient,
+    cliffTime,
+    amountPerPeriod,
+    proof,
+    numberOfPeriod,
+    cliffUnlockAmount,
+    updateRecipientMode,
+    cancelMode,
+    maxAmount,
+    leafIndex,
+  } = params;
+  const program = createLockerProgram(new Wallet(recipient));
+  const rootEscrowState = await program.account.rootEscrow.fetch(rootEscrow);
+  const claimStatus = deriveRootEscrowClaimStatus(
+    rootEscrow,
+    recipient.publicKey,
+    leafIndex
+  );
+
+  const tokenProgram =
+    rootEscrowState.tokenProgramFlag == ESCROW_USE_SPL_TOKEN
+      ? TOKEN_PROGRAM_ID
+      : TOKEN_2022_PROGRAM_ID;
+
+  const rootEscrowToken = getAssociatedTokenAddressSync(
+    rootEscrowState.tokenMint,
+    rootEscrow,
+    true,
+    tokenProgram,
+    ASSOCIATED_TOKEN_PROGRAM_ID
+  );
+  const recipientToken = (
+    await getOrCreateAssociatedTokenAccount(
+      program.provider.connection,
+      recipient,
+      rootEscrowState.tokenMint,
+      recipient.publicKey,
+      false,
+      "confirmed",
+      {
+        commitment: "confirmed",
+      },
+      tokenProgram
+    )
+  ).address;
+
+  let remainingAccountsInfo = null;
+  let remainingAccounts: AccountMeta[] = [];
+  if (rootEscrowState.tokenProgramFlag == 1) {
+    let inputTransferHookAccounts =
+      await TokenExtensionUtil.getExtraAccountMetasForTransferHook(
+        program.provider.connection,
+        rootEscrowState.tokenMint,
+        rootEscrowToken,
+        recipientToken,
+        rootEscrow,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+    [remainingAccountsInfo, remainingAccounts] = new RemainingAccountsBuilder()
+      .addSlice(
+        RemainingAccountsType.TransferHookEscrow,
+        inputTransferHookAccounts
+      )
+      .build();
+  }
+
+  const claimStatusBefore = await program.account.rootEscrowClaimStatus.fetchNullable(
+    claimStatus
+  );
+  const recipientTokenBefore = await getTokenBalance(
+    program.provider.connection,
+    recipientToken
+  );
+
+  await program.methods
+    .claimFromRootEscrow({
+      vestingStartTime,
+      cliffTime,
+      frequency,
+      amountPerPeriod,
+      numberOfPeriod,
+      cliffUnlockAmount,
+      updateRecipientMode,
+      cancelMode,
+      leafIndex: leafIndex ?? null,
+    }, proof, maxAmount, remainingAccountsInfo)
+    .accounts({
+      rootEscrow,
+      claimStatus,
+      tokenMint: rootEscrowState.tokenMint,
+      rootEscrowToken,
+      recipient: recipient.publicKey,
+      recipientToken,
+      systemProgram: web3.SystemProgram.programId,
+      tokenProgram,
+    }).remainingAccounts(remainingAccounts ? remainingAccounts : []).signers([recipient])
+    .rpc();
+
+  if (isAssertion) {
+    const claimStatusState = await program.account.rootEscrowClaimStatus.fetch(claimStatus);
+    const recipientTokenAfter = await getTokenBalance(
+      program.provider.connection,
+      recipientToken
+    );
+    const claimedAmount = claimStatusState.totalClaimedAmount.sub(
+      claimStatusBefore ? claimStatusBefore.totalClaimedAmount : new BN(0)
+    );
+    expect(claimStatusState.recipient.toString()).eq(recipient.publicKey.toString());
+    expect((recipientTokenAfter - recipientTokenBefore).toString()).eq(
+      claimedAmount.toString()
+    );
+  }
+  return claimStatus;
+}
+
+export interface CloseRootEscrowClaimStatusParams {
+  rootEscrow: web3.PublicKey;
+  claimStatus: web3.PublicKey;
+  recipient: web3.Keypair;
+}
+
+export async function closeRootEscrowClaimStatus(
+  params: CloseRootEscrowClaimStatusParams
+) {
+  let { rootEscrow, claimStatus, recipient } = params;
+  const program = createLockerProgram(new Wallet(recipient));
+
+  await program.methods
+    .closeRootEscrowClaimStatus()
+    .accounts({
+      rootEscrow,
+      claimStatus,
+      recipient: recipient.publicKey,
+    })
+    .signers([recipient])
+    .rpc();
+
+  const claimStatusState = await program.provider.connection.getAccountInfo(
+    claimStatus
+  );
+  expect(claimStatusState).eq(null);
+}
+
+export async function closeRootEscrowClaimStatusFromClosedRoot(
+  params: CloseRootEscrowClaimStatusParams
+) {
+  let { rootEscrow, claimStatus, recipient } = params;
+  const program = createLockerProgram(new Wallet(recipient));
+
+  await program.methods
+    .closeRootEscrowClaimStatusFromClosedRoot()
+    .accounts({
+      rootEscrow,
+      claimStatus,
+      recipient: recipient.publicKey,
+    })
+    .signers([recipient])
+    .rpc();
+
+  const claimStatusState = await program.provider.connection.getAccountInfo(
+    claimStatus
+  );
+  expect(claimStatusState).eq(null);
+}
+
+export const LEAVES_PER_CLAIM_BITMAP = 65536;
+
+export interface CreateRootEscrowClaimBitmapParams {
+  rootEscrow: web3.PublicKey;
+  payer: web3.Keypair;
+  chunkIndex: BN;
+}
+
+export async function createRootEscrowClaimBitmap(
+  params: CreateRootEscrowClaimBitmapParams
+) {
+  let { rootEscrow, payer, chunkIndex } = params;
+  const program = createLockerProgram(new Wallet(payer));
+  const claimBitmap = deriveRootEscrowClaimBitmap(rootEscrow, chunkIndex);
+
+  await program.methods
+    .createRootEscrowClaimBitmap(chunkIndex)
+    .accounts({
+      rootEscrow,
+      claimBitmap,
+      payer: payer.publicKey,
+      systemProgram: web3.SystemProgram.programId,
+    })
+    .signers([payer])
+    .rpc();
+
+  return claimBitmap;
+}
+
+export interface ClaimFromRootEscrowBitmapParams {
+  isAssertion: boolean;
+  rootEscrow: web3.PublicKey;
+  vestingStartTime: BN;
+  cliffTime: BN;
+  frequency: BN;
+  cliffUnlockAmount: BN;
+  amountPerPeriod: BN;
+  numberOfPeriod: BN;
+  updateRecipientMode: number,
+  cancelMode: number,
+  recipient: web3.Keypair;
+  proof: Array<number>[];
+  leafIndex: BN;
+}
+
+export async function claimFromRootEscrowBitmap(
+  params: ClaimFromRootEscrowBitmapParams
+) {
+  let {
+    isAssertion,
+    rootEscrow,
+    frequency,
+    vestingStartTime,
+    recipient,
+    cliffTime,
+    amountPerPeriod,
+    proof,
+    numberOfPeriod,
+    cliffUnlockAmount,
+    updateRecipientMode,
+    cancelMode,
+    leafIndex,
+  } = params;
+  const program = createLockerProgram(new Wallet(recipient));
+  const rootEscrowState = await program.account.rootEscrow.fetch(rootEscrow);
+  const claimBitmap = deriveRootEscrowClaimBitmap(
+    rootEscrow,
+    leafIndex.divn(LEAVES_PER_CLAIM_BITMAP)
+  );
+
+  const tokenProgram =
+    rootEscrowState.tokenProgramFlag == ESCROW_USE_SPL_TOKEN
+      ? TOKEN_PROGRAM_ID
+      : TOKEN_2022_PROGRAM_ID;
+
+  const rootEscrowToken = getAssociatedTokenAddressSync(
+    rootEscrowState.tokenMint,
+    rootEscrow,
+    true,
+    tokenProgram,
+    ASSOCIATED_TOKEN_PROGRAM_ID
+  );
+  const recipientToken = (
+    await getOrCreateAssociatedTokenAccount(
+      program.provider.connection,
+      recipient,
+      rootEscrowState.tokenMint,
+      recipient.publicKey,
+      false,
+      "confirmed",
+      {
+        commitment: "confirmed",
+      },
+      tokenProgram
+    )
+  ).address;
+
+  let remainingAccountsInfo = null;
+  let remainingAccounts: AccountMeta[] = [];
+  if (rootEscrowState.tokenProgramFlag == 1) {
+    let inputTransferHookAccounts =
+      await TokenExtensionUtil.getExtraAccountMetasForTransferHook(
+        program.provider.connection,
+        rootEscrowState.tokenMint,
+        rootEscrowToken,
+        recipientToken,
+        rootEscrow,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+    [remainingAccountsInfo, remainingAccounts] = new RemainingAccountsBuilder()
+      .addSlice(
+        RemainingAccountsType.TransferHookEscrow,
+        inputTransferHookAccounts
+      )
+      .build();
+  }
+
+  const recipientTokenBefore = await getTokenBalance(
+    program.provider.connection,
+    recipientToken
+  );
+
+  await program.methods
+    .claimFromRootEscrowBitmap({
+      vestingStartTime,
+      cliffTime,
+      frequency,
+      amountPerPeriod,
+      numberOfPeriod,
+      cliffUnlockAmount,
+      updateRecipientMode,
+      cancelMode,
+      leafIndex,
+    }, proof, remainingAccountsInfo)
+    .accounts({
+      rootEscrow,
+      claimBitmap,
+      tokenMint: rootEscrowState.tokenMint,
+      rootEscrowToken,
+      recipient: recipient.publicKey,
+      recipientToken,
+      tokenProgram,
+    }).remainingAccounts(remainingAccounts ? remainingAccounts : []).signers([recipient])
+    .rpc();
+
+  if (isAssertion) {
+    const recipientTokenAfter = await getTokenBalance(
+      program.provider.connection,
+      recipientToken
+    );
+    expect((recipientTokenAfter - recipientTokenBefore).toString()).eq(
+      cliffUnlockAmount.add(amountPerPeriod.mul(numberOfPeriod)).toString()
+    );
+  }
+}
+
+export interface CloseRootEscrowClaimBitmapParams {
+  rootEscrow: web3.PublicKey;
+  claimBitmap: web3.PublicKey;
+  creator: web3.Keypair;
+}
+
+export async function closeRootEscrowClaimBitmap(
+  params: CloseRootEscrowClaimBitmapParams
+) {
+  let { rootEscrow, claimBitmap, creator } = params;
+  const program = createLockerProgram(new Wallet(creator));
+
+  await program.methods
+    .closeRootEscrowClaimBitmap()
+    .accounts({
+      rootEscrow,
+      claimBitmap,
+      creator: creator.publicKey,
+    })
+    .signers([creator])
+    .rpc();
+
+  const claimBitmapState = await program.provider.connection.getAccountInfo(
+    claimBitmap
+  );
+  expect(claimBitmapState).eq(null);
+}
+
+export interface DepositRootEscrowRentPoolParams {
+  isAssertion: boolean;
+  rootEscrow: web3.PublicKey;
+  creator: web3.Keypair;
+  amount: BN;
+}
+
+export async function depositRootEscrowRentPool(
+  params: DepositRootEscrowRentPoolParams
+) {
+  let { isAssertion, rootEscrow, creator, amount } = params;
+  const program = createLockerProgram(new Wallet(creator));
+  const rootEscrowStateBefore = await program.account.rootEscrow.fetch(
+    rootEscrow
+  );
+
+  await program.methods
+    .depositRootEscrowRentPool(amount)
+    .accounts({
+      rootEscrow,
+      creator: creator.publicKey,
+      systemProgram: web3.SystemProgram.programId,
+    })
+    .signers([creator])
+    .rpc();
+
+  if (isAssertion) {
+    const rootEscrowState = await program.account.rootEscrow.fetch(rootEscrow);
+    expect(rootEscrowState.rentPoolAmount.toString()).eq(
+      rootEscrowStateBefore.rentPoolAmount.add(amount).toString()
+    );
+  }
+}
+
+export interface ClawbackRootEscrowParams {
+  isAssertion: boolean;
+  rootEscrow: web3.PublicKey;
+  creator: web3.Keypair;
+  tokenMint?: web3.PublicKey;
+}
+
+export async function clawbackRootEscrow(params: ClawbackRootEscrowParams) {
+  let { isAssertion, rootEscrow, creator } = params;
+  const program = createLockerProgram(new Wallet(creator));
+  const { tokenMint, rootEscrowMint, tokenProgramFlag } =
+    await getRootEscrowMint(program, rootEscrow, params.tokenMint);
+  // accounting of the mint is kept by root escrow mint for multi mint root escrow
+  const fetchAccounting = () =>
+    rootEscrowMint
+      ? program.account.rootEscrowMint.fetch(rootEscrowMint)
+      : program.account.rootEscrow.fetch(rootEscrow);
+  let rootEscrowState = { ...(await fetchAccounting()), tokenMint, tokenProgramFlag };
+  let tokenProgram = rootEscrowState.tokenProgramFlag == 0 ? TOKEN_PROGRAM_ID : TOKEN_2022_PROGRAM_ID;
+  const creatorToken = getAssociatedTokenAddressSync(
+    rootEscrowState.tokenMint,
+    creator.publicKey,
+    false,
+    tokenProgram,
+    ASSOCIATED_TOKEN_PROGRAM_ID
+  );
+  const rootEscrowToken = getAssociatedTokenAddressSync(
+    rootEscrowState.tokenMint,
+    rootEscrow,
+    true,
+    tokenProgram,
+    ASSOCIATED_TOKEN_PROGRAM_ID
+  );
+
+  let remainingAccountsInfo = null;
+  let remainingAccounts: AccountMeta[] = [];
+  if (rootEscrowState.tokenProgramFlag == 1) {
+    let transferHookAccounts =
+      await TokenExtensionUtil.getExtraAccountMetasForTransferHook(
+        program.provider.connection,
+        rootEscrowState.tokenMint,
+        rootEscrowToken,
+        creatorToken,
+        rootEscrow,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+    [remainingAccountsInfo, remainingAccounts] = new RemainingAccountsBuilder()
+      .addSlice(
+        RemainingAccountsType.TransferHookEscrow,
+        transferHookAccounts
+      )
+      .build();
+  }
+
+  const creatorTokenBalanceBefore = await getTokenBalance(
+    program.provider.connection,
+    creatorToken
+  );
+
+  const accounts = {
+    rootEscrow,
+    tokenMint: rootEscrowState.tokenMint,
+    rootEscrowToken,
+    creator: creator.publicKey,
+    creatorToken,
+    tokenProgram,
+  };
+  const builder = rootEscrowMint
+    ? program.methods
+      .clawbackMultiMintRootEscrow(remainingAccountsInfo)
+      .accounts({ ...accounts, rootEscrowMint })
+    : program.methods.clawbackRootEscrow(remainingAccountsInfo).accounts(accounts);
+  await builder.remainingAccounts(remainingAccounts ? remainingAccounts : []).signers([creator]).rpc();
+
+  if (isAssertion) {
+    const rootEscrowStateAfter = await fetchAccounting();
+    const clawbackAmount = rootEscrowState.totalFundedAmount
+      .sub(rootEscrowState.totalDistributeAmount)
+      .sub(rootEscrowState.totalClawbackAmount);
+    expect(rootEscrowStateAfter.totalClawbackAmount.toString()).eq(
+      rootEscrowState.totalClawbackAmount.add(clawbackAmount).toString()
+    );
+    const creatorTokenBalance = await getTokenBalance(
+      program.provider.connection,
+      creatorToken
+    );
+    expect(creatorTokenBalance - creatorTokenBalanceBefore).eq(
+      clawbackAmount.toNumber()
+    );
+  }
+}
+
+export interface CloseRootEscrowParams {
+  isAssertion: boolean;
+  rootEscrow: web3.PublicKey;
+  creator: web3.Keypair;
+}
+
+export async function closeRootEscrow(params: CloseRootEscrowParams) {
+  let { isAssertion, rootEscrow, creator } = params;
+  const program = createLockerProgram(new Wallet(creator));
+  let rootEscrowState = await program.account.rootEscrow.fetch(rootEscrow);
+  let tokenProgram = rootEscrowState.tokenProgramFlag == 0 ? TOKEN_PROGRAM_ID : TOKEN_2022_PROGRAM_ID;
+  const creatorToken = getAssociatedTokenAddressSync(
+    rootEscrowState.tokenMint,
+    creator.publicKey,
+    false,
+    tokenProgram,
+    ASSOCIATED_TOKEN_PROGRAM_ID
+  );
+  const rootEscrowToken = getAssociatedTokenAddressSync(
+    rootEscrowState.tokenMint,
+    rootEscrow,
+    true,
+    tokenProgram,
+    ASSOCIATED_TOKEN_PROGRAM_ID
+  );
+
+  let remainingAccountsInfo = null;
+  let remainingAccounts: AccountMeta[] = [];
+  if (rootEscrowState.tokenProgramFlag == 1) {
+    let transferHookAccounts =
+      await TokenExtensionUtil.getExtraAccountMetasForTransferHook(
+        program.provider.connection,
         rootEscrowState.tokenMint,
-        payerToken,
         rootEscrowToken,
-        payerKP.publicKey,
+        creatorToken,
+        rootEscrow,
         TOKEN_2022_PROGRAM_ID
       );
 
     [remainingAccountsInfo, remainingAccounts] = new RemainingAccountsBuilder()
       .addSlice(
         RemainingAccountsType.TransferHookEscrow,
-        inputTransferHookAccounts
+        transferHookAccounts
       )
       .build();
   }
-  await program.methods.fundRootEscrow(rootEscrowState.maxClaimAmount, remainingAccountsInfo).accounts({
+
+  await program.methods.closeRootEscrow(remainingAccountsInfo).accounts({
     rootEscrow,
     tokenMint: rootEscrowState.tokenMint,
     rootEscrowToken,
-    payer: payerKP.publicKey,
-    payerToken,
+    creator: creator.publicKey,
+    creatorToken,
     tokenProgram,
-    systemProgram: web3.SystemProgram.programId,
-    associateTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-  }).remainingAccounts(remainingAccounts ? remainingAccounts : []).signers([payerKP]).rpc().catch(console.log).then(console.log);
+  }).remainingAccounts(remainingAccounts ? remainingAccounts : []).signers([creator]).rpc();
+
+  if (isAssertion) {
+    let rootEscrowStateAfter = await program.account.rootEscrow.fetchNullable(
+      rootEscrow
+    );
+    expect(rootEscrowStateAfter).eq(null);
+    let rootEscrowTokenState = await program.provider.connection.getAccountInfo(
+      rootEscrowToken
+    );
+    expect(rootEscrowTokenState).eq(null);
+  }
+}
+
+export interface CloseRootEscrowMintParams {
+  isAssertion: boolean;
+  rootEscrow: web3.PublicKey;
+  creator: web3.Keypair;
+  tokenMint: web3.PublicKey;
+}
+
+export async function closeRootEscrowMint(params: CloseRootEscrowMintParams) {
+  let { isAssertion, rootEscrow, creator, tokenMint } = params;
+  const program = createLockerProgram(new Wallet(creator));
+  const rootEscrowMint = deriveRootEscrowMint(rootEscrow, tokenMint);
+  let rootEscrowMintState = await program.account.rootEscrowMint.fetch(
+    rootEscrowMint
+  );
+  let tokenProgram = rootEscrowMintState.tokenProgramFlag == 0 ? TOKEN_PROGRAM_ID : TOKEN_2022_PROGRAM_ID;
+  const creatorToken = getAssociatedTokenAddressSync(
+    tokenMint,
+    creator.publicKey,
+    false,
+    tokenProgram,
+    ASSOCIATED_TOKEN_PROGRAM_ID
+  );
+  const rootEscrowToken = getAssociatedTokenAddressSync(
+    tokenMint,
+    rootEscrow,
+    true,
+    tokenProgram,
+    ASSOCIATED_TOKEN_PROGRAM_ID
+  );
+
+  let remainingAccountsInfo = null;
+  let remainingAccounts: AccountMeta[] = [];
+  if (rootEscrowMintState.tokenProgramFlag == 1) {
+    let transferHookAccounts =
+      await TokenExtensionUtil.getExtraAccountMetasForTransferHook(
+        program.provider.connection,
+        tokenMint,
+        rootEscrowToken,
+        creatorToken,
+        rootEscrow,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+    [remainingAccountsInfo, remainingAccounts] = new RemainingAccountsBuilder()
+      .addSlice(
+        RemainingAccountsType.TransferHookEscrow,
+        transferHookAccounts
+      )
+      .build();
+  }
+
+  const rootEscrowState = await program.account.rootEscrow.fetch(rootEscrow);
+
+  await program.methods.closeRootEscrowMint(remainingAccountsInfo).accounts({
+    rootEscrow,
+    rootEscrowMint,
+    tokenMint,
+    rootEscrowToken,
+    creator: creator.publicKey,
+    creatorToken,
+    tokenProgram,
+  }).remainingAccounts(remainingAccounts ? remainingAccounts : []).signers([creator]).rpc();
+
+  if (isAssertion) {
+    let rootEscrowMintStateAfter =
+      await program.account.rootEscrowMint.fetchNullable(rootEscrowMint);
+    expect(rootEscrowMintStateAfter).eq(null);
+    let rootEscrowTokenState = await program.provider.connection.getAccountInfo(
+      rootEscrowToken
+    );
+    expect(rootEscrowTokenState).eq(null);
+
+    // root escrow is closed with its last mint
+    let rootEscrowStateAfter = await program.account.rootEscrow.fetchNullable(
+      rootEscrow
+    );
+    if (rootEscrowState.totalMint.toNumber() == 1) {
+      expect(rootEscrowStateAfter).eq(null);
+    } else {
+      expect(rootEscrowStateAfter.totalMint.toNumber()).eq(
+        rootEscrowState.totalMint.toNumber() - 1
+      );
+    }
+  }
 }
 
 export interface CreateVestingEscrowFromRootParams {
@@ -669,6 +1830,8 @@ export interface CreateVestingEscrowFromRootParams {
   recipient: web3.PublicKey;
   proof: Array<number>[];
   payerKP: web3.Keypair;
+  leafIndex?: BN;
+  tokenMint?: web3.PublicKey;
 }
 
 export async function createVestingEscrowFromRoot(params: CreateVestingEscrowFromRootParams) {
@@ -686,10 +1849,22 @@ export async function createVestingEscrowFromRoot(params: CreateVestingEscrowFro
     updateRecipientMode,
     cancelMode,
     payerKP,
+    leafIndex,
   } = params;
   const program = createLockerProgram(new Wallet(payerKP));
-  const rootEscrowState = await program.account.rootEscrow.fetch(rootEscrow);
-  let base = deriveBase(rootEscrow, recipient);
+  const { tokenMint, rootEscrowMint, tokenProgramFlag } =
+    await getRootEscrowMint(program, rootEscrow, params.tokenMint);
+  const rootEscrowState = {
+    ...(await program.account.rootEscrow.fetch(rootEscrow)),
+    tokenMint,
+    tokenProgramFlag,
+  };
+  let base = deriveBase(
+    rootEscrow,
+    recipient,
+    leafIndex,
+    rootEscrowMint ? tokenMint : undefined
+  );
   let [escrow] = deriveEscrow(base, LOCKER_PROGRAM_ID);
 
   const tokenProgram =
@@ -733,30 +1908,45 @@ export async function createVestingEscrowFromRoot(params: CreateVestingEscrowFro
       .build();
   }
 
-  await program.methods
-    .createVestingEscrowFromRoot({
-      vestingStartTime,
-      cliffTime,
-      frequency,
-      amountPerPeriod,
-      numberOfPeriod,
-      cliffUnlockAmount,
-      updateRecipientMode,
-      cancelMode,
-    }, proof, remainingAccountsInfo)
-    .accounts({
-      rootEscrow,
-      base,
-      escrow,
-      escrowToken,
-      rootEscrowToken,
-      tokenMint: rootEscrowState.tokenMint,
-      payer: payerKP.publicKey,
-      recipient,
-      tokenProgram,
-      systemProgram: web3.SystemProgram.programId,
-      associateTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-    }).remainingAccounts(remainingAccounts ? remainingAccounts : []).signers([payerKP])
+  const vestingParams = {
+    vestingStartTime,
+    cliffTime,
+    frequency,
+    amountPerPeriod,
+    numberOfPeriod,
+    cliffUnlockAmount,
+    updateRecipientMode,
+    cancelMode,
+  };
+  const vestingParamsWithLeafIndex = {
+    ...vestingParams,
+    leafIndex: leafIndex ?? null,
+  };
+  const accounts = {
+    rootEscrow,
+    base,
+    escrow,
+    escrowToken,
+    rootEscrowToken,
+    tokenMint: rootEscrowState.tokenMint,
+    payer: payerKP.publicKey,
+    recipient,
+    tokenProgram,
+    systemProgram: web3.SystemProgram.programId,
+    associateTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+  };
+  const builder = rootEscrowMint
+    ? program.methods
+      .createVestingEscrowFromMultiMintRoot(vestingParamsWithLeafIndex, proof, remainingAccountsInfo)
+      .accounts({ ...accounts, rootEscrowMint })
+    : leafIndex
+      ? program.methods
+        .createVestingEscrowFromRootV2(vestingParamsWithLeafIndex, proof, remainingAccountsInfo)
+        .accounts(accounts)
+      : program.methods
+        .createVestingEscrowFromRoot(vestingParams, proof, remainingAccountsInfo)
+        .accounts(accounts);
+  await builder.remainingAccounts(remainingAccounts ? remainingAccounts : []).signers([payerKP])
     .rpc().catch(console.log).then(console.log);
 
   if (isAssertion) {
@@ -840,19 +2030,52 @@ export async function cancelVestingPlan(
       .build();
   }
 
-  await program.methods
-    .cancelVestingEscrow(remainingAccountsInfo)
-    .accounts({
-      escrow,
-      tokenMint: escrowState.tokenMint,
-      escrowToken,
-      rentReceiver,
-      creatorToken: creatorToken,
-      recipientToken: recipientToken,
-      signer: signer.publicKey,
-      tokenProgram,
-      memoProgram: MEMO_PROGRAM,
-    })
+  const accounts = {
+    escrow,
+    tokenMint: escrowState.tokenMint,
+    escrowToken,
+    rentReceiver,
+    creatorToken: creatorToken,
+    recipientToken: recipientToken,
+    signer: signer.publicKey,
+    tokenProgram,
+    memoProgram: MEMO_PROGRAM,
+  };
+  // escrows created from root escrow are cancelled to creator once their root escrow, or root
+  // escrow mint, is closed
+  let builder;
+  const rootEscrow = escrowState.rootEscrow;
+  if (rootEscrow.equals(web3.PublicKey.default)) {
+    builder = program.methods
+      .cancelVestingEscrow(remainingAccountsInfo)
+      .accounts(accounts);
+  } else {
+    const rootEscrowState = await program.account.rootEscrow.fetchNullable(
+      rootEscrow
+    );
+    const rootEscrowMint = deriveRootEscrowMint(
+      rootEscrow,
+      escrowState.tokenMint
+    );
+    if (rootEscrowState == null) {
+      builder = program.methods
+        .cancelVestingEscrowFromClosedRoot(remainingAccountsInfo)
+        .accounts({ ...accounts, rootEscrow });
+    } else if (
+      rootEscrowState.tokenMint.equals(web3.PublicKey.default) &&
+      (await program.provider.connection.getAccountInfo(rootEscrowMint)) == null
+    ) {
+      builder = program.methods
+        .cancelVestingEscrowFromClosedRootEscrowMint(remainingAccountsInfo)
+        .accounts({ ...accounts, rootEscrow, rootEscrowMint });
+    } else {
+      // fails while tokens can be returned to root escrow
+      builder = program.methods
+        .cancelVestingEscrow(remainingAccountsInfo)
+        .accounts(accounts);
+    }
+  }
+  await builder
     .preInstructions([
       ComputeBudgetProgram.setComputeUnitLimit({
         units: 400_000,
@@ -916,6 +2139,131 @@ export async function cancelVestingPlan(
   }
 }
 
+export interface CancelVestingEscrowFromRootParams {
+  isAssertion: boolean;
+  escrow: web3.PublicKey;
+  rentReceiver: web3.PublicKey;
+  recipientToken: web3.PublicKey;
+  signer: web3.Keypair;
+}
+
+export async function cancelVestingEscrowFromRoot(
+  params: CancelVestingEscrowFromRootParams
+) {
+  let { isAssertion, escrow, rentReceiver, recipientToken, signer } = params;
+  const program = createLockerProgram(new Wallet(signer));
+  const escrowState = await program.account.vestingEscrow.fetch(escrow);
+  const rootEscrow = escrowState.rootEscrow;
+  const { rootEscrowMint } = await getRootEscrowMint(
+    program,
+    rootEscrow,
+    escrowState.tokenMint
+  );
+  // accounting of the mint is kept by root escrow mint for multi mint root escrow
+  const fetchAccounting = () =>
+    rootEscrowMint
+      ? program.account.rootEscrowMint.fetch(rootEscrowMint)
+      : program.account.rootEscrow.fetch(rootEscrow);
+  const rootEscrowState = await fetchAccounting();
+  const tokenProgram =
+    escrowState.tokenProgramFlag == ESCROW_USE_SPL_TOKEN
+      ? TOKEN_PROGRAM_ID
+      : TOKEN_2022_PROGRAM_ID;
+
+  const escrowToken = getAssociatedTokenAddressSync(
+    escrowState.tokenMint,
+    escrow,
+    true,
+    tokenProgram,
+    ASSOCIATED_TOKEN_PROGRAM_ID
+  );
+  const rootEscrowToken = getAssociatedTokenAddressSync(
+    escrowState.tokenMint,
+    rootEscrow,
+    true,
+    tokenProgram,
+    ASSOCIATED_TOKEN_PROGRAM_ID
+  );
+
+  let remainingAccountsInfo = null;
+  let remainingAccounts: AccountMeta[] = [];
+  if (tokenProgram == TOKEN_2022_PROGRAM_ID) {
+    let cancelTransferHookAccounts =
+      await TokenExtensionUtil.getExtraAccountMetasForTransferHook(
+        program.provider.connection,
+        escrowState.tokenMint,
+        escrowToken,
+        recipientToken,
+        escrow,
+        tokenProgram
+      );
+
+    [remainingAccountsInfo, remainingAccounts] = new RemainingAccountsBuilder()
+      .addSlice(
+        RemainingAccountsType.TransferHookEscrow,
+        cancelTransferHookAccounts
+      )
+      .build();
+  }
+
+  const escrowTokenBalanceBefore = await getTokenBalance(
+    program.provider.connection,
+    escrowToken
+  );
+  const rootEscrowTokenBalanceBefore = await getTokenBalance(
+    program.provider.connection,
+    rootEscrowToken
+  );
+
+  const accounts = {
+    escrow,
+    rootEscrow,
+    tokenMint: escrowState.tokenMint,
+    escrowToken,
+    rootEscrowToken,
+    recipientToken,
+    rentReceiver,
+    signer: signer.publicKey,
+    tokenProgram,
+    memoProgram: MEMO_PROGRAM,
+  };
+  const builder = rootEscrowMint
+    ? program.methods
+      .cancelVestingEscrowFromMultiMintRoot(remainingAccountsInfo)
+      .accounts({ ...accounts, rootEscrowMint })
+    : program.methods
+      .cancelVestingEscrowFromRoot(remainingAccountsInfo)
+      .accounts(accounts);
+  await builder
+    .remainingAccounts(remainingAccounts ? remainingAccounts : [])
+    .signers([signer])
+    .rpc();
+
+  if (isAssertion) {
+    const escrowState = await program.account.vestingEscrow.fetch(escrow);
+    expect(escrowState.cancelledAt.toNumber()).greaterThan(0);
+
+    const escrowTokenAccount = await program.provider.connection.getAccountInfo(
+      escrowToken
+    );
+    expect(escrowTokenAccount).eq(null);
+
+    // unvested tokens are returned to root escrow and are no longer distributed
+    const rootEscrowTokenBalance = await getTokenBalance(
+      program.provider.connection,
+      rootEscrowToken
+    );
+    const returnedAmount =
+      rootEscrowTokenBalance - rootEscrowTokenBalanceBefore;
+    expect(returnedAmount).lte(escrowTokenBalanceBefore);
+
+    const rootEscrowStateAfter = await fetchAccounting();
+    expect(rootEscrowStateAfter.totalDistributeAmount.toString()).eq(
+      rootEscrowState.totalDistributeAmount.sub(new BN(returnedAmount)).toString()
+    );
+  }
+}
+
 export interface CloseVestingEscrowParams {
   isAssertion: boolean;
   creator: web3.Keypair;
@@ -948,8 +2296,13 @@ export async function closeVestingEscrow(params: CloseVestingEscrowParams) {
     ASSOCIATED_TOKEN_PROGRAM_ID
   );
 
-  let remainingAccountsInfo = null;
-  let remainingAccounts: AccountMeta[] = [];
+  const remainingAccountsBuilder = new RemainingAccountsBuilder();
+  // the escrow recipients account of a multi recipient escrow is closed with the escrow
+  if (escrowState.recipientFlag == 1) {
+    remainingAccountsBuilder.addSlice(RemainingAccountsType.EscrowRecipients, [
+      { pubkey: escrowState.recipient, isSigner: false, isWritable: true },
+    ]);
+  }
   if (escrowState.tokenProgramFlag == 1) {
     let cancelTransferHookAccounts =
       await TokenExtensionUtil.getExtraAccountMetasForTransferHook(
@@ -961,13 +2314,13 @@ export async function closeVestingEscrow(params: CloseVestingEscrowParams) {
         tokenProgram
       );
 
-    [remainingAccountsInfo, remainingAccounts] = new RemainingAccountsBuilder()
-      .addSlice(
-        RemainingAccountsType.TransferHookEscrow,
-        cancelTransferHookAccounts
-      )
-      .build();
+    remainingAccountsBuilder.addSlice(
+      RemainingAccountsType.TransferHookEscrow,
+      cancelTransferHookAccounts
+    );
   }
+  const [remainingAccountsInfo, remainingAccounts] =
+    remainingAccountsBuilder.build();
 
   await program.methods
     .closeVestingEscrow(remainingAccountsInfo)
@@ -1010,6 +2363,8 @@ export interface VestingEcrow {
   numberOfPeriod: BN;
   updateRecipientMode: number,
   cancelMode: number,
+  mint?: web3.PublicKey;
+  leafIndex?: BN;
 }
 
 export function getTotalDepsitAmount(escrow: VestingEcrow) {
@@ -12,6 +12,10 @@ export type NodeType = {
   numberOfPeriod: BN;
   updateRecipientMode: number,
   cancelMode: number,
+  // only for multi mint trees (leaf version 1)
+  mint?: web3.PublicKey;
+  // only for trees with several leaves per recipient (leaf version 2 and 3)
+  leafIndex?: BN;
 };
 export class EscrowRecipientTree {
   private readonly _tree: MerkleTree;
@@ -45,9 +49,13 @@ export class EscrowRecipientTree {
       vestingStartTime,
       updateRecipientMode,
       cancelMode,
+      mint,
+      leafIndex,
     } = node;
     const buf = Buffer.concat([
       recipient.toBuffer(),
+      mint ? mint.toBuffer() : Buffer.alloc(0),
+      leafIndex ? leafIndex.toArrayLike(Buffer, "le", 8) : Buffer.alloc(0),
       new BN(vestingStartTime).toArrayLike(Buffer, "le", 8),
       new BN(cliffTime).toArrayLike(Buffer, "le", 8),
       new BN(frequency).toArrayLike(Buffer, "le", 8),
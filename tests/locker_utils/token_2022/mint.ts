@@ -14,7 +14,9 @@ import {
   createInitializeInterestBearingMintInstruction,
   createInitializeMintCloseAuthorityInstruction,
   createInitializeMintInstruction,
+  createInitializePausableConfigInstruction,
   createInitializePermanentDelegateInstruction,
+  createInitializeScaledUiAmountConfigInstruction,
   createInitializeTransferFeeConfigInstruction,
   createInitializeTransferHookInstruction,
   ExtensionType,
@@ -24,6 +26,12 @@ import {
   thawAccount,
   TOKEN_2022_PROGRAM_ID,
 } from "@solana/spl-token";
+import {
+  createInitializeGroupInstruction,
+  createInitializeMemberInstruction,
+  TOKEN_GROUP_MEMBER_SIZE,
+  TOKEN_GROUP_SIZE,
+} from "@solana/spl-token-group";
 import { TEST_TRANSFER_HOOK_PROGRAM_ID } from "./token-extensions";
 import { createAndFundWallet } from "../../common";
 import {
@@ -47,6 +55,12 @@ const tokenDecimal = 8;
 
 let mintAuthority: web3.Keypair;
 
+// UI amount of a ScaledUiAmount mint is its amount multiplied by this multiplier
+export const SCALED_UI_AMOUNT_MULTIPLIER = 2;
+
+// type (2 bytes) + length (2 bytes) of a variable length extension
+const TLV_HEADER_SIZE = 4;
+
 export async function createMintTransaction(
   provider: AnchorProvider,
   UserKP: web3.Keypair,
@@ -61,6 +75,17 @@ export async function createMintTransaction(
   // Define the amount to be minted and the amount to be transferred, accounting for decimals
   let mintAmount = BigInt(1_000_000 * Math.pow(10, tokenDecimal)); // Mint 1,000,000 tokens
 
+  // A member must belong to another mint holding the group
+  let groupMint: web3.PublicKey | null = null;
+  if (extensions.includes(ExtensionType.TokenGroupMember)) {
+    groupMint = await createMintTransaction(
+      provider,
+      UserKP,
+      [ExtensionType.GroupPointer, ExtensionType.TokenGroup],
+      false
+    );
+  }
+
   mintAuthority = new web3.Keypair();
   let mintKeypair = new web3.Keypair();
   let TOKEN = mintKeypair.publicKey;
@@ -79,10 +104,19 @@ export async function createMintTransaction(
       UserKP,
       TOKEN,
       transferFeeConfigAuthority,
-      withdrawWithheldAuthority
+      withdrawWithheldAuthority,
+      groupMint
     );
 
-  let mintLen = getMintLen(extensions) + additionalLength;
+  // TokenGroup and TokenGroupMember are allocated by their initialize instructions
+  let mintLen =
+    getMintLen(
+      extensions.filter(
+        (extension) =>
+          extension != ExtensionType.TokenGroup &&
+          extension != ExtensionType.TokenGroupMember
+      )
+    ) + additionalLength;
   const mintLamports =
     await provider.connection.getMinimumBalanceForRentExemption(
       mintLen + rentReserveSpace
@@ -111,10 +145,15 @@ export async function createMintTransaction(
     ...postInstructions
   );
 
+  // group and member initialization require the mint authority signature
+  const signers =
+    rentReserveSpace > 0
+      ? [UserKP, mintKeypair, mintAuthority]
+      : [UserKP, mintKeypair];
   await sendAndConfirmTransaction(
     provider.connection,
     mintTransaction,
-    [UserKP, mintKeypair],
+    signers,
     undefined
   );
 
@@ -162,7 +201,8 @@ function createExtensionMintIx(
   UserKP: web3.Keypair,
   TOKEN: web3.PublicKey,
   transferFeeConfigAuthority: web3.Keypair,
-  withdrawWithheldAuthority: web3.Keypair
+  withdrawWithheldAuthority: web3.Keypair,
+  groupMint: web3.PublicKey | null
 ): {
   instructions: web3.TransactionInstruction[];
   postInstructions: web3.TransactionInstruction[];
@@ -173,7 +213,7 @@ function createExtensionMintIx(
   const postIx = [];
   let confidentialTransferMintSizePatch = 0;
   let confidentialTransferFeeConfigSizePatch = 0;
-  let groupPointerSize = 0;
+  let tokenGroupSize = 0;
 
   if (extensions.includes(ExtensionType.TransferFeeConfig)) {
     ix.push(
@@ -199,6 +239,27 @@ function createExtensionMintIx(
     );
   }
 
+  if (extensions.includes(ExtensionType.ScaledUiAmountConfig)) {
+    ix.push(
+      createInitializeScaledUiAmountConfigInstruction(
+        TOKEN,
+        UserKP.publicKey,
+        SCALED_UI_AMOUNT_MULTIPLIER,
+        TOKEN_2022_PROGRAM_ID
+      )
+    );
+  }
+
+  if (extensions.includes(ExtensionType.PausableConfig)) {
+    ix.push(
+      createInitializePausableConfigInstruction(
+        TOKEN,
+        UserKP.publicKey,
+        TOKEN_2022_PROGRAM_ID
+      )
+    );
+  }
+
   if (extensions.includes(ExtensionType.DefaultAccountState)) {
     ix.push(
       createInitializeDefaultAccountStateInstruction(
@@ -291,20 +352,20 @@ function createExtensionMintIx(
       )
     );
 
-    // This extension is not yet stable
-    //  Trying this https://solana.com/developers/courses/token-extensions/group-member#lab
-    //  However, the instruction always failed with error 0xc.
-    // groupPointerSize = TOKEN_GROUP_SIZE;
-    // postIx.push(
-    //   createInitializeGroupInstruction({
-    //     group: TOKEN,
-    //     maxSize: 10,
-    //     mint: TOKEN,
-    //     mintAuthority: UserKP.publicKey,
-    //     programId: TOKEN_2022_PROGRAM_ID,
-    //     updateAuthority: UserKP.publicKey,
-    //   })
-    // );
+  }
+
+  if (extensions.includes(ExtensionType.TokenGroup)) {
+    tokenGroupSize += TLV_HEADER_SIZE + TOKEN_GROUP_SIZE;
+    postIx.push(
+      createInitializeGroupInstruction({
+        group: TOKEN,
+        maxSize: BigInt(10),
+        mint: TOKEN,
+        mintAuthority: mintAuthority.publicKey,
+        programId: TOKEN_2022_PROGRAM_ID,
+        updateAuthority: UserKP.publicKey,
+      })
+    );
   }
 
   if (extensions.includes(ExtensionType.GroupMemberPointer)) {
@@ -318,13 +379,27 @@ function createExtensionMintIx(
     );
   }
 
+  if (extensions.includes(ExtensionType.TokenGroupMember)) {
+    tokenGroupSize += TLV_HEADER_SIZE + TOKEN_GROUP_MEMBER_SIZE;
+    postIx.push(
+      createInitializeMemberInstruction({
+        group: groupMint,
+        groupUpdateAuthority: UserKP.publicKey,
+        member: TOKEN,
+        memberMint: TOKEN,
+        memberMintAuthority: mintAuthority.publicKey,
+        programId: TOKEN_2022_PROGRAM_ID,
+      })
+    );
+  }
+
   return {
     instructions: ix,
     postInstructions: postIx,
     additionalLength:
       confidentialTransferMintSizePatch +
       confidentialTransferFeeConfigSizePatch,
-    rentReserveSpace: groupPointerSize,
+    rentReserveSpace: tokenGroupSize,
   };
 }
 
@@ -2,9 +2,12 @@ import { AccountMeta } from "@solana/web3.js";
 
 export enum RemainingAccountsType {
   TransferHookEscrow = "transferHookEscrow",
+  EscrowRecipients = "escrowRecipients",
 }
 
-type RemainingAccountsAnchorType = { transferHookEscrow: {} };
+type RemainingAccountsAnchorType =
+  | { transferHookEscrow: {} }
+  | { escrowRecipients: {} };
 
 export type RemainingAccountsSliceData = {
   accountsType: RemainingAccountsAnchorType;
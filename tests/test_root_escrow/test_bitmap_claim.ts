@@ -0,0 +1,181 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3 } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import { createAndFundWallet } from "../common";
+import {
+  claimFromRootEscrowBitmap,
+  closeRootEscrow,
+  closeRootEscrowClaimBitmap,
+  createLockerProgram,
+  createRootEscrow,
+  createRootEscrowClaimBitmap,
+  fundRootEscrow,
+  getMaxClaimAmount,
+  VestingEcrow,
+} from "../locker_utils";
+import { Keypair } from "@solana/web3.js";
+import { EscrowRecipientTree } from "../locker_utils/merkle_tree/EscrowRecipientTree";
+
+const provider = anchor.AnchorProvider.env();
+
+describe("Root escrow bitmap claim", () => {
+  let payer: web3.Keypair = Keypair.generate();
+
+  let recipients: web3.Keypair[] = [];
+  let allEscrows: VestingEcrow[] = [];
+  let maxNumNodes = 3;
+  let mint: web3.PublicKey;
+
+  before(async () => {
+    {
+      await createAndFundWallet(provider.connection, payer);
+    }
+
+    // everything unlocks at cliff, leaf index is the position of the leaf
+    for (let i = 0; i < maxNumNodes; i++) {
+      const result = await createAndFundWallet(provider.connection);
+      recipients.push(result.keypair);
+      allEscrows.push({
+        recipient: result.keypair.publicKey,
+        vestingStartTime: new BN(100),
+        cliffTime: new BN(200),
+        frequency: new BN(0),
+        cliffUnlockAmount: new BN(1000 * (i + 1)),
+        amountPerPeriod: new BN(0),
+        numberOfPeriod: new BN(0),
+        updateRecipientMode: 0,
+        cancelMode: 0,
+        leafIndex: new BN(i),
+      });
+    }
+
+    mint = await createMint(
+      provider.connection,
+      payer,
+      payer.publicKey,
+      null,
+      6,
+      web3.Keypair.generate(),
+      {
+        commitment: "confirmed",
+      },
+      TOKEN_PROGRAM_ID
+    );
+
+    const payerToken = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        payer.publicKey,
+        false,
+        "confirmed",
+        {
+          commitment: "confirmed",
+        },
+        TOKEN_PROGRAM_ID
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      payer,
+      mint,
+      payerToken,
+      payer,
+      getMaxClaimAmount(allEscrows).toNumber(),
+      [],
+      {
+        commitment: "confirmed",
+      },
+      TOKEN_PROGRAM_ID
+    );
+  });
+
+  it("Recipients claim from root escrow tracked by a claim bitmap", async () => {
+    const tree = new EscrowRecipientTree(allEscrows);
+    let rootEscrow = await createRootEscrow({
+      isAssertion: true,
+      tokenMint: mint,
+      ownerKeypair: payer,
+      maxClaimAmount: getMaxClaimAmount(allEscrows),
+      maxEscrow: new BN(maxNumNodes),
+      version: new BN(0),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      root: tree.getRoot(),
+      leafVersion: 2,
+      distributionMode: 2,
+    });
+
+    await fundRootEscrow({
+      isAssertion: true,
+      rootEscrow,
+      payerKP: payer,
+    });
+
+    const claimBitmap = await createRootEscrowClaimBitmap({
+      rootEscrow,
+      payer,
+      chunkIndex: new BN(0),
+    });
+
+    for (let i = 0; i < maxNumNodes; i++) {
+      const escrow = allEscrows[i];
+      let proofBuffers = tree.getProof(escrow);
+      let proof = [];
+      proofBuffers.forEach(function (value) {
+        proof.push(Array.from(new Uint8Array(value)));
+      });
+
+      const claimParams = {
+        isAssertion: true,
+        rootEscrow,
+        vestingStartTime: escrow.vestingStartTime,
+        cliffTime: escrow.cliffTime,
+        frequency: escrow.frequency,
+        cliffUnlockAmount: escrow.cliffUnlockAmount,
+        amountPerPeriod: escrow.amountPerPeriod,
+        numberOfPeriod: escrow.numberOfPeriod,
+        updateRecipientMode: escrow.updateRecipientMode,
+        cancelMode: escrow.cancelMode,
+        recipient: recipients[i],
+        proof,
+        leafIndex: escrow.leafIndex,
+      };
+      await claimFromRootEscrowBitmap(claimParams);
+
+      try {
+        await claimFromRootEscrowBitmap(claimParams);
+        expect.fail("should not claim twice");
+      } catch (error) {
+        expect(JSON.parse(JSON.stringify(error)).logs.join()).to.include(
+          "LeafAlreadyClaimed"
+        );
+      }
+    }
+
+    const program = createLockerProgram(new anchor.Wallet(payer));
+    const rootEscrowState = await program.account.rootEscrow.fetch(rootEscrow);
+    expect(rootEscrowState.totalEscrowCreated.toNumber()).eq(maxNumNodes);
+    expect(rootEscrowState.totalDistributeAmount.toString()).eq(
+      getMaxClaimAmount(allEscrows).toString()
+    );
+
+    await closeRootEscrowClaimBitmap({
+      rootEscrow,
+      claimBitmap,
+      creator: payer,
+    });
+    await closeRootEscrow({
+      isAssertion: true,
+      rootEscrow,
+      creator: payer,
+    });
+  });
+});
@@ -0,0 +1,320 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3 } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import {
+  createAndFundWallet,
+  getCurrentBlockTime,
+  sleep,
+} from "../common";
+import {
+  cancelVestingEscrowFromRoot,
+  cancelVestingPlan,
+  clawbackRootEscrow,
+  closeRootEscrow,
+  createRootEscrow,
+  createLockerProgram,
+  createVestingEscrowFromRoot,
+  deriveBase,
+  deriveEscrow,
+  fundRootEscrow,
+  getMaxClaimAmount,
+  LOCKER_PROGRAM_ID,
+  VestingEcrow,
+} from "../locker_utils";
+import { Keypair } from "@solana/web3.js";
+import { EscrowRecipientTree } from "../locker_utils/merkle_tree/EscrowRecipientTree";
+
+const provider = anchor.AnchorProvider.env();
+
+describe("Cancel escrow created from root escrow", () => {
+  let payer: web3.Keypair = Keypair.generate();
+
+  let allEscrows: VestingEcrow[] = [];
+  let recipientTokens: web3.PublicKey[] = [];
+  let maxNumNodes = 3;
+  let tree: EscrowRecipientTree;
+  let mint: web3.PublicKey;
+  let payerToken: web3.PublicKey;
+
+  before(async () => {
+    {
+      await createAndFundWallet(provider.connection, payer);
+    }
+
+    const currentBlockTime = await getCurrentBlockTime(provider.connection);
+    for (let i = 0; i < maxNumNodes; i++) {
+      const result = await createAndFundWallet(provider.connection);
+      // cliff is far in the future, so nothing is claimable on cancel
+      allEscrows.push({
+        recipient: result.keypair.publicKey,
+        vestingStartTime: new BN(currentBlockTime),
+        cliffTime: new BN(currentBlockTime + 10_000),
+        frequency: new BN(10),
+        cliffUnlockAmount: new BN(100),
+        amountPerPeriod: new BN(100),
+        numberOfPeriod: new BN(200),
+        updateRecipientMode: 0,
+        cancelMode: 1,
+      });
+    }
+
+    tree = new EscrowRecipientTree(allEscrows);
+
+    mint = await createMint(
+      provider.connection,
+      payer,
+      payer.publicKey,
+      null,
+      6,
+      web3.Keypair.generate(),
+      {
+        commitment: "confirmed",
+      },
+      TOKEN_PROGRAM_ID
+    );
+
+    payerToken = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        payer.publicKey,
+        false,
+        "confirmed",
+        {
+          commitment: "confirmed",
+        },
+        TOKEN_PROGRAM_ID
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      payer,
+      mint,
+      payerToken,
+      payer,
+      getMaxClaimAmount(allEscrows).toNumber(),
+      [],
+      {
+        commitment: "confirmed",
+      },
+      TOKEN_PROGRAM_ID
+    );
+
+    for (let i = 0; i < maxNumNodes; i++) {
+      recipientTokens.push(
+        (
+          await getOrCreateAssociatedTokenAccount(
+            provider.connection,
+            payer,
+            mint,
+            allEscrows[i].recipient,
+            false,
+            "confirmed",
+            {
+              commitment: "confirmed",
+            },
+            TOKEN_PROGRAM_ID
+          )
+        ).address
+      );
+    }
+  });
+
+  async function createEscrowFromRoot(rootEscrow: web3.PublicKey, i: number) {
+    let proofBuffers = tree.getProof({
+      vestingStartTime: allEscrows[i].vestingStartTime,
+      cliffTime: allEscrows[i].cliffTime,
+      frequency: allEscrows[i].frequency,
+      cliffUnlockAmount: allEscrows[i].cliffUnlockAmount,
+      amountPerPeriod: allEscrows[i].amountPerPeriod,
+      numberOfPeriod: allEscrows[i].numberOfPeriod,
+      updateRecipientMode: allEscrows[i].updateRecipientMode,
+      cancelMode: allEscrows[i].cancelMode,
+      recipient: allEscrows[i].recipient,
+    });
+    let proof = [];
+    proofBuffers.forEach(function (value) {
+      proof.push(Array.from(new Uint8Array(value)));
+    });
+
+    await createVestingEscrowFromRoot({
+      isAssertion: true,
+      rootEscrow,
+      vestingStartTime: allEscrows[i].vestingStartTime,
+      cliffTime: allEscrows[i].cliffTime,
+      frequency: allEscrows[i].frequency,
+      cliffUnlockAmount: allEscrows[i].cliffUnlockAmount,
+      amountPerPeriod: allEscrows[i].amountPerPeriod,
+      numberOfPeriod: allEscrows[i].numberOfPeriod,
+      updateRecipientMode: allEscrows[i].updateRecipientMode,
+      cancelMode: allEscrows[i].cancelMode,
+      recipient: allEscrows[i].recipient,
+      payerKP: payer,
+      proof,
+    });
+
+    let [escrow] = deriveEscrow(
+      deriveBase(rootEscrow, allEscrows[i].recipient),
+      LOCKER_PROGRAM_ID
+    );
+    return escrow;
+  }
+
+  it("Unvested tokens are returned to root escrow and clawed back", async () => {
+    const program = createLockerProgram(new anchor.Wallet(payer));
+    const currentBlockTime = await getCurrentBlockTime(
+      program.provider.connection
+    );
+    const clawbackDeadline = new BN(currentBlockTime).add(new BN(5));
+
+    let rootEscrow = await createRootEscrow({
+      isAssertion: true,
+      tokenMint: mint,
+      ownerKeypair: payer,
+      maxClaimAmount: getMaxClaimAmount(allEscrows),
+      maxEscrow: new BN(maxNumNodes),
+      version: new BN(0),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      root: tree.getRoot(),
+      clawbackDeadline,
+    });
+
+    await fundRootEscrow({
+      isAssertion: true,
+      rootEscrow,
+      payerKP: payer,
+    });
+
+    let escrow0 = await createEscrowFromRoot(rootEscrow, 0);
+    let escrow1 = await createEscrowFromRoot(rootEscrow, 1);
+    let escrow2 = await createEscrowFromRoot(rootEscrow, 2);
+    const escrowAmount = (i: number) =>
+      allEscrows[i].cliffUnlockAmount.add(
+        allEscrows[i].amountPerPeriod.mul(allEscrows[i].numberOfPeriod)
+      );
+
+    let escrowState = await program.account.vestingEscrow.fetch(escrow0);
+    expect(escrowState.rootEscrow.toString()).eq(rootEscrow.toString());
+
+    // the creator cancel path is not allowed for escrows created from root
+    try {
+      await cancelVestingPlan(
+        {
+          isAssertion: false,
+          escrow: escrow0,
+          rentReceiver: payer.publicKey,
+          creatorToken: recipientTokens[0],
+          recipientToken: recipientTokens[0],
+          signer: payer,
+        },
+        0,
+        0
+      );
+      expect.fail("should not cancel to creator");
+    } catch (error) {
+      expect(JSON.parse(JSON.stringify(error)).logs.join()).to.include(
+        "EscrowCreatedFromRoot"
+      );
+    }
+
+    // rent of the escrow token account goes to the creator of root escrow
+    try {
+      await cancelVestingEscrowFromRoot({
+        isAssertion: false,
+        escrow: escrow0,
+        rentReceiver: allEscrows[0].recipient,
+        recipientToken: recipientTokens[0],
+        signer: payer,
+      });
+      expect.fail("should not send rent to another account");
+    } catch (error) {
+      expect(JSON.parse(JSON.stringify(error)).logs.join()).to.include(
+        "ConstraintAddress"
+      );
+    }
+
+    await cancelVestingEscrowFromRoot({
+      isAssertion: true,
+      escrow: escrow0,
+      rentReceiver: payer.publicKey,
+      recipientToken: recipientTokens[0],
+      signer: payer,
+    });
+
+    const rootEscrowState = await program.account.rootEscrow.fetch(rootEscrow);
+    expect(rootEscrowState.totalDistributeAmount.toString()).eq(
+      escrowAmount(1).add(escrowAmount(2)).toString()
+    );
+
+    while (true) {
+      const currentBlockTime = await getCurrentBlockTime(
+        program.provider.connection
+      );
+      if (currentBlockTime > clawbackDeadline.toNumber()) {
+        break;
+      } else {
+        await sleep(1000);
+        console.log("Wait until clawback deadline");
+      }
+    }
+
+    await clawbackRootEscrow({
+      isAssertion: true,
+      rootEscrow,
+      creator: payer,
+    });
+
+    // tokens returned after clawback can be clawed back again
+    await cancelVestingEscrowFromRoot({
+      isAssertion: true,
+      escrow: escrow1,
+      rentReceiver: payer.publicKey,
+      recipientToken: recipientTokens[1],
+      signer: payer,
+    });
+
+    await clawbackRootEscrow({
+      isAssertion: true,
+      rootEscrow,
+      creator: payer,
+    });
+
+    const rootEscrowStateAfter = await program.account.rootEscrow.fetch(
+      rootEscrow
+    );
+    expect(rootEscrowStateAfter.totalDistributeAmount.toString()).eq(
+      escrowAmount(2).toString()
+    );
+    expect(rootEscrowStateAfter.totalClawbackAmount.toString()).eq(
+      rootEscrowStateAfter.totalFundedAmount.sub(escrowAmount(2)).toString()
+    );
+
+    await closeRootEscrow({
+      isAssertion: true,
+      rootEscrow,
+      creator: payer,
+    });
+
+    // once root escrow is closed, unvested tokens go to creator
+    await cancelVestingPlan(
+      {
+        isAssertion: true,
+        escrow: escrow2,
+        rentReceiver: payer.publicKey,
+        creatorToken: payerToken,
+        recipientToken: recipientTokens[2],
+        signer: payer,
+      },
+      0,
+      escrowAmount(2).toNumber()
+    );
+  });
+});
@@ -0,0 +1,212 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3 } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import {
+  createAndFundWallet,
+  getCurrentBlockTime,
+  sleep,
+} from "../common";
+import {
+  clawbackRootEscrow,
+  closeRootEscrow,
+  createRootEscrow,
+  createLockerProgram,
+  createVestingEscrowFromRoot,
+  fundRootEscrow,
+  getMaxClaimAmount,
+  VestingEcrow,
+} from "../locker_utils";
+import { Keypair } from "@solana/web3.js";
+import { EscrowRecipientTree } from "../locker_utils/merkle_tree/EscrowRecipientTree";
+
+const provider = anchor.AnchorProvider.env();
+
+describe("Root escrow clawback", () => {
+  let payer: web3.Keypair = Keypair.generate();
+
+  let allEscrows: VestingEcrow[] = [];
+  let maxNumNodes = 5;
+  let tree: EscrowRecipientTree;
+  let mint: web3.PublicKey;
+
+  before(async () => {
+    {
+      await createAndFundWallet(provider.connection, payer);
+    }
+
+    for (let i = 0; i < maxNumNodes; i++) {
+      const result = await createAndFundWallet(provider.connection);
+      allEscrows.push({
+        recipient: result.keypair.publicKey,
+        vestingStartTime: new BN(100),
+        cliffTime: new BN(200),
+        frequency: new BN(10),
+        cliffUnlockAmount: new BN(100),
+        amountPerPeriod: new BN(100),
+        numberOfPeriod: new BN(200),
+        updateRecipientMode: 0,
+        cancelMode: 0,
+      });
+    }
+
+    tree = new EscrowRecipientTree(allEscrows);
+
+    mint = await createMint(
+      provider.connection,
+      payer,
+      payer.publicKey,
+      null,
+      6,
+      web3.Keypair.generate(),
+      {
+        commitment: "confirmed",
+      },
+      TOKEN_PROGRAM_ID
+    );
+
+    const payerToken = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        payer.publicKey,
+        false,
+        "confirmed",
+        {
+          commitment: "confirmed",
+        },
+        TOKEN_PROGRAM_ID
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      payer,
+      mint,
+      payerToken,
+      payer,
+      getMaxClaimAmount(allEscrows).toNumber(),
+      [],
+      {
+        commitment: "confirmed",
+      },
+      TOKEN_PROGRAM_ID
+    );
+  });
+
+  async function createEscrowFromRoot(rootEscrow: web3.PublicKey, i: number) {
+    let proofBuffers = tree.getProof({
+      vestingStartTime: allEscrows[i].vestingStartTime,
+      cliffTime: allEscrows[i].cliffTime,
+      frequency: allEscrows[i].frequency,
+      cliffUnlockAmount: allEscrows[i].cliffUnlockAmount,
+      amountPerPeriod: allEscrows[i].amountPerPeriod,
+      numberOfPeriod: allEscrows[i].numberOfPeriod,
+      updateRecipientMode: allEscrows[i].updateRecipientMode,
+      cancelMode: allEscrows[i].cancelMode,
+      recipient: allEscrows[i].recipient,
+    });
+    let proof = [];
+    proofBuffers.forEach(function (value) {
+      proof.push(Array.from(new Uint8Array(value)));
+    });
+
+    await createVestingEscrowFromRoot({
+      isAssertion: false,
+      rootEscrow,
+      vestingStartTime: allEscrows[i].vestingStartTime,
+      cliffTime: allEscrows[i].cliffTime,
+      frequency: allEscrows[i].frequency,
+      cliffUnlockAmount: allEscrows[i].cliffUnlockAmount,
+      amountPerPeriod: allEscrows[i].amountPerPeriod,
+      numberOfPeriod: allEscrows[i].numberOfPeriod,
+      updateRecipientMode: allEscrows[i].updateRecipientMode,
+      cancelMode: allEscrows[i].cancelMode,
+      recipient: allEscrows[i].recipient,
+      payerKP: payer,
+      proof,
+    });
+  }
+
+  it("Claw back undistributed tokens after deadline", async () => {
+    const program = createLockerProgram(new anchor.Wallet(payer));
+    const currentBlockTime = await getCurrentBlockTime(
+      program.provider.connection
+    );
+    const clawbackDeadline = new BN(currentBlockTime).add(new BN(5));
+
+    let rootEscrow = await createRootEscrow({
+      isAssertion: true,
+      tokenMint: mint,
+      ownerKeypair: payer,
+      maxClaimAmount: getMaxClaimAmount(allEscrows),
+      maxEscrow: new BN(maxNumNodes),
+      version: new BN(0),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      root: tree.getRoot(),
+      clawbackDeadline,
+    });
+
+    await fundRootEscrow({
+      isAssertion: true,
+      rootEscrow,
+      payerKP: payer,
+    });
+
+    await createEscrowFromRoot(rootEscrow, 0);
+    await createEscrowFromRoot(rootEscrow, 1);
+
+    try {
+      await clawbackRootEscrow({
+        isAssertion: false,
+        rootEscrow,
+        creator: payer,
+      });
+      expect.fail("should not claw back before deadline");
+    } catch (error) {
+      expect(JSON.parse(JSON.stringify(error)).logs.join()).to.include(
+        "ClawbackDeadlineNotReached"
+      );
+    }
+
+    while (true) {
+      const currentBlockTime = await getCurrentBlockTime(
+        program.provider.connection
+      );
+      if (currentBlockTime > clawbackDeadline.toNumber()) {
+        break;
+      } else {
+        await sleep(1000);
+        console.log("Wait until clawback deadline");
+      }
+    }
+
+    await clawbackRootEscrow({
+      isAssertion: true,
+      rootEscrow,
+      creator: payer,
+    });
+
+    // escrow creation is blocked after the deadline
+    await createEscrowFromRoot(rootEscrow, 2);
+    const rootEscrowState = await program.account.rootEscrow.fetch(rootEscrow);
+    expect(rootEscrowState.totalEscrowCreated.toNumber()).eq(2);
+    expect(rootEscrowState.totalClawbackAmount.toString()).eq(
+      rootEscrowState.totalFundedAmount
+        .sub(rootEscrowState.totalDistributeAmount)
+        .toString()
+    );
+
+    await closeRootEscrow({
+      isAssertion: true,
+      rootEscrow,
+      creator: payer,
+    });
+  });
+});
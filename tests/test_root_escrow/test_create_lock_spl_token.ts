@@ -10,7 +10,7 @@ import { BN } from "bn.js";
 import {
   createAndFundWallet,
 } from "../common";
-import { createRootEscrow, fundRootEscrow, createVestingEscrowFromRoot, VestingEcrow, getMaxClaimAmount } from "../locker_utils";
+import { closeRootEscrow, createRootEscrow, fundRootEscrow, createVestingEscrowFromRoot, VestingEcrow, getMaxClaimAmount } from "../locker_utils";
 import {
   Keypair,
 } from "@solana/web3.js";
@@ -128,5 +128,12 @@ describe("Root escrow Create vesting with spl token", () => {
         proof
       });
     }
+
+    console.log("Close root escrow");
+    await closeRootEscrow({
+      isAssertion: true,
+      rootEscrow,
+      creator: payer,
+    });
   });
 });
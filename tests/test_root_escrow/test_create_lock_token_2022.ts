@@ -11,7 +11,7 @@ import { BN } from "bn.js";
 import {
   createAndFundWallet,
 } from "../common";
-import { createRootEscrow, fundRootEscrow, createVestingEscrowFromRoot, VestingEcrow, getMaxClaimAmount } from "../locker_utils";
+import { closeRootEscrow, createRootEscrow, fundRootEscrow, createVestingEscrowFromRoot, VestingEcrow, getMaxClaimAmount } from "../locker_utils";
 import {
   Keypair,
   PublicKey,
@@ -142,5 +142,12 @@ describe("Root escrow Create vesting with token 2022", () => {
         proof
       });
     }
+
+    console.log("Close root escrow");
+    await closeRootEscrow({
+      isAssertion: true,
+      rootEscrow,
+      creator: payer,
+    });
   });
 });
@@ -0,0 +1,234 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3 } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import { createAndFundWallet } from "../common";
+import {
+  claimFromRootEscrow,
+  closeRootEscrow,
+  closeRootEscrowClaimStatus,
+  closeRootEscrowClaimStatusFromClosedRoot,
+  createLockerProgram,
+  createRootEscrow,
+  createVestingEscrowFromRoot,
+  fundRootEscrow,
+  getMaxClaimAmount,
+  VestingEcrow,
+} from "../locker_utils";
+import { Keypair } from "@solana/web3.js";
+import { EscrowRecipientTree } from "../locker_utils/merkle_tree/EscrowRecipientTree";
+
+const provider = anchor.AnchorProvider.env();
+
+describe("Root escrow direct claim", () => {
+  let payer: web3.Keypair = Keypair.generate();
+
+  let recipients: web3.Keypair[] = [];
+  let allEscrows: VestingEcrow[] = [];
+  let maxNumNodes = 3;
+  let mint: web3.PublicKey;
+
+  before(async () => {
+    {
+      await createAndFundWallet(provider.connection, payer);
+    }
+
+    for (let i = 0; i < maxNumNodes; i++) {
+      const result = await createAndFundWallet(provider.connection);
+      recipients.push(result.keypair);
+      allEscrows.push({
+        recipient: result.keypair.publicKey,
+        vestingStartTime: new BN(100),
+        cliffTime: new BN(200),
+        frequency: new BN(10),
+        cliffUnlockAmount: new BN(100),
+        amountPerPeriod: new BN(100),
+        numberOfPeriod: new BN(200),
+        updateRecipientMode: 0,
+        cancelMode: 0,
+      });
+    }
+
+    mint = await createMint(
+      provider.connection,
+      payer,
+      payer.publicKey,
+      null,
+      6,
+      web3.Keypair.generate(),
+      {
+        commitment: "confirmed",
+      },
+      TOKEN_PROGRAM_ID
+    );
+
+    const payerToken = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        payer.publicKey,
+        false,
+        "confirmed",
+        {
+          commitment: "confirmed",
+        },
+        TOKEN_PROGRAM_ID
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      payer,
+      mint,
+      payerToken,
+      payer,
+      getMaxClaimAmount(allEscrows).toNumber(),
+      [],
+      {
+        commitment: "confirmed",
+      },
+      TOKEN_PROGRAM_ID
+    );
+  });
+
+  it("Recipients claim from root escrow without vesting escrow", async () => {
+    const tree = new EscrowRecipientTree(allEscrows);
+    let rootEscrow = await createRootEscrow({
+      isAssertion: true,
+      tokenMint: mint,
+      ownerKeypair: payer,
+      maxClaimAmount: getMaxClaimAmount(allEscrows),
+      maxEscrow: new BN(maxNumNodes),
+      version: new BN(0),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      root: tree.getRoot(),
+      distributionMode: 1,
+    });
+
+    await fundRootEscrow({
+      isAssertion: true,
+      rootEscrow,
+      payerKP: payer,
+    });
+
+    const program = createLockerProgram(new anchor.Wallet(payer));
+    let claimStatuses: web3.PublicKey[] = [];
+    let proofs = [];
+    for (let i = 0; i < maxNumNodes; i++) {
+      const escrow = allEscrows[i];
+      let proofBuffers = tree.getProof(escrow);
+      let proof = [];
+      proofBuffers.forEach(function (value) {
+        proof.push(Array.from(new Uint8Array(value)));
+      });
+      proofs.push(proof);
+
+      if (i == 0) {
+        // no vesting escrow can be created in direct claim mode
+        await createVestingEscrowFromRoot({
+          isAssertion: false,
+          rootEscrow,
+          vestingStartTime: escrow.vestingStartTime,
+          cliffTime: escrow.cliffTime,
+          frequency: escrow.frequency,
+          cliffUnlockAmount: escrow.cliffUnlockAmount,
+          amountPerPeriod: escrow.amountPerPeriod,
+          numberOfPeriod: escrow.numberOfPeriod,
+          updateRecipientMode: escrow.updateRecipientMode,
+          cancelMode: escrow.cancelMode,
+          recipient: escrow.recipient,
+          payerKP: payer,
+          proof,
+        });
+        const rootEscrowState = await program.account.rootEscrow.fetch(
+          rootEscrow
+        );
+        expect(rootEscrowState.totalEscrowCreated.toNumber()).eq(0);
+      }
+
+      // claim the cliff unlock amount, then the rest
+      for (const maxAmount of [
+        escrow.cliffUnlockAmount,
+        new BN(1_000_000),
+      ]) {
+        const claimStatus = await claimFromRootEscrow({
+          isAssertion: true,
+          rootEscrow,
+          vestingStartTime: escrow.vestingStartTime,
+          cliffTime: escrow.cliffTime,
+          frequency: escrow.frequency,
+          cliffUnlockAmount: escrow.cliffUnlockAmount,
+          amountPerPeriod: escrow.amountPerPeriod,
+          numberOfPeriod: escrow.numberOfPeriod,
+          updateRecipientMode: escrow.updateRecipientMode,
+          cancelMode: escrow.cancelMode,
+          recipient: recipients[i],
+          proof,
+          maxAmount,
+        });
+        if (maxAmount.eq(escrow.cliffUnlockAmount)) {
+          claimStatuses.push(claimStatus);
+        }
+      }
+    }
+
+    const rootEscrowState = await program.account.rootEscrow.fetch(rootEscrow);
+    expect(rootEscrowState.totalEscrowCreated.toNumber()).eq(maxNumNodes);
+    expect(rootEscrowState.totalDistributeAmount.toString()).eq(
+      getMaxClaimAmount(allEscrows).toString()
+    );
+
+    // recipients get rent of their claim status back once root escrow is fully distributed
+    for (let i = 0; i < maxNumNodes - 1; i++) {
+      await closeRootEscrowClaimStatus({
+        rootEscrow,
+        claimStatus: claimStatuses[i],
+        recipient: recipients[i],
+      });
+    }
+
+    // a closed claim status can't be used to claim the leaf again
+    const escrow = allEscrows[0];
+    try {
+      await claimFromRootEscrow({
+        isAssertion: false,
+        rootEscrow,
+        vestingStartTime: escrow.vestingStartTime,
+        cliffTime: escrow.cliffTime,
+        frequency: escrow.frequency,
+        cliffUnlockAmount: escrow.cliffUnlockAmount,
+        amountPerPeriod: escrow.amountPerPeriod,
+        numberOfPeriod: escrow.numberOfPeriod,
+        updateRecipientMode: escrow.updateRecipientMode,
+        cancelMode: escrow.cancelMode,
+        recipient: recipients[0],
+        proof: proofs[0],
+        maxAmount: new BN(1_000_000),
+      });
+      expect.fail("should not claim a leaf again");
+    } catch (error) {
+      expect(JSON.parse(JSON.stringify(error)).logs.join()).to.include(
+        "LeafAlreadyClaimed"
+      );
+    }
+
+    await closeRootEscrow({
+      isAssertion: true,
+      rootEscrow,
+      creator: payer,
+    });
+
+    // claim status left open is still closed to its recipient after root escrow is closed
+    await closeRootEscrowClaimStatusFromClosedRoot({
+      rootEscrow,
+      claimStatus: claimStatuses[maxNumNodes - 1],
+      recipient: recipients[maxNumNodes - 1],
+    });
+  });
+});
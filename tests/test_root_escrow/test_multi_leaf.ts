@@ -0,0 +1,159 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3 } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import { createAndFundWallet } from "../common";
+import {
+  createLockerProgram,
+  createRootEscrow,
+  createVestingEscrowFromRoot,
+  fundRootEscrow,
+  getMaxClaimAmount,
+  VestingEcrow,
+} from "../locker_utils";
+import { Keypair } from "@solana/web3.js";
+import { EscrowRecipientTree } from "../locker_utils/merkle_tree/EscrowRecipientTree";
+
+const provider = anchor.AnchorProvider.env();
+
+describe("Root escrow multiple leaves per recipient", () => {
+  let payer: web3.Keypair = Keypair.generate();
+
+  let allEscrows: VestingEcrow[] = [];
+  let maxNumNodes = 3;
+  let mint: web3.PublicKey;
+
+  before(async () => {
+    {
+      await createAndFundWallet(provider.connection, payer);
+    }
+
+    // every recipient holds a seed and a strategic grant with different schedules
+    for (let i = 0; i < maxNumNodes; i++) {
+      const result = await createAndFundWallet(provider.connection);
+      allEscrows.push({
+        recipient: result.keypair.publicKey,
+        vestingStartTime: new BN(100),
+        cliffTime: new BN(200),
+        frequency: new BN(10),
+        cliffUnlockAmount: new BN(100),
+        amountPerPeriod: new BN(100),
+        numberOfPeriod: new BN(200),
+        updateRecipientMode: 0,
+        cancelMode: 0,
+        leafIndex: new BN(0),
+      });
+      allEscrows.push({
+        recipient: result.keypair.publicKey,
+        vestingStartTime: new BN(300),
+        cliffTime: new BN(400),
+        frequency: new BN(20),
+        cliffUnlockAmount: new BN(50),
+        amountPerPeriod: new BN(50),
+        numberOfPeriod: new BN(100),
+        updateRecipientMode: 0,
+        cancelMode: 0,
+        leafIndex: new BN(1),
+      });
+    }
+
+    mint = await createMint(
+      provider.connection,
+      payer,
+      payer.publicKey,
+      null,
+      6,
+      web3.Keypair.generate(),
+      {
+        commitment: "confirmed",
+      },
+      TOKEN_PROGRAM_ID
+    );
+
+    const payerToken = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        payer.publicKey,
+        false,
+        "confirmed",
+        {
+          commitment: "confirmed",
+        },
+        TOKEN_PROGRAM_ID
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      payer,
+      mint,
+      payerToken,
+      payer,
+      getMaxClaimAmount(allEscrows).toNumber(),
+      [],
+      {
+        commitment: "confirmed",
+      },
+      TOKEN_PROGRAM_ID
+    );
+  });
+
+  it("Create an escrow for each leaf of a recipient", async () => {
+    const tree = new EscrowRecipientTree(allEscrows);
+    let rootEscrow = await createRootEscrow({
+      isAssertion: true,
+      tokenMint: mint,
+      ownerKeypair: payer,
+      maxClaimAmount: getMaxClaimAmount(allEscrows),
+      maxEscrow: new BN(allEscrows.length),
+      version: new BN(0),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      root: tree.getRoot(),
+      leafVersion: 2,
+    });
+
+    await fundRootEscrow({
+      isAssertion: true,
+      rootEscrow,
+      payerKP: payer,
+    });
+
+    for (const escrow of allEscrows) {
+      let proofBuffers = tree.getProof(escrow);
+      let proof = [];
+      proofBuffers.forEach(function (value) {
+        proof.push(Array.from(new Uint8Array(value)));
+      });
+      await createVestingEscrowFromRoot({
+        isAssertion: true,
+        rootEscrow,
+        vestingStartTime: escrow.vestingStartTime,
+        cliffTime: escrow.cliffTime,
+        frequency: escrow.frequency,
+        cliffUnlockAmount: escrow.cliffUnlockAmount,
+        amountPerPeriod: escrow.amountPerPeriod,
+        numberOfPeriod: escrow.numberOfPeriod,
+        updateRecipientMode: escrow.updateRecipientMode,
+        cancelMode: escrow.cancelMode,
+        recipient: escrow.recipient,
+        payerKP: payer,
+        proof,
+        leafIndex: escrow.leafIndex,
+      });
+    }
+
+    const program = createLockerProgram(new anchor.Wallet(payer));
+    const rootEscrowState = await program.account.rootEscrow.fetch(rootEscrow);
+    expect(rootEscrowState.leafVersion).eq(2);
+    expect(rootEscrowState.totalEscrowCreated.toNumber()).eq(
+      allEscrows.length
+    );
+  });
+});
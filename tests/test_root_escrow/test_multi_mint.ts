@@ -0,0 +1,192 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3 } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import { createAndFundWallet } from "../common";
+import {
+  closeRootEscrowMint,
+  createLockerProgram,
+  createMultiMintRootEscrow,
+  createRootEscrowMint,
+  createVestingEscrowFromRoot,
+  deriveRootEscrowMint,
+  fundRootEscrow,
+  getMaxClaimAmount,
+  VestingEcrow,
+} from "../locker_utils";
+import { Keypair } from "@solana/web3.js";
+import { EscrowRecipientTree } from "../locker_utils/merkle_tree/EscrowRecipientTree";
+
+const provider = anchor.AnchorProvider.env();
+
+describe("Root escrow multi mint", () => {
+  let payer: web3.Keypair = Keypair.generate();
+
+  let allEscrows: VestingEcrow[] = [];
+  let maxNumNodes = 3;
+  let mints: web3.PublicKey[] = [];
+  let tree: EscrowRecipientTree;
+
+  before(async () => {
+    {
+      await createAndFundWallet(provider.connection, payer);
+    }
+
+    for (let i = 0; i < 2; i++) {
+      const mint = await createMint(
+        provider.connection,
+        payer,
+        payer.publicKey,
+        null,
+        6,
+        web3.Keypair.generate(),
+        {
+          commitment: "confirmed",
+        },
+        TOKEN_PROGRAM_ID
+      );
+      mints.push(mint);
+    }
+
+    // every recipient gets a leaf per mint, with a different schedule
+    for (let i = 0; i < maxNumNodes; i++) {
+      const result = await createAndFundWallet(provider.connection);
+      for (let j = 0; j < mints.length; j++) {
+        allEscrows.push({
+          recipient: result.keypair.publicKey,
+          vestingStartTime: new BN(100),
+          cliffTime: new BN(200),
+          frequency: new BN(10),
+          cliffUnlockAmount: new BN(100 * (j + 1)),
+          amountPerPeriod: new BN(100 * (j + 1)),
+          numberOfPeriod: new BN(200),
+          updateRecipientMode: 0,
+          cancelMode: 0,
+          mint: mints[j],
+        });
+      }
+    }
+    tree = new EscrowRecipientTree(allEscrows);
+
+    for (const mint of mints) {
+      const payerToken = (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          payer,
+          mint,
+          payer.publicKey,
+          false,
+          "confirmed",
+          {
+            commitment: "confirmed",
+          },
+          TOKEN_PROGRAM_ID
+        )
+      ).address;
+      await mintTo(
+        provider.connection,
+        payer,
+        mint,
+        payerToken,
+        payer,
+        getMaxClaimAmount(
+          allEscrows.filter((escrow) => escrow.mint.equals(mint))
+        ).toNumber(),
+        [],
+        {
+          commitment: "confirmed",
+        },
+        TOKEN_PROGRAM_ID
+      );
+    }
+  });
+
+  it("Distribute two mints from one root escrow", async () => {
+    const program = createLockerProgram(new anchor.Wallet(payer));
+    let rootEscrow = await createMultiMintRootEscrow({
+      ownerKeypair: payer,
+      maxEscrow: new BN(allEscrows.length),
+      version: new BN(0),
+      root: tree.getRoot(),
+      leafVersion: 1,
+    });
+
+    for (const mint of mints) {
+      const mintEscrows = allEscrows.filter((escrow) =>
+        escrow.mint.equals(mint)
+      );
+      await createRootEscrowMint({
+        rootEscrow,
+        creator: payer,
+        tokenMint: mint,
+        maxClaimAmount: getMaxClaimAmount(mintEscrows),
+      });
+
+      await fundRootEscrow({
+        isAssertion: true,
+        rootEscrow,
+        payerKP: payer,
+        tokenMint: mint,
+      });
+    }
+
+    for (const escrow of allEscrows) {
+      let proofBuffers = tree.getProof(escrow);
+      let proof = [];
+      proofBuffers.forEach(function (value) {
+        proof.push(Array.from(new Uint8Array(value)));
+      });
+      await createVestingEscrowFromRoot({
+        isAssertion: true,
+        rootEscrow,
+        vestingStartTime: escrow.vestingStartTime,
+        cliffTime: escrow.cliffTime,
+        frequency: escrow.frequency,
+        cliffUnlockAmount: escrow.cliffUnlockAmount,
+        amountPerPeriod: escrow.amountPerPeriod,
+        numberOfPeriod: escrow.numberOfPeriod,
+        updateRecipientMode: escrow.updateRecipientMode,
+        cancelMode: escrow.cancelMode,
+        recipient: escrow.recipient,
+        payerKP: payer,
+        proof,
+        tokenMint: escrow.mint,
+      });
+    }
+
+    const rootEscrowState = await program.account.rootEscrow.fetch(rootEscrow);
+    expect(rootEscrowState.leafVersion).eq(1);
+    expect(rootEscrowState.totalMint.toNumber()).eq(mints.length);
+    expect(rootEscrowState.totalEscrowCreated.toNumber()).eq(
+      allEscrows.length
+    );
+
+    for (const mint of mints) {
+      const mintEscrows = allEscrows.filter((escrow) =>
+        escrow.mint.equals(mint)
+      );
+      const rootEscrowMintState = await program.account.rootEscrowMint.fetch(
+        deriveRootEscrowMint(rootEscrow, mint)
+      );
+      expect(rootEscrowMintState.totalDistributeAmount.toString()).eq(
+        getMaxClaimAmount(mintEscrows).toString()
+      );
+    }
+
+    // root escrow is closed with its last mint
+    for (const mint of mints) {
+      await closeRootEscrowMint({
+        isAssertion: true,
+        rootEscrow,
+        creator: payer,
+        tokenMint: mint,
+      });
+    }
+  });
+});
@@ -0,0 +1,228 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3 } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import { createAndFundWallet } from "../common";
+import {
+  createLockerProgram,
+  createRootEscrow,
+  createVestingEscrowFromRoot,
+  depositRootEscrowRentPool,
+  fundRootEscrow,
+  getMaxClaimAmount,
+  VestingEcrow,
+} from "../locker_utils";
+import { Keypair } from "@solana/web3.js";
+import { EscrowRecipientTree } from "../locker_utils/merkle_tree/EscrowRecipientTree";
+
+const provider = anchor.AnchorProvider.env();
+
+describe("Root escrow rent pool", () => {
+  let payer: web3.Keypair = Keypair.generate();
+
+  let recipients: web3.Keypair[] = [];
+  let allEscrows: VestingEcrow[] = [];
+  let maxNumNodes = 3;
+  let mint: web3.PublicKey;
+  let baseRent: number;
+
+  before(async () => {
+    {
+      await createAndFundWallet(provider.connection, payer);
+    }
+    baseRent = await provider.connection.getMinimumBalanceForRentExemption(8);
+
+    for (let i = 0; i < maxNumNodes; i++) {
+      const result = await createAndFundWallet(provider.connection);
+      recipients.push(result.keypair);
+      allEscrows.push({
+        recipient: result.keypair.publicKey,
+        vestingStartTime: new BN(100),
+        cliffTime: new BN(200),
+        frequency: new BN(10),
+        cliffUnlockAmount: new BN(100),
+        amountPerPeriod: new BN(100),
+        numberOfPeriod: new BN(200),
+        updateRecipientMode: 0,
+        cancelMode: 0,
+      });
+    }
+
+    mint = await createMint(
+      provider.connection,
+      payer,
+      payer.publicKey,
+      null,
+      6,
+      web3.Keypair.generate(),
+      {
+        commitment: "confirmed",
+      },
+      TOKEN_PROGRAM_ID
+    );
+
+    const payerToken = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        payer.publicKey,
+        false,
+        "confirmed",
+        {
+          commitment: "confirmed",
+        },
+        TOKEN_PROGRAM_ID
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      payer,
+      mint,
+      payerToken,
+      payer,
+      getMaxClaimAmount(allEscrows).muln(2).toNumber(),
+      [],
+      {
+        commitment: "confirmed",
+      },
+      TOKEN_PROGRAM_ID
+    );
+  });
+
+  it("Recipient creates own escrow with rent reimbursed", async () => {
+    const tree = new EscrowRecipientTree(allEscrows);
+    let rootEscrow = await createRootEscrow({
+      isAssertion: true,
+      tokenMint: mint,
+      ownerKeypair: payer,
+      maxClaimAmount: getMaxClaimAmount(allEscrows),
+      maxEscrow: new BN(maxNumNodes),
+      version: new BN(0),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      root: tree.getRoot(),
+    });
+
+    await fundRootEscrow({
+      isAssertion: true,
+      rootEscrow,
+      payerKP: payer,
+    });
+
+    await depositRootEscrowRentPool({
+      isAssertion: true,
+      rootEscrow,
+      creator: payer,
+      amount: new BN(web3.LAMPORTS_PER_SOL),
+    });
+
+    const program = createLockerProgram(new anchor.Wallet(payer));
+    for (let i = 0; i < maxNumNodes; i++) {
+      const escrow = allEscrows[i];
+      let proofBuffers = tree.getProof(escrow);
+      let proof = [];
+      proofBuffers.forEach(function (value) {
+        proof.push(Array.from(new Uint8Array(value)));
+      });
+
+      const balanceBefore = await provider.connection.getBalance(
+        recipients[i].publicKey
+      );
+      await createVestingEscrowFromRoot({
+        isAssertion: true,
+        rootEscrow,
+        vestingStartTime: escrow.vestingStartTime,
+        cliffTime: escrow.cliffTime,
+        frequency: escrow.frequency,
+        cliffUnlockAmount: escrow.cliffUnlockAmount,
+        amountPerPeriod: escrow.amountPerPeriod,
+        numberOfPeriod: escrow.numberOfPeriod,
+        updateRecipientMode: escrow.updateRecipientMode,
+        cancelMode: escrow.cancelMode,
+        recipient: escrow.recipient,
+        payerKP: recipients[i],
+        proof,
+      });
+      const balanceAfter = await provider.connection.getBalance(
+        recipients[i].publicKey
+      );
+      // recipient only pays the transaction fee and rent of base, which is never closed
+      expect(balanceBefore - balanceAfter).lessThanOrEqual(baseRent + 5000);
+    }
+
+    const rootEscrowState = await program.account.rootEscrow.fetch(rootEscrow);
+    expect(rootEscrowState.totalEscrowCreated.toNumber()).eq(maxNumNodes);
+    expect(rootEscrowState.rentPoolAmount.toNumber()).lessThan(
+      web3.LAMPORTS_PER_SOL
+    );
+  });
+
+  it("Operator creates escrows with rent paid by the rent pool", async () => {
+    const tree = new EscrowRecipientTree(allEscrows);
+    let rootEscrow = await createRootEscrow({
+      isAssertion: true,
+      tokenMint: mint,
+      ownerKeypair: payer,
+      maxClaimAmount: getMaxClaimAmount(allEscrows),
+      maxEscrow: new BN(maxNumNodes),
+      version: new BN(1),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      root: tree.getRoot(),
+    });
+
+    // rent pool is deposited when funding root escrow
+    await fundRootEscrow({
+      isAssertion: true,
+      rootEscrow,
+      payerKP: payer,
+      rentDeposit: new BN(web3.LAMPORTS_PER_SOL),
+    });
+
+    const program = createLockerProgram(new anchor.Wallet(payer));
+    let rootEscrowState = await program.account.rootEscrow.fetch(rootEscrow);
+    expect(rootEscrowState.rentPoolAmount.toNumber()).eq(
+      web3.LAMPORTS_PER_SOL
+    );
+
+    for (const escrow of allEscrows) {
+      let proofBuffers = tree.getProof(escrow);
+      let proof = [];
+      proofBuffers.forEach(function (value) {
+        proof.push(Array.from(new Uint8Array(value)));
+      });
+
+      const balanceBefore = await provider.connection.getBalance(
+        payer.publicKey
+      );
+      await createVestingEscrowFromRoot({
+        isAssertion: true,
+        rootEscrow,
+        vestingStartTime: escrow.vestingStartTime,
+        cliffTime: escrow.cliffTime,
+        frequency: escrow.frequency,
+        cliffUnlockAmount: escrow.cliffUnlockAmount,
+        amountPerPeriod: escrow.amountPerPeriod,
+        numberOfPeriod: escrow.numberOfPeriod,
+        updateRecipientMode: escrow.updateRecipientMode,
+        cancelMode: escrow.cancelMode,
+        recipient: escrow.recipient,
+        payerKP: payer,
+        proof,
+      });
+      const balanceAfter = await provider.connection.getBalance(
+        payer.publicKey
+      );
+      // operator only pays the transaction fee and rent of base, which is never closed
+      expect(balanceBefore - balanceAfter).lessThanOrEqual(baseRent + 5000);
+    }
+
+    rootEscrowState = await program.account.rootEscrow.fetch(rootEscrow);
+    expect(rootEscrowState.totalEscrowCreated.toNumber()).eq(maxNumNodes);
+  });
+});
@@ -0,0 +1,186 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3 } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import { createAndFundWallet } from "../common";
+import {
+  createLockerProgram,
+  createRootEscrow,
+  createVestingEscrowFromRoot,
+  fundRootEscrow,
+  getMaxClaimAmount,
+  updateRootEscrowRoot,
+  VestingEcrow,
+} from "../locker_utils";
+import { Keypair } from "@solana/web3.js";
+import { EscrowRecipientTree } from "../locker_utils/merkle_tree/EscrowRecipientTree";
+
+const provider = anchor.AnchorProvider.env();
+
+describe("Root escrow update root", () => {
+  let payer: web3.Keypair = Keypair.generate();
+
+  let allEscrows: VestingEcrow[] = [];
+  let maxNumNodes = 5;
+  let mint: web3.PublicKey;
+
+  before(async () => {
+    {
+      await createAndFundWallet(provider.connection, payer);
+    }
+
+    for (let i = 0; i < maxNumNodes; i++) {
+      const result = await createAndFundWallet(provider.connection);
+      allEscrows.push({
+        recipient: result.keypair.publicKey,
+        vestingStartTime: new BN(100),
+        cliffTime: new BN(200),
+        frequency: new BN(10),
+        cliffUnlockAmount: new BN(100),
+        amountPerPeriod: new BN(100),
+        numberOfPeriod: new BN(200),
+        updateRecipientMode: 0,
+        cancelMode: 0,
+      });
+    }
+
+    mint = await createMint(
+      provider.connection,
+      payer,
+      payer.publicKey,
+      null,
+      6,
+      web3.Keypair.generate(),
+      {
+        commitment: "confirmed",
+      },
+      TOKEN_PROGRAM_ID
+    );
+
+    const payerToken = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        payer.publicKey,
+        false,
+        "confirmed",
+        {
+          commitment: "confirmed",
+        },
+        TOKEN_PROGRAM_ID
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      payer,
+      mint,
+      payerToken,
+      payer,
+      getMaxClaimAmount(allEscrows).toNumber(),
+      [],
+      {
+        commitment: "confirmed",
+      },
+      TOKEN_PROGRAM_ID
+    );
+  });
+
+  it("Update root before any escrow is created", async () => {
+    // root escrow is created from a csv missing the last recipient
+    const wrongEscrows = allEscrows.slice(0, maxNumNodes - 1);
+    const wrongTree = new EscrowRecipientTree(wrongEscrows);
+    let rootEscrow = await createRootEscrow({
+      isAssertion: true,
+      tokenMint: mint,
+      ownerKeypair: payer,
+      maxClaimAmount: getMaxClaimAmount(wrongEscrows),
+      maxEscrow: new BN(wrongEscrows.length),
+      version: new BN(0),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      root: wrongTree.getRoot(),
+    });
+
+    await fundRootEscrow({
+      isAssertion: true,
+      rootEscrow,
+      payerKP: payer,
+    });
+
+    const tree = new EscrowRecipientTree(allEscrows);
+    await updateRootEscrowRoot({
+      isAssertion: true,
+      rootEscrow,
+      creator: payer,
+      root: tree.getRoot(),
+      maxClaimAmount: getMaxClaimAmount(allEscrows),
+      maxEscrow: new BN(maxNumNodes),
+    });
+
+    // fund the remaining amount
+    await fundRootEscrow({
+      isAssertion: true,
+      rootEscrow,
+      payerKP: payer,
+    });
+
+    const lastEscrow = allEscrows[maxNumNodes - 1];
+    let proofBuffers = tree.getProof({
+      vestingStartTime: lastEscrow.vestingStartTime,
+      cliffTime: lastEscrow.cliffTime,
+      frequency: lastEscrow.frequency,
+      cliffUnlockAmount: lastEscrow.cliffUnlockAmount,
+      amountPerPeriod: lastEscrow.amountPerPeriod,
+      numberOfPeriod: lastEscrow.numberOfPeriod,
+      updateRecipientMode: lastEscrow.updateRecipientMode,
+      cancelMode: lastEscrow.cancelMode,
+      recipient: lastEscrow.recipient,
+    });
+    let proof = [];
+    proofBuffers.forEach(function (value) {
+      proof.push(Array.from(new Uint8Array(value)));
+    });
+    await createVestingEscrowFromRoot({
+      isAssertion: true,
+      rootEscrow,
+      vestingStartTime: lastEscrow.vestingStartTime,
+      cliffTime: lastEscrow.cliffTime,
+      frequency: lastEscrow.frequency,
+      cliffUnlockAmount: lastEscrow.cliffUnlockAmount,
+      amountPerPeriod: lastEscrow.amountPerPeriod,
+      numberOfPeriod: lastEscrow.numberOfPeriod,
+      updateRecipientMode: lastEscrow.updateRecipientMode,
+      cancelMode: lastEscrow.cancelMode,
+      recipient: lastEscrow.recipient,
+      payerKP: payer,
+      proof,
+    });
+
+    // root can't be updated once distribution has started
+    try {
+      await updateRootEscrowRoot({
+        isAssertion: false,
+        rootEscrow,
+        creator: payer,
+        root: wrongTree.getRoot(),
+        maxClaimAmount: getMaxClaimAmount(allEscrows),
+        maxEscrow: new BN(maxNumNodes),
+      });
+      expect.fail("should not update root");
+    } catch (error) {
+      expect(JSON.parse(JSON.stringify(error)).logs.join()).to.include(
+        "RootEscrowDistributionStarted"
+      );
+    }
+
+    const program = createLockerProgram(new anchor.Wallet(payer));
+    const rootEscrowState = await program.account.rootEscrow.fetch(rootEscrow);
+    expect(rootEscrowState.totalEscrowCreated.toNumber()).eq(1);
+  });
+});
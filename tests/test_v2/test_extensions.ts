@@ -1,19 +1,37 @@
 import * as anchor from "@coral-xyz/anchor";
 import { web3 } from "@coral-xyz/anchor";
 import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
   createAssociatedTokenAccountIdempotent,
   ExtensionType,
+  getAssociatedTokenAddressSync,
+  getMint,
+  pause,
+  resume,
   TOKEN_2022_PROGRAM_ID,
+  transferChecked,
 } from "@solana/spl-token";
 import { BN } from "bn.js";
-import { createAndFundWallet, getCurrentBlockTime, sleep } from "../common";
 import {
+  createAndFundWallet,
+  getCurrentBlockTime,
+  invokeAndAssertError,
+  sleep,
+} from "../common";
+import {
+  cancelVestingPlan,
   claimTokenV2,
+  closeVestingEscrow,
   createLockerProgram,
   createVestingPlanV2,
+  getCpiEvents,
 } from "../locker_utils";
-import { assert } from "chai";
-import { ADMIN, createMintTransaction } from "../locker_utils/token_2022/mint";
+import { assert, expect } from "chai";
+import {
+  ADMIN,
+  createMintTransaction,
+  SCALED_UI_AMOUNT_MULTIPLIER,
+} from "../locker_utils/token_2022/mint";
 
 const provider = anchor.AnchorProvider.env();
 
@@ -45,6 +63,262 @@ describe("[V2] Test supported/unsupported Token Mint", () => {
     await check(TOKEN);
   });
 
+  it("ScaledUiAmount", async () => {
+    let extensions = [ExtensionType.ScaledUiAmountConfig];
+
+    TOKEN = await createMintTransaction(provider, UserKP, extensions);
+
+    await check(TOKEN);
+  });
+
+  it("ScaledUiAmount claim emits EventTransferUiAmount", async () => {
+    let extensions = [ExtensionType.ScaledUiAmountConfig];
+
+    TOKEN = await createMintTransaction(provider, UserKP, extensions);
+
+    const program = createLockerProgram(new anchor.Wallet(UserKP));
+    let currentBlockTime = await getCurrentBlockTime(
+      program.provider.connection
+    );
+    // fully unlocked at creation, so the claim doesn't wait for the cliff
+    let escrow = await createVestingPlanV2({
+      ownerKeypair: UserKP,
+      vestingStartTime: new BN(0),
+      tokenMint: TOKEN,
+      isAssertion: true,
+      cliffTime: new BN(currentBlockTime),
+      frequency: new BN(1),
+      cliffUnlockAmount: new BN(100_000),
+      amountPerPeriod: new BN(0),
+      numberOfPeriod: new BN(0),
+      recipient: RecipientKP.publicKey,
+      updateRecipientMode: 0,
+      cancelMode: 0,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+    });
+
+    let RecipientToken = await createAssociatedTokenAccountIdempotent(
+      provider.connection,
+      UserKP,
+      TOKEN,
+      RecipientKP.publicKey,
+      {},
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const signature = await claimTokenV2({
+      recipient: RecipientKP,
+      recipientToken: RecipientToken,
+      escrow,
+      maxAmount: new BN(1_000_000),
+      isAssertion: true,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+    });
+
+    const events = await getCpiEvents(program, signature);
+    const event = events.find((e) => e.name == "eventTransferUiAmount");
+    expect(event).not.undefined;
+    expect(event.data.amount.toNumber()).eq(100_000);
+    expect(event.data.escrow.toString()).eq(escrow.toString());
+    expect(event.data.destination.toString()).eq(RecipientToken.toString());
+
+    const { decimals } = await getMint(
+      provider.connection,
+      TOKEN,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    expect(parseFloat(event.data.uiAmount)).closeTo(
+      (100_000 * SCALED_UI_AMOUNT_MULTIPLIER) / Math.pow(10, decimals),
+      1e-9
+    );
+  });
+
+  it("Pausable", async () => {
+    let extensions = [ExtensionType.PausableConfig];
+
+    TOKEN = await createMintTransaction(provider, UserKP, extensions);
+
+    await check(TOKEN);
+  });
+
+  it("Pausable claim, cancel and close fail while mint is paused", async () => {
+    let extensions = [ExtensionType.PausableConfig];
+
+    TOKEN = await createMintTransaction(provider, UserKP, extensions);
+
+    const program = createLockerProgram(new anchor.Wallet(UserKP));
+    let currentBlockTime = await getCurrentBlockTime(
+      program.provider.connection
+    );
+    // fully unlocked at creation, so the claim doesn't wait for the cliff
+    let escrow = await createVestingPlanV2({
+      ownerKeypair: UserKP,
+      vestingStartTime: new BN(0),
+      tokenMint: TOKEN,
+      isAssertion: true,
+      cliffTime: new BN(currentBlockTime),
+      frequency: new BN(1),
+      cliffUnlockAmount: new BN(100_000),
+      amountPerPeriod: new BN(0),
+      numberOfPeriod: new BN(0),
+      recipient: RecipientKP.publicKey,
+      updateRecipientMode: 0,
+      cancelMode: 1,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+    });
+
+    const UserToken = getAssociatedTokenAddressSync(
+      TOKEN,
+      UserKP.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+    const EscrowToken = getAssociatedTokenAddressSync(
+      TOKEN,
+      escrow,
+      true,
+      TOKEN_2022_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    );
+    let RecipientToken = await createAssociatedTokenAccountIdempotent(
+      provider.connection,
+      UserKP,
+      TOKEN,
+      RecipientKP.publicKey,
+      {},
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    await pause(
+      provider.connection,
+      UserKP,
+      TOKEN,
+      UserKP,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    console.log("Claim token of paused mint");
+    await invokeAndAssertError(
+      async () => {
+        await claimTokenV2({
+          recipient: RecipientKP,
+          recipientToken: RecipientToken,
+          escrow,
+          maxAmount: new BN(1_000_000),
+          isAssertion: true,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        });
+      },
+      "Mint is paused",
+      true
+    );
+
+    console.log("Cancel escrow of paused mint");
+    await invokeAndAssertError(
+      async () => {
+        await cancelVestingPlan(
+          {
+            escrow,
+            isAssertion: false,
+            rentReceiver: UserKP.publicKey,
+            creatorToken: UserToken,
+            recipientToken: RecipientToken,
+            signer: UserKP,
+          },
+          100_000,
+          100_000
+        );
+      },
+      "Mint is paused",
+      true
+    );
+
+    await resume(
+      provider.connection,
+      UserKP,
+      TOKEN,
+      UserKP,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    console.log("Claim all tokens");
+    await claimTokenV2({
+      recipient: RecipientKP,
+      recipientToken: RecipientToken,
+      escrow,
+      maxAmount: new BN(1_000_000),
+      isAssertion: true,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+    });
+
+    // tokens wrongly sent to the escrow are transferred to creator on close
+    const { decimals } = await getMint(
+      provider.connection,
+      TOKEN,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await transferChecked(
+      provider.connection,
+      UserKP,
+      UserToken,
+      TOKEN,
+      EscrowToken,
+      UserKP,
+      1_000,
+      decimals,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    await pause(
+      provider.connection,
+      UserKP,
+      TOKEN,
+      UserKP,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    console.log("Close escrow of paused mint");
+    await invokeAndAssertError(
+      async () => {
+        await closeVestingEscrow({
+          escrow,
+          creator: UserKP,
+          isAssertion: true,
+        });
+      },
+      "Mint is paused",
+      true
+    );
+
+    await resume(
+      provider.connection,
+      UserKP,
+      TOKEN,
+      UserKP,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    console.log("Close escrow");
+    await closeVestingEscrow({
+      escrow,
+      creator: UserKP,
+      isAssertion: true,
+    });
+  });
+
   it("DefaultAccountState", async () => {
     // Define the extensions to be used by the mint
     let extensions = [ExtensionType.DefaultAccountState];
@@ -145,6 +419,25 @@ describe("[V2] Test supported/unsupported Token Mint", () => {
     await check(TOKEN);
   });
 
+  it("TokenGroup", async () => {
+    let extensions = [ExtensionType.GroupPointer, ExtensionType.TokenGroup];
+
+    TOKEN = await createMintTransaction(provider, UserKP, extensions);
+
+    await check(TOKEN);
+  });
+
+  it("TokenGroupMember", async () => {
+    let extensions = [
+      ExtensionType.GroupMemberPointer,
+      ExtensionType.TokenGroupMember,
+    ];
+
+    TOKEN = await createMintTransaction(provider, UserKP, extensions);
+
+    await check(TOKEN);
+  });
+
   async function check(TOKEN: web3.PublicKey, errorMsg = "Unsupported mint") {
     const program = createLockerProgram(new anchor.Wallet(UserKP));
     let currentBlockTime = await getCurrentBlockTime(
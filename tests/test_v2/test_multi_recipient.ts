@@ -0,0 +1,199 @@
+import * as anchor from "@coral-xyz/anchor";
+import { web3 } from "@coral-xyz/anchor";
+import {
+  createAssociatedTokenAccountIdempotent,
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+} from "@solana/spl-token";
+import { BN } from "bn.js";
+import { expect } from "chai";
+import { createAndFundWallet, getCurrentBlockTime, sleep } from "../common";
+import {
+  claimMultiRecipient,
+  claimTokenV2,
+  closeVestingEscrow,
+  createLockerProgram,
+  createMultiRecipientVestingPlan,
+  deriveEscrowRecipients,
+} from "../locker_utils";
+import { ADMIN, createMintTransaction } from "../locker_utils/token_2022/mint";
+
+const provider = anchor.AnchorProvider.env();
+
+describe("[V2] Multi recipient vesting escrow", () => {
+  let TOKEN: web3.PublicKey;
+  let UserKP: web3.Keypair;
+  let FirstRecipientKP: web3.Keypair;
+  let SecondRecipientKP: web3.Keypair;
+  let FirstRecipientToken: web3.PublicKey;
+  let SecondRecipientToken: web3.PublicKey;
+
+  let extensions: ExtensionType[];
+
+  before(async () => {
+    {
+      await createAndFundWallet(provider.connection, ADMIN);
+    }
+    {
+      const result = await createAndFundWallet(provider.connection);
+      UserKP = result.keypair;
+    }
+    {
+      const result = await createAndFundWallet(provider.connection);
+      FirstRecipientKP = result.keypair;
+    }
+    {
+      const result = await createAndFundWallet(provider.connection);
+      SecondRecipientKP = result.keypair;
+    }
+
+    extensions = [ExtensionType.TransferHook];
+
+    TOKEN = await createMintTransaction(
+      provider,
+      UserKP,
+      extensions,
+      true,
+      false
+    );
+
+    FirstRecipientToken = await createAssociatedTokenAccountIdempotent(
+      provider.connection,
+      UserKP,
+      TOKEN,
+      FirstRecipientKP.publicKey,
+      {},
+      TOKEN_2022_PROGRAM_ID
+    );
+    SecondRecipientToken = await createAssociatedTokenAccountIdempotent(
+      provider.connection,
+      UserKP,
+      TOKEN,
+      SecondRecipientKP.publicKey,
+      {},
+      TOKEN_2022_PROGRAM_ID
+    );
+  });
+
+  it("Reject shares not summing to 10000 bps", async () => {
+    try {
+      await createMultiRecipientVestingPlan({
+        ownerKeypair: UserKP,
+        vestingStartTime: new BN(0),
+        tokenMint: TOKEN,
+        isAssertion: false,
+        cliffTime: new BN(1),
+        frequency: new BN(1),
+        cliffUnlockAmount: new BN(100_000),
+        amountPerPeriod: new BN(50_000),
+        numberOfPeriod: new BN(2),
+        recipients: [
+          { recipient: FirstRecipientKP.publicKey, shareBps: 7_000 },
+          { recipient: SecondRecipientKP.publicKey, shareBps: 2_000 },
+        ],
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      });
+      expect.fail("should not create escrow");
+    } catch (error) {
+      expect(JSON.parse(JSON.stringify(error)).logs.join()).to.include(
+        "InvalidRecipientShares"
+      );
+    }
+  });
+
+  it("Full flow", async () => {
+    console.log("Create multi recipient vesting plan");
+    const program = createLockerProgram(new anchor.Wallet(UserKP));
+    let currentBlockTime = await getCurrentBlockTime(
+      program.provider.connection
+    );
+
+    const cliffTime = new BN(currentBlockTime).add(new BN(5));
+    let escrow = await createMultiRecipientVestingPlan({
+      ownerKeypair: UserKP,
+      vestingStartTime: new BN(0),
+      tokenMint: TOKEN,
+      isAssertion: true,
+      cliffTime,
+      frequency: new BN(1),
+      cliffUnlockAmount: new BN(100_000),
+      amountPerPeriod: new BN(50_000),
+      numberOfPeriod: new BN(2),
+      recipients: [
+        { recipient: FirstRecipientKP.publicKey, shareBps: 7_000 },
+        { recipient: SecondRecipientKP.publicKey, shareBps: 3_000 },
+      ],
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+    });
+
+    while (true) {
+      const currentBlockTime = await getCurrentBlockTime(
+        program.provider.connection
+      );
+      if (currentBlockTime > cliffTime.toNumber() + 2) {
+        break;
+      } else {
+        await sleep(1000);
+        console.log("Wait until vesting is finished");
+      }
+    }
+
+    console.log("Reject claim without escrow recipients account");
+    try {
+      await claimTokenV2({
+        recipient: FirstRecipientKP,
+        recipientToken: FirstRecipientToken,
+        escrow,
+        maxAmount: new BN(1_000_000),
+        isAssertion: false,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      });
+      expect.fail("should not claim without escrow recipients account");
+    } catch (error) {
+      expect(JSON.parse(JSON.stringify(error)).logs.join()).to.include(
+        "InvalidEscrowRecipients"
+      );
+    }
+
+    console.log("Claim token for each recipient");
+    await claimMultiRecipient({
+      recipient: FirstRecipientKP,
+      recipientToken: FirstRecipientToken,
+      escrow,
+      maxAmount: new BN(1_000_000),
+      isAssertion: true,
+    });
+    await claimMultiRecipient({
+      recipient: SecondRecipientKP,
+      recipientToken: SecondRecipientToken,
+      escrow,
+      maxAmount: new BN(1_000_000),
+      isAssertion: true,
+    });
+
+    const [escrowRecipients] = deriveEscrowRecipients(
+      escrow,
+      program.programId
+    );
+    const escrowRecipientsState =
+      await program.account.vestingEscrowRecipients.fetch(escrowRecipients);
+    expect(
+      escrowRecipientsState.recipients[0].totalClaimedAmount.toNumber()
+    ).eq(140_000);
+    expect(
+      escrowRecipientsState.recipients[1].totalClaimedAmount.toNumber()
+    ).eq(60_000);
+
+    console.log("Close vesting escrow");
+    await closeVestingEscrow({
+      escrow,
+      creator: UserKP,
+      isAssertion: true,
+    });
+    const escrowRecipientsStateAfter =
+      await program.account.vestingEscrowRecipients.fetchNullable(
+        escrowRecipients
+      );
+    expect(escrowRecipientsStateAfter).eq(null);
+  });
+});
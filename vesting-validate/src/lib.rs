@@ -0,0 +1,145 @@
+use std::fmt;
+
+/// Highest update recipient and cancel mode, from neither creator or recipient (0) to either
+/// creator and recipient (3)
+pub const MAX_ACTOR_MODE: u8 = 3;
+
+/// Schedule and modes of a vesting escrow, as in `CreateVestingEscrowParameters` of the locker
+/// program and in the rows of a merkle tree csv
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VestingParams {
+    pub vesting_start_time: u64,
+    pub cliff_time: u64,
+    pub frequency: u64,
+    pub cliff_unlock_amount: u64,
+    pub amount_per_period: u64,
+    pub number_of_period: u64,
+    pub update_recipient_mode: u8,
+    pub cancel_mode: u8,
+}
+
+/// Reason vesting params are rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VestingValidateError {
+    /// cliff time is before vesting start time
+    InvalidVestingStartTime {
+        vesting_start_time: u64,
+        cliff_time: u64,
+    },
+    InvalidUpdateRecipientMode(u8),
+    InvalidCancelMode(u8),
+    /// periods unlock tokens with a zero frequency
+    FrequencyIsZero,
+}
+
+impl fmt::Display for VestingValidateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidVestingStartTime {
+                vesting_start_time,
+                cliff_time,
+            } => write!(
+                f,
+                "cliff_time {} is before vesting_start_time {}",
+                cliff_time, vesting_start_time
+            ),
+            Self::InvalidUpdateRecipientMode(mode) => {
+                write!(f, "invalid update_recipient_mode {}", mode)
+            }
+            Self::InvalidCancelMode(mode) => write!(f, "invalid cancel_mode {}", mode),
+            Self::FrequencyIsZero => write!(f, "frequency is zero"),
+        }
+    }
+}
+
+impl std::error::Error for VestingValidateError {}
+
+impl VestingParams {
+    /// Every rule broken by the params, in the order the locker program checks them
+    pub fn get_errors(&self) -> Vec<VestingValidateError> {
+        let mut errors = Vec::new();
+        if self.cliff_time < self.vesting_start_time {
+            errors.push(VestingValidateError::InvalidVestingStartTime {
+                vesting_start_time: self.vesting_start_time,
+                cliff_time: self.cliff_time,
+            });
+        }
+        if self.update_recipient_mode > MAX_ACTOR_MODE {
+            errors.push(VestingValidateError::InvalidUpdateRecipientMode(
+                self.update_recipient_mode,
+            ));
+        }
+        if self.cancel_mode > MAX_ACTOR_MODE {
+            errors.push(VestingValidateError::InvalidCancelMode(self.cancel_mode));
+        }
+        if self.amount_per_period > 0 && self.number_of_period > 0 && self.frequency == 0 {
+            errors.push(VestingValidateError::FrequencyIsZero);
+        }
+        errors
+    }
+
+    /// Check the params, failing with the first rule broken
+    pub fn validate(&self) -> Result<(), VestingValidateError> {
+        match self.get_errors().first() {
+            Some(err) => Err(*err),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let params = VestingParams {
+            vesting_start_time: 0,
+            cliff_time: 10,
+            frequency: 1,
+            cliff_unlock_amount: 100,
+            amount_per_period: 10,
+            number_of_period: 10,
+            update_recipient_mode: 0,
+            cancel_mode: 3,
+        };
+        assert_eq!(params.validate(), Ok(()));
+
+        let params = VestingParams {
+            vesting_start_time: 20,
+            frequency: 0,
+            update_recipient_mode: 4,
+            cancel_mode: 5,
+            ..params
+        };
+        assert_eq!(
+            params.get_errors(),
+            vec![
+                VestingValidateError::InvalidVestingStartTime {
+                    vesting_start_time: 20,
+                    cliff_time: 10
+                },
+                VestingValidateError::InvalidUpdateRecipientMode(4),
+                VestingValidateError::InvalidCancelMode(5),
+                VestingValidateError::FrequencyIsZero,
+            ]
+        );
+        assert_eq!(
+            params.validate(),
+            Err(VestingValidateError::InvalidVestingStartTime {
+                vesting_start_time: 20,
+                cliff_time: 10
+            })
+        );
+
+        // periods without amount don't need a frequency
+        let params = VestingParams {
+            vesting_start_time: 0,
+            amount_per_period: 0,
+            update_recipient_mode: 0,
+            cancel_mode: 0,
+            ..params
+        };
+        assert_eq!(params.validate(), Ok(()));
+    }
+}